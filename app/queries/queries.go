@@ -6,6 +6,7 @@ import (
 
 	wasmkeeper "github.com/CosmWasm/wasmd/x/wasm/keeper"
 	wasmvmtypes "github.com/CosmWasm/wasmvm/v3/types"
+	sanctiontypes "github.com/SolanaPan/nnb/v1/x/sanction/types"
 	tokenfactorytypes "github.com/SolanaPan/nnb/v1/x/tokenfactory/types"
 	abci "github.com/cometbft/cometbft/abci/types"
 	"github.com/cosmos/cosmos-sdk/baseapp"
@@ -30,6 +31,7 @@ func init() {
 	setWhitelistedQuery("/osmosis.tokenfactory.v1beta1.Query/Params", &tokenfactorytypes.QueryParamsResponse{})
 	setWhitelistedQuery("/osmosis.tokenfactory.v1beta1.Query/DenomAuthorityMetadata", &tokenfactorytypes.QueryDenomAuthorityMetadataResponse{})
 	setWhitelistedQuery("/connect.oracle.v2.Query/GetPrice", &oracletypes.GetPriceResponse{})
+	setWhitelistedQuery("/mantrachain.sanction.v1.Query/Blacklist", &sanctiontypes.QueryBlacklistResponse{})
 }
 
 // setWhitelistedQuery sets the whitelisted query at the provided path.
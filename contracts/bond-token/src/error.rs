@@ -0,0 +1,52 @@
+//! Every variant renders as `<Code>: <description>`, where `<Code>` is the
+//! variant name. Codes are part of the contract's interface and never change
+//! meaning; clients should match on the part before the first colon.
+
+use cosmwasm_std::{Decimal, OverflowError, StdError, Timestamp};
+use rwa_common::CommonError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("Std: {0}")]
+    Std(#[from] StdError),
+
+    #[error("Overflow: {0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Cw20Base: {0}")]
+    Cw20Base(#[from] cw20_base::ContractError),
+
+    #[error(transparent)]
+    Common(#[from] CommonError),
+
+    #[error("NotPayingAgent: only the paying agent can {action}")]
+    NotPayingAgent { action: String },
+
+    #[error("NotTrustee: only the trustee can {action}")]
+    NotTrustee { action: String },
+
+    #[error("NotIssuerOrTrustee: only the issuer or the trustee can {action}")]
+    NotIssuerOrTrustee { action: String },
+
+    #[error("NotPayingAgentOrBondholder: only the paying agent or the bondholder can {action}")]
+    NotPayingAgentOrBondholder { action: String },
+
+    #[error("BondMatured: the bond matured at {maturity_date}")]
+    BondMatured { maturity_date: Timestamp },
+
+    #[error("BondNotMatured: the bond matures at {maturity_date}")]
+    BondNotMatured { maturity_date: Timestamp },
+
+    #[error("InvalidCouponPeriod: the coupon period must end after it starts")]
+    InvalidCouponPeriod {},
+
+    #[error("ExceedsOutstandingPrincipal: requested {requested}, outstanding {outstanding}")]
+    ExceedsOutstandingPrincipal {
+        requested: Decimal,
+        outstanding: Decimal,
+    },
+
+    #[error("CouponPaymentNotFound: no coupon payment with id {payment_id}")]
+    CouponPaymentNotFound { payment_id: String },
+}
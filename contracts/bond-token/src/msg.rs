@@ -0,0 +1,234 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Timestamp, Uint128};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use rwa_common::{
+    Cw20ExecuteMsg, Cw20QueryMsg, Drift, PauseCategory, PauseExecuteMsg, PauseQueryMsg,
+    RoleExecuteMsg, RoleQueryMsg, SanctionExecuteMsg, SanctionQueryMsg,
+};
+
+use crate::state::{
+    BondInfo, BondRating, BondTransfer, CalculationMethod, CouponPayment, InterestCalculation,
+    PaymentMethod, PaymentStatus, RedemptionRecord, RedemptionType, TransferType,
+};
+
+// Extended instantiate message
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub cw20_base: Cw20InstantiateMsg,
+    pub bond_info: BondInfo,
+    /// Administrator of the role registry, defaulting to the sender
+    pub admin: Option<String>,
+    /// Whether token movements are screened against the sanction blacklist,
+    /// defaulting to true
+    pub sanction_screening: Option<bool>,
+}
+
+// Extended execute messages: the standard CW20 messages, the shared role, pause
+// and sanction messages, and the bond specific ones, sharing the same JSON
+// namespace
+#[cw_serde]
+#[serde(untagged)]
+pub enum ExecuteMsg {
+    Cw20(Cw20ExecuteMsg),
+    Roles(RoleExecuteMsg),
+    Pause(PauseExecuteMsg),
+    Sanctions(SanctionExecuteMsg),
+    Bond(BondExecuteMsg),
+}
+
+// Bond specific messages. Messages that create a record take an optional id:
+// without one the next generated id is used. Either way the id is returned in
+// the response data.
+#[cw_serde]
+pub enum BondExecuteMsg {
+    PayCoupon {
+        payment_id: Option<String>,
+        coupon_period_start: Timestamp,
+        coupon_period_end: Timestamp,
+        coupon_amount: Decimal,
+        principal_amount: Decimal,
+        payment_method: PaymentMethod,
+    },
+    RedeemBonds {
+        redemption_id: Option<String>,
+        bondholder: String,
+        bonds_to_redeem: Uint128,
+        redemption_type: RedemptionType,
+        redemption_reason: String,
+    },
+    RecordTransfer {
+        transfer_id: Option<String>,
+        from: String,
+        to: String,
+        bonds_transferred: Uint128,
+        transfer_price: Decimal,
+        transfer_type: TransferType,
+        transfer_reason: String,
+    },
+    CalculateInterest {
+        calculation_id: Option<String>,
+        bondholder: String,
+        bonds_held: Uint128,
+        days_held: u32,
+        calculation_method: CalculationMethod,
+    },
+    UpdatePaymentStatus {
+        payment_id: String,
+        status: PaymentStatus,
+        transaction_hash: Option<String>,
+    },
+    UpdateBondRating {
+        new_rating: BondRating,
+    },
+    UpdateCollateralValue {
+        new_collateral_value: Decimal,
+    },
+}
+
+impl From<RoleExecuteMsg> for ExecuteMsg {
+    fn from(msg: RoleExecuteMsg) -> Self {
+        ExecuteMsg::Roles(msg)
+    }
+}
+
+impl From<SanctionExecuteMsg> for ExecuteMsg {
+    fn from(msg: SanctionExecuteMsg) -> Self {
+        ExecuteMsg::Sanctions(msg)
+    }
+}
+
+impl From<PauseExecuteMsg> for ExecuteMsg {
+    fn from(msg: PauseExecuteMsg) -> Self {
+        ExecuteMsg::Pause(msg)
+    }
+}
+
+impl BondExecuteMsg {
+    /// The circuit breaker that stops this message
+    pub fn pause_category(&self) -> PauseCategory {
+        match self {
+            BondExecuteMsg::PayCoupon { .. } => PauseCategory::RecordKeeping,
+            BondExecuteMsg::RedeemBonds { .. } => PauseCategory::Retirement,
+            BondExecuteMsg::RecordTransfer { .. } => PauseCategory::RecordKeeping,
+            BondExecuteMsg::CalculateInterest { .. } => PauseCategory::RecordKeeping,
+            BondExecuteMsg::UpdatePaymentStatus { .. } => PauseCategory::RecordKeeping,
+            BondExecuteMsg::UpdateBondRating { .. } => PauseCategory::RecordKeeping,
+            BondExecuteMsg::UpdateCollateralValue { .. } => PauseCategory::RecordKeeping,
+        }
+    }
+}
+
+impl From<BondExecuteMsg> for ExecuteMsg {
+    fn from(msg: BondExecuteMsg) -> Self {
+        ExecuteMsg::Bond(msg)
+    }
+}
+
+// Extended query messages
+#[cw_serde]
+#[derive(QueryResponses)]
+#[serde(untagged)]
+#[query_responses(nested)]
+pub enum QueryMsg {
+    Cw20(Cw20QueryMsg),
+    Roles(RoleQueryMsg),
+    Pause(PauseQueryMsg),
+    Sanctions(SanctionQueryMsg),
+    Bond(BondQueryMsg),
+}
+
+// Bond specific queries
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum BondQueryMsg {
+    #[returns(BondInfo)]
+    BondInfo {},
+    #[returns(CouponPayment)]
+    CouponPayment { payment_id: String },
+    #[returns(Vec<(String, CouponPayment)>)]
+    AllCouponPayments {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(RedemptionRecord)]
+    RedemptionRecord { redemption_id: String },
+    #[returns(Vec<(String, RedemptionRecord)>)]
+    AllRedemptionRecords {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(BondTransfer)]
+    BondTransfer { transfer_id: String },
+    #[returns(Vec<(String, BondTransfer)>)]
+    AllBondTransfers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(InterestCalculation)]
+    InterestCalculation { calculation_id: String },
+    #[returns(Vec<(String, InterestCalculation)>)]
+    AllInterestCalculations {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(BondholderInfoResponse)]
+    BondholderInfo { bondholder: String },
+    #[returns(Decimal)]
+    OutstandingPrincipal {},
+    #[returns(Decimal)]
+    AccruedInterest { bondholder: String },
+    #[returns(Timestamp)]
+    NextCouponDate {},
+    #[returns(BondYieldResponse)]
+    BondYield {},
+    #[returns(ReconcileSupplyResponse)]
+    ReconcileSupply {},
+}
+
+impl From<RoleQueryMsg> for QueryMsg {
+    fn from(msg: RoleQueryMsg) -> Self {
+        QueryMsg::Roles(msg)
+    }
+}
+
+impl From<SanctionQueryMsg> for QueryMsg {
+    fn from(msg: SanctionQueryMsg) -> Self {
+        QueryMsg::Sanctions(msg)
+    }
+}
+
+impl From<PauseQueryMsg> for QueryMsg {
+    fn from(msg: PauseQueryMsg) -> Self {
+        QueryMsg::Pause(msg)
+    }
+}
+
+impl From<BondQueryMsg> for QueryMsg {
+    fn from(msg: BondQueryMsg) -> Self {
+        QueryMsg::Bond(msg)
+    }
+}
+
+#[cw_serde]
+pub struct BondholderInfoResponse {
+    pub address: String,
+    pub bond_balance: Uint128,
+    pub face_value_held: Decimal,
+}
+
+#[cw_serde]
+pub struct BondYieldResponse {
+    pub coupon_rate: Decimal,
+    pub current_yield: Decimal,
+    pub yield_to_maturity: Decimal,
+}
+
+#[cw_serde]
+pub struct ReconcileSupplyResponse {
+    /// `outstanding_principal` against the face value of the CW20 total supply
+    pub principal: Drift<Decimal>,
+    pub reconciled: bool,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,186 @@
+//! Property tests: random sequences of operations run against the contract
+//! and against a plain model of its counters, which must agree after every
+//! step, including on the drift reported by `ReconcileSupply`.
+
+use cosmwasm_std::Decimal;
+use proptest::prelude::*;
+use rwa_common::Drift;
+
+use super::suite::{Suite, SuiteBuilder, DAY, PAYING_AGENT};
+use crate::state::RedemptionType;
+
+const HOLDERS: [&str; 3] = ["alice", "bob", "carol"];
+const FACE_VALUE: u128 = 1_000;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Redeem {
+        holder: usize,
+        bonds: u128,
+    },
+    RepayPrincipal {
+        amount: u128,
+    },
+    Transfer {
+        from: usize,
+        to: usize,
+        amount: u128,
+    },
+    Burn {
+        holder: usize,
+        amount: u128,
+    },
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let holder = 0..HOLDERS.len();
+    prop_oneof![
+        (holder.clone(), 1..300u128).prop_map(|(holder, bonds)| Op::Redeem { holder, bonds }),
+        (1..200_000u128).prop_map(|amount| Op::RepayPrincipal { amount }),
+        (holder.clone(), holder.clone(), 1..300u128).prop_map(|(from, to, amount)| Op::Transfer {
+            from,
+            to,
+            amount
+        }),
+        (holder, 1..100u128).prop_map(|(holder, amount)| Op::Burn { holder, amount }),
+    ]
+}
+
+/// What the contract should hold after the operations it accepted. Amounts
+/// of principal are whole currency units.
+#[derive(Debug)]
+struct Model {
+    balances: [u128; 3],
+    supply: u128,
+    outstanding: u128,
+    repaid: u128,
+    redeemed: u128,
+}
+
+impl Model {
+    fn new(balances: [u128; 3]) -> Self {
+        let supply = balances.iter().sum();
+        Model {
+            balances,
+            supply,
+            outstanding: supply * FACE_VALUE,
+            repaid: 0,
+            redeemed: 0,
+        }
+    }
+
+    /// Applies `op` and returns whether the contract is expected to accept it.
+    fn apply(&mut self, op: &Op) -> bool {
+        match *op {
+            Op::Redeem { holder, bonds } => {
+                let value = bonds * FACE_VALUE;
+                if value > self.outstanding || self.balances[holder] < bonds {
+                    return false;
+                }
+                self.balances[holder] -= bonds;
+                self.supply -= bonds;
+                self.outstanding -= value;
+                self.redeemed += value;
+                true
+            }
+            Op::RepayPrincipal { amount } => {
+                if amount > self.outstanding {
+                    return false;
+                }
+                self.outstanding -= amount;
+                self.repaid += amount;
+                true
+            }
+            Op::Transfer { from, to, amount } => {
+                if self.balances[from] < amount {
+                    return false;
+                }
+                self.balances[from] -= amount;
+                self.balances[to] += amount;
+                true
+            }
+            Op::Burn { holder, amount } => {
+                if self.balances[holder] < amount {
+                    return false;
+                }
+                self.balances[holder] -= amount;
+                self.supply -= amount;
+                true
+            }
+        }
+    }
+}
+
+fn dec(value: u128) -> Decimal {
+    Decimal::from_ratio(value, 1u128)
+}
+
+fn execute(suite: &mut Suite, step: usize, op: &Op) -> bool {
+    let res = match *op {
+        Op::Redeem { holder, bonds } => suite.redeem_bonds(
+            HOLDERS[holder],
+            &format!("r-{step}"),
+            HOLDERS[holder],
+            bonds,
+            RedemptionType::EarlyRedemption,
+        ),
+        Op::RepayPrincipal { amount } => {
+            let start = suite.block_time();
+            suite.pay_coupon(
+                PAYING_AGENT,
+                &format!("c-{step}"),
+                start,
+                start.plus_seconds(180 * DAY),
+                Decimal::zero(),
+                dec(amount),
+            )
+        }
+        Op::Transfer { from, to, amount } => suite.transfer(HOLDERS[from], HOLDERS[to], amount),
+        Op::Burn { holder, amount } => suite.burn(HOLDERS[holder], amount),
+    };
+    res.is_ok()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn counters_follow_the_model(
+        balances in prop::array::uniform3(0..1_000u128),
+        ops in prop::collection::vec(op(), 1..40),
+    ) {
+        let mut builder = SuiteBuilder::new();
+        for (holder, amount) in HOLDERS.iter().zip(balances) {
+            builder = builder.with_bonds(holder, amount);
+        }
+        let mut suite = builder.build();
+        let mut model = Model::new(balances);
+        let issued_principal = model.outstanding;
+
+        for (step, op) in ops.iter().enumerate() {
+            let expected = model.apply(op);
+            prop_assert_eq!(execute(&mut suite, step, op), expected, "{:?}", op);
+
+            let info = suite.bond_info();
+            prop_assert_eq!(info.outstanding_principal, dec(model.outstanding));
+            prop_assert_eq!(info.total_principal_repaid, dec(model.repaid));
+            prop_assert_eq!(suite.total_supply().u128(), model.supply);
+            for (holder, balance) in HOLDERS.iter().zip(model.balances) {
+                prop_assert_eq!(suite.balance(holder).u128(), balance);
+            }
+
+            // Principal is conserved; the face value of the supply drifts
+            // from it by exactly what the model predicts
+            prop_assert_eq!(
+                model.outstanding + model.repaid + model.redeemed,
+                issued_principal
+            );
+            let report = suite.reconcile_supply();
+            prop_assert_eq!(
+                &report.principal,
+                &Drift::between(dec(model.outstanding), dec(model.supply * FACE_VALUE))
+            );
+            prop_assert_eq!(report.reconciled, model.outstanding == model.supply * FACE_VALUE);
+        }
+    }
+}
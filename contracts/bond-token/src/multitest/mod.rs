@@ -0,0 +1,4 @@
+mod invariants;
+mod sanctions;
+mod suite;
+mod tests;
@@ -0,0 +1,88 @@
+//! Sanction screening. Multi-test does not serve the sanction module's
+//! stargate query, so these tests call the entry points directly and let
+//! `MockSanctionQuerier` answer the blacklist.
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{OwnedDeps, Response, Uint128};
+use rwa_common::sanctions::testing::{mock_dependencies_with_blacklist, MockSanctionQuerier};
+use rwa_common::{CommonError, Cw20ExecuteMsg};
+
+use super::suite::{SuiteBuilder, ADMIN, PAYING_AGENT};
+use crate::contract::{execute, instantiate};
+use crate::error::ContractError;
+use crate::msg::{BondExecuteMsg, ExecuteMsg};
+use crate::state::RedemptionType;
+
+const MALLORY: &str = "mallory";
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockSanctionQuerier>;
+
+fn setup() -> Deps {
+    let mut deps = mock_dependencies_with_blacklist(&[MALLORY]);
+    let mut msg = SuiteBuilder::new()
+        .with_bonds("alice", 600)
+        .with_bonds(MALLORY, 400)
+        .instantiate_msg(mock_env().block.time);
+    msg.sanction_screening = None;
+    instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+    deps
+}
+
+fn execute_as(
+    deps: &mut Deps,
+    sender: &str,
+    msg: impl Into<ExecuteMsg>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        msg.into(),
+    )
+}
+
+fn redeem(bondholder: &str, bonds: u128) -> BondExecuteMsg {
+    BondExecuteMsg::RedeemBonds {
+        redemption_id: None,
+        bondholder: bondholder.to_string(),
+        bonds_to_redeem: Uint128::new(bonds),
+        redemption_type: RedemptionType::EarlyRedemption,
+        redemption_reason: "liquidity".to_string(),
+    }
+}
+
+fn sanctioned() -> ContractError {
+    ContractError::Common(CommonError::Sanctioned {
+        address: MALLORY.to_string(),
+    })
+}
+
+#[test]
+fn sanctioned_bondholders_cannot_redeem() {
+    let mut deps = setup();
+
+    let err = execute_as(&mut deps, MALLORY, redeem(MALLORY, 100)).unwrap_err();
+    assert_eq!(err, sanctioned());
+
+    // Not even through the paying agent
+    let err = execute_as(&mut deps, PAYING_AGENT, redeem(MALLORY, 100)).unwrap_err();
+    assert_eq!(err, sanctioned());
+
+    execute_as(&mut deps, PAYING_AGENT, redeem("alice", 100)).unwrap();
+}
+
+#[test]
+fn sanctioned_bondholders_cannot_transfer() {
+    let mut deps = setup();
+
+    let err = execute_as(
+        &mut deps,
+        MALLORY,
+        ExecuteMsg::Cw20(Cw20ExecuteMsg::Transfer {
+            recipient: "alice".to_string(),
+            amount: Uint128::new(10),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, sanctioned());
+}
@@ -0,0 +1,503 @@
+//! Test fixture for the bond contract: a `SuiteBuilder` that instantiates the
+//! contract in a multi-test `App`, and a `Suite` with one helper per message
+//! so tests read as a sequence of actions.
+
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{Addr, Decimal, Empty, Timestamp, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, TokenInfoResponse};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use rwa_common::pause::PauseStatusResponse;
+use rwa_common::{
+    Cw20ExecuteMsg, Cw20QueryMsg, PauseCategory, PauseExecuteMsg, PauseQueryMsg, RoleExecuteMsg,
+    RoleQueryMsg,
+};
+
+use crate::msg::{
+    BondExecuteMsg, BondQueryMsg, BondYieldResponse, BondholderInfoResponse, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, QueryMsg, ReconcileSupplyResponse,
+};
+use crate::state::{
+    BondInfo, BondRating, BondTransfer, BondType, CalculationMethod, CollateralType,
+    CouponFrequency, CouponPayment, InterestCalculation, PaymentMethod, PaymentStatus,
+    RedemptionRecord, RedemptionType, TransferType,
+};
+
+pub const ADMIN: &str = "admin";
+pub const ISSUER: &str = "issuer";
+pub const TRUSTEE: &str = "trustee";
+pub const PAYING_AGENT: &str = "agent";
+
+pub const DAY: u64 = 24 * 60 * 60;
+pub const YEAR: u64 = 365 * DAY;
+
+fn contract_bond() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_migrate(crate::contract::migrate);
+    Box::new(contract)
+}
+
+pub struct SuiteBuilder {
+    initial_balances: Vec<Cw20Coin>,
+    face_value: Decimal,
+    coupon_rate: Decimal,
+    term: u64,
+}
+
+impl SuiteBuilder {
+    pub fn new() -> Self {
+        Self {
+            initial_balances: vec![],
+            face_value: Decimal::from_ratio(1_000u128, 1u128),
+            coupon_rate: Decimal::percent(5),
+            term: 5 * YEAR,
+        }
+    }
+
+    /// Issues `amount` bond tokens to `address` at instantiation; the
+    /// outstanding principal is the face value of everything issued.
+    pub fn with_bonds(mut self, address: &str, amount: u128) -> Self {
+        self.initial_balances.push(Cw20Coin {
+            address: address.to_string(),
+            amount: amount.into(),
+        });
+        self
+    }
+
+    pub fn with_term(mut self, seconds: u64) -> Self {
+        self.term = seconds;
+        self
+    }
+
+    /// The message `build` instantiates the contract with.
+    pub fn instantiate_msg(&self, issue_date: Timestamp) -> InstantiateMsg {
+        let issued: Uint128 = self.initial_balances.iter().map(|coin| coin.amount).sum();
+        let outstanding = Decimal::from_ratio(issued, 1u128) * self.face_value;
+
+        InstantiateMsg {
+            cw20_base: Cw20InstantiateMsg {
+                name: "Acme Senior Secured Note 2029".to_string(),
+                symbol: "ACME-SSN".to_string(),
+                decimals: 0,
+                initial_balances: self.initial_balances.clone(),
+                mint: None,
+                marketing: None,
+            },
+            bond_info: BondInfo {
+                bond_id: "US0000000001".to_string(),
+                bond_name: "Acme Senior Secured Note".to_string(),
+                issuer: Addr::unchecked(ISSUER),
+                bond_type: BondType::Corporate,
+                face_value: self.face_value,
+                total_issue_amount: issued,
+                coupon_rate: self.coupon_rate,
+                coupon_frequency: CouponFrequency::SemiAnnually,
+                maturity_date: issue_date.plus_seconds(self.term),
+                issue_date,
+                currency: "USD".to_string(),
+                bond_rating: BondRating::BBB,
+                collateral_type: CollateralType::Equipment,
+                collateral_value: outstanding,
+                trustee: Addr::unchecked(TRUSTEE),
+                paying_agent: Addr::unchecked(PAYING_AGENT),
+                total_coupons_paid: Decimal::zero(),
+                total_principal_repaid: Decimal::zero(),
+                outstanding_principal: outstanding,
+                next_coupon_date: issue_date.plus_seconds(180 * DAY),
+                accrued_interest: Decimal::zero(),
+            },
+            admin: None,
+            // Multi-test does not serve the sanction module's stargate query;
+            // screening is covered by the mock querier tests in sanctions.rs
+            sanction_screening: Some(false),
+        }
+    }
+
+    pub fn build(self) -> Suite {
+        let mut app = App::default();
+        let code_id = app.store_code(contract_bond());
+
+        let msg = self.instantiate_msg(app.block_info().time);
+        let contract = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked(ADMIN),
+                &msg,
+                &[],
+                "bond-token",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+
+        Suite {
+            app,
+            code_id,
+            contract,
+        }
+    }
+}
+
+pub struct Suite {
+    pub app: App,
+    pub code_id: u64,
+    pub contract: Addr,
+}
+
+impl Suite {
+    pub fn execute(&mut self, sender: &str, msg: impl Into<ExecuteMsg>) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &msg.into(),
+            &[],
+        )
+    }
+
+    pub fn block_time(&self) -> Timestamp {
+        self.app.block_info().time
+    }
+
+    pub fn advance_time(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += seconds / 5;
+        });
+    }
+
+    pub fn pay_coupon(
+        &mut self,
+        sender: &str,
+        payment_id: &str,
+        coupon_period_start: Timestamp,
+        coupon_period_end: Timestamp,
+        coupon_amount: Decimal,
+        principal_amount: Decimal,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            BondExecuteMsg::PayCoupon {
+                payment_id: Some(payment_id.to_string()),
+                coupon_period_start,
+                coupon_period_end,
+                coupon_amount,
+                principal_amount,
+                payment_method: PaymentMethod::BankTransfer,
+            },
+        )
+    }
+
+    pub fn redeem_bonds(
+        &mut self,
+        sender: &str,
+        redemption_id: &str,
+        bondholder: &str,
+        bonds: u128,
+        redemption_type: RedemptionType,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            BondExecuteMsg::RedeemBonds {
+                redemption_id: Some(redemption_id.to_string()),
+                bondholder: bondholder.to_string(),
+                bonds_to_redeem: bonds.into(),
+                redemption_type,
+                redemption_reason: "scheduled".to_string(),
+            },
+        )
+    }
+
+    pub fn record_transfer(
+        &mut self,
+        sender: &str,
+        transfer_id: &str,
+        from: &str,
+        to: &str,
+        bonds: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            BondExecuteMsg::RecordTransfer {
+                transfer_id: Some(transfer_id.to_string()),
+                from: from.to_string(),
+                to: to.to_string(),
+                bonds_transferred: bonds.into(),
+                transfer_price: Decimal::percent(99_500),
+                transfer_type: TransferType::Sale,
+                transfer_reason: "secondary market sale".to_string(),
+            },
+        )
+    }
+
+    pub fn calculate_interest(
+        &mut self,
+        sender: &str,
+        calculation_id: &str,
+        bondholder: &str,
+        bonds_held: u128,
+        days_held: u32,
+        calculation_method: CalculationMethod,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            BondExecuteMsg::CalculateInterest {
+                calculation_id: Some(calculation_id.to_string()),
+                bondholder: bondholder.to_string(),
+                bonds_held: bonds_held.into(),
+                days_held,
+                calculation_method,
+            },
+        )
+    }
+
+    pub fn update_payment_status(
+        &mut self,
+        sender: &str,
+        payment_id: &str,
+        status: PaymentStatus,
+        transaction_hash: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            BondExecuteMsg::UpdatePaymentStatus {
+                payment_id: payment_id.to_string(),
+                status,
+                transaction_hash: transaction_hash.map(str::to_string),
+            },
+        )
+    }
+
+    pub fn update_bond_rating(
+        &mut self,
+        sender: &str,
+        new_rating: BondRating,
+    ) -> AnyResult<AppResponse> {
+        self.execute(sender, BondExecuteMsg::UpdateBondRating { new_rating })
+    }
+
+    pub fn update_collateral_value(
+        &mut self,
+        sender: &str,
+        new_collateral_value: Decimal,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            BondExecuteMsg::UpdateCollateralValue {
+                new_collateral_value,
+            },
+        )
+    }
+
+    pub fn transfer(
+        &mut self,
+        sender: &str,
+        recipient: &str,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            ExecuteMsg::Cw20(Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: amount.into(),
+            }),
+        )
+    }
+
+    pub fn burn(&mut self, sender: &str, amount: u128) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            ExecuteMsg::Cw20(Cw20ExecuteMsg::Burn {
+                amount: amount.into(),
+            }),
+        )
+    }
+
+    pub fn grant_role(
+        &mut self,
+        sender: &str,
+        role: &str,
+        address: &str,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            RoleExecuteMsg::GrantRole {
+                role: role.to_string(),
+                address: address.to_string(),
+            },
+        )
+    }
+
+    pub fn revoke_role(
+        &mut self,
+        sender: &str,
+        role: &str,
+        address: &str,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            RoleExecuteMsg::RevokeRole {
+                role: role.to_string(),
+                address: address.to_string(),
+            },
+        )
+    }
+
+    pub fn pause(
+        &mut self,
+        sender: &str,
+        categories: Option<Vec<PauseCategory>>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(sender, PauseExecuteMsg::Pause { categories })
+    }
+
+    pub fn unpause(
+        &mut self,
+        sender: &str,
+        categories: Option<Vec<PauseCategory>>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(sender, PauseExecuteMsg::Unpause { categories })
+    }
+
+    pub fn migrate(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.app.migrate_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &MigrateMsg {},
+            self.code_id,
+        )
+    }
+
+    fn query<T: serde::de::DeserializeOwned>(&self, msg: impl Into<QueryMsg>) -> T {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &msg.into())
+            .unwrap()
+    }
+
+    pub fn bond_info(&self) -> BondInfo {
+        self.query(BondQueryMsg::BondInfo {})
+    }
+
+    pub fn coupon_payment(&self, payment_id: &str) -> CouponPayment {
+        self.query(BondQueryMsg::CouponPayment {
+            payment_id: payment_id.to_string(),
+        })
+    }
+
+    pub fn all_coupon_payments(
+        &self,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<(String, CouponPayment)> {
+        self.query(BondQueryMsg::AllCouponPayments {
+            start_after: start_after.map(str::to_string),
+            limit,
+        })
+    }
+
+    pub fn redemption_record(&self, redemption_id: &str) -> RedemptionRecord {
+        self.query(BondQueryMsg::RedemptionRecord {
+            redemption_id: redemption_id.to_string(),
+        })
+    }
+
+    pub fn all_redemption_records(
+        &self,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<(String, RedemptionRecord)> {
+        self.query(BondQueryMsg::AllRedemptionRecords {
+            start_after: start_after.map(str::to_string),
+            limit,
+        })
+    }
+
+    pub fn bond_transfer(&self, transfer_id: &str) -> BondTransfer {
+        self.query(BondQueryMsg::BondTransfer {
+            transfer_id: transfer_id.to_string(),
+        })
+    }
+
+    pub fn all_bond_transfers(
+        &self,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<(String, BondTransfer)> {
+        self.query(BondQueryMsg::AllBondTransfers {
+            start_after: start_after.map(str::to_string),
+            limit,
+        })
+    }
+
+    pub fn interest_calculation(&self, calculation_id: &str) -> InterestCalculation {
+        self.query(BondQueryMsg::InterestCalculation {
+            calculation_id: calculation_id.to_string(),
+        })
+    }
+
+    pub fn all_interest_calculations(
+        &self,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<(String, InterestCalculation)> {
+        self.query(BondQueryMsg::AllInterestCalculations {
+            start_after: start_after.map(str::to_string),
+            limit,
+        })
+    }
+
+    pub fn bondholder_info(&self, bondholder: &str) -> BondholderInfoResponse {
+        self.query(BondQueryMsg::BondholderInfo {
+            bondholder: bondholder.to_string(),
+        })
+    }
+
+    pub fn outstanding_principal(&self) -> Decimal {
+        self.query(BondQueryMsg::OutstandingPrincipal {})
+    }
+
+    pub fn accrued_interest(&self, bondholder: &str) -> Decimal {
+        self.query(BondQueryMsg::AccruedInterest {
+            bondholder: bondholder.to_string(),
+        })
+    }
+
+    pub fn next_coupon_date(&self) -> Timestamp {
+        self.query(BondQueryMsg::NextCouponDate {})
+    }
+
+    pub fn bond_yield(&self) -> BondYieldResponse {
+        self.query(BondQueryMsg::BondYield {})
+    }
+
+    pub fn reconcile_supply(&self) -> ReconcileSupplyResponse {
+        self.query(BondQueryMsg::ReconcileSupply {})
+    }
+
+    pub fn has_role(&self, role: &str, address: &str) -> bool {
+        self.query(RoleQueryMsg::HasRole {
+            role: role.to_string(),
+            address: address.to_string(),
+        })
+    }
+
+    pub fn pause_status(&self) -> Vec<PauseCategory> {
+        let res: PauseStatusResponse = self.query(PauseQueryMsg::PauseStatus {});
+        res.paused
+    }
+
+    pub fn balance(&self, address: &str) -> Uint128 {
+        let res: BalanceResponse = self.query(QueryMsg::Cw20(Cw20QueryMsg::Balance {
+            address: address.to_string(),
+        }));
+        res.balance
+    }
+
+    pub fn total_supply(&self) -> Uint128 {
+        let res: TokenInfoResponse = self.query(QueryMsg::Cw20(Cw20QueryMsg::TokenInfo {}));
+        res.total_supply
+    }
+}
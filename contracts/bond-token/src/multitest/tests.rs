@@ -0,0 +1,559 @@
+use cosmwasm_std::{from_json, Addr, Decimal, Uint128};
+use rwa_common::pause::GUARDIAN;
+use rwa_common::{CommonError, PauseCategory};
+
+use super::suite::{SuiteBuilder, ADMIN, DAY, ISSUER, PAYING_AGENT, TRUSTEE, YEAR};
+use crate::error::ContractError;
+use crate::msg::BondExecuteMsg;
+use crate::state::{
+    BondRating, CalculationMethod, PaymentMethod, PaymentStatus, RedemptionType,
+    PAYING_AGENT as PAYING_AGENT_ROLE,
+};
+
+fn dec(value: u128) -> Decimal {
+    Decimal::from_ratio(value, 1u128)
+}
+
+#[test]
+fn pay_coupon_records_payment_and_advances_schedule() {
+    let mut suite = SuiteBuilder::new().with_bonds("alice", 1_000).build();
+    let start = suite.block_time();
+    let next_coupon_date = suite.next_coupon_date();
+
+    suite
+        .pay_coupon(
+            PAYING_AGENT,
+            "c-1",
+            start,
+            start.plus_seconds(180 * DAY),
+            dec(25_000),
+            dec(100_000),
+        )
+        .unwrap();
+
+    let payment = suite.coupon_payment("c-1");
+    assert_eq!(payment.total_payment, dec(125_000));
+    assert_eq!(payment.payment_status, PaymentStatus::Paid);
+
+    let info = suite.bond_info();
+    assert_eq!(info.total_coupons_paid, dec(25_000));
+    assert_eq!(info.total_principal_repaid, dec(100_000));
+    assert_eq!(suite.outstanding_principal(), dec(900_000));
+    // The schedule is kept in whole seconds
+    assert_eq!(
+        suite.next_coupon_date().seconds(),
+        next_coupon_date.plus_seconds(180 * DAY).seconds()
+    );
+}
+
+#[test]
+fn pay_coupon_requires_paying_agent() {
+    let mut suite = SuiteBuilder::new().with_bonds("alice", 1_000).build();
+    let start = suite.block_time();
+
+    let err = suite
+        .pay_coupon(
+            ISSUER,
+            "c-1",
+            start,
+            start.plus_seconds(180 * DAY),
+            dec(25_000),
+            Decimal::zero(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotPayingAgent {
+            action: "make coupon payments".to_string()
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn pay_coupon_validates_period_and_principal() {
+    let mut suite = SuiteBuilder::new()
+        .with_bonds("alice", 1_000)
+        .with_term(YEAR)
+        .build();
+    let start = suite.block_time();
+    let maturity_date = suite.bond_info().maturity_date;
+
+    let err = suite
+        .pay_coupon(
+            PAYING_AGENT,
+            "c-1",
+            start,
+            start,
+            dec(25_000),
+            Decimal::zero(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidCouponPeriod {},
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .pay_coupon(
+            PAYING_AGENT,
+            "c-1",
+            maturity_date,
+            maturity_date.plus_seconds(180 * DAY),
+            dec(25_000),
+            Decimal::zero(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::BondMatured { maturity_date },
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .pay_coupon(
+            PAYING_AGENT,
+            "c-1",
+            start,
+            start.plus_seconds(180 * DAY),
+            dec(25_000),
+            dec(1_000_001),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ExceedsOutstandingPrincipal {
+            requested: dec(1_000_001),
+            outstanding: dec(1_000_000),
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn bondholder_redeems_early() {
+    let mut suite = SuiteBuilder::new()
+        .with_bonds("alice", 600)
+        .with_bonds("bob", 400)
+        .build();
+
+    suite
+        .redeem_bonds(
+            "alice",
+            "r-1",
+            "alice",
+            100,
+            RedemptionType::EarlyRedemption,
+        )
+        .unwrap();
+
+    assert_eq!(suite.balance("alice"), Uint128::new(500));
+    assert_eq!(suite.total_supply(), Uint128::new(900));
+    assert_eq!(suite.outstanding_principal(), dec(900_000));
+
+    let record = suite.redemption_record("r-1");
+    assert_eq!(record.bondholder, Addr::unchecked("alice"));
+    assert_eq!(record.redemption_value, dec(100_000));
+}
+
+#[test]
+fn paying_agent_redeems_at_maturity() {
+    let mut suite = SuiteBuilder::new()
+        .with_bonds("alice", 600)
+        .with_term(YEAR)
+        .build();
+
+    let err = suite
+        .redeem_bonds(PAYING_AGENT, "r-1", "alice", 600, RedemptionType::Maturity)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::BondNotMatured {
+            maturity_date: suite.bond_info().maturity_date
+        },
+        err.downcast().unwrap()
+    );
+
+    suite.advance_time(YEAR);
+    suite
+        .redeem_bonds(PAYING_AGENT, "r-1", "alice", 600, RedemptionType::Maturity)
+        .unwrap();
+    assert_eq!(suite.balance("alice"), Uint128::zero());
+    assert_eq!(suite.outstanding_principal(), Decimal::zero());
+
+    let err = suite
+        .redeem_bonds(PAYING_AGENT, "r-2", "alice", 1, RedemptionType::CallOption)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::BondMatured {
+            maturity_date: suite.bond_info().maturity_date
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn redeem_bonds_rejects_third_parties_and_overdrafts() {
+    let mut suite = SuiteBuilder::new()
+        .with_bonds("alice", 600)
+        .with_bonds("bob", 400)
+        .build();
+
+    let err = suite
+        .redeem_bonds("bob", "r-1", "alice", 100, RedemptionType::EarlyRedemption)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotPayingAgentOrBondholder {
+            action: "redeem bonds".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .redeem_bonds("bob", "r-1", "bob", 500, RedemptionType::EarlyRedemption)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::InsufficientBalance {
+            needed: Uint128::new(500),
+            available: Uint128::new(400),
+        }),
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.total_supply(), Uint128::new(1_000));
+}
+
+#[test]
+fn paying_agent_can_be_replaced() {
+    let mut suite = SuiteBuilder::new()
+        .with_bonds("alice", 600)
+        .with_term(YEAR)
+        .build();
+    suite.advance_time(YEAR);
+
+    suite
+        .grant_role(ADMIN, PAYING_AGENT_ROLE, "new-agent")
+        .unwrap();
+    suite
+        .revoke_role(ADMIN, PAYING_AGENT_ROLE, PAYING_AGENT)
+        .unwrap();
+    assert!(!suite.has_role(PAYING_AGENT_ROLE, PAYING_AGENT));
+
+    // The revoked agent can no longer redeem on a bondholder's behalf
+    let err = suite
+        .redeem_bonds(PAYING_AGENT, "r-1", "alice", 600, RedemptionType::Maturity)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotPayingAgentOrBondholder {
+            action: "redeem bonds".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .redeem_bonds("new-agent", "r-1", "alice", 600, RedemptionType::Maturity)
+        .unwrap();
+    assert_eq!(suite.balance("alice"), Uint128::zero());
+}
+
+#[test]
+fn paused_record_keeping_stops_coupon_payments() {
+    let mut suite = SuiteBuilder::new().with_bonds("alice", 1_000).build();
+    let start = suite.block_time();
+    suite.grant_role(ADMIN, GUARDIAN, "guardian").unwrap();
+    suite
+        .pause("guardian", Some(vec![PauseCategory::RecordKeeping]))
+        .unwrap();
+
+    let err = suite
+        .pay_coupon(
+            PAYING_AGENT,
+            "c-1",
+            start,
+            start.plus_seconds(180 * DAY),
+            dec(25_000),
+            Decimal::zero(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::Paused {
+            category: PauseCategory::RecordKeeping
+        }),
+        err.downcast().unwrap()
+    );
+
+    // Bondholders can still redeem and transfer
+    suite
+        .redeem_bonds(
+            "alice",
+            "r-1",
+            "alice",
+            100,
+            RedemptionType::EarlyRedemption,
+        )
+        .unwrap();
+    suite.transfer("alice", "bob", 100).unwrap();
+    assert_eq!(suite.balance("alice"), Uint128::new(800));
+
+    suite.unpause(ADMIN, None).unwrap();
+    assert!(suite.pause_status().is_empty());
+    suite
+        .pay_coupon(
+            PAYING_AGENT,
+            "c-1",
+            start,
+            start.plus_seconds(180 * DAY),
+            dec(25_000),
+            Decimal::zero(),
+        )
+        .unwrap();
+}
+
+#[test]
+fn record_transfer() {
+    let mut suite = SuiteBuilder::new().with_bonds("alice", 600).build();
+
+    suite.transfer("alice", "bob", 200).unwrap();
+    suite
+        .record_transfer("alice", "t-1", "alice", "bob", 200)
+        .unwrap();
+
+    let transfer = suite.bond_transfer("t-1");
+    assert_eq!(transfer.from, Addr::unchecked("alice"));
+    assert_eq!(transfer.to, Addr::unchecked("bob"));
+    assert_eq!(transfer.bonds_transferred, Uint128::new(200));
+    assert_eq!(suite.balance("bob"), Uint128::new(200));
+}
+
+#[test]
+fn calculate_interest() {
+    let mut suite = SuiteBuilder::new().with_bonds("alice", 600).build();
+
+    // 100 bonds at 1,000 face value and 5% a year accrue 5,000 a year
+    let cases = [
+        ("i-1", 365, CalculationMethod::Actual365, dec(5_000)),
+        ("i-2", 73, CalculationMethod::SimpleInterest, dec(1_000)),
+        ("i-3", 180, CalculationMethod::Actual360, dec(2_500)),
+        ("i-4", 90, CalculationMethod::Thirty360, dec(1_250)),
+        (
+            "i-5",
+            365,
+            CalculationMethod::CompoundInterest,
+            Decimal::zero(),
+        ),
+    ];
+    for (id, days, method, expected) in cases {
+        suite
+            .calculate_interest(ISSUER, id, "alice", 100, days, method)
+            .unwrap();
+        let calculation = suite.interest_calculation(id);
+        assert_eq!(calculation.accrued_interest, expected, "{id}");
+        assert_eq!(calculation.coupon_rate, Decimal::percent(5));
+    }
+}
+
+#[test]
+fn update_payment_status() {
+    let mut suite = SuiteBuilder::new().with_bonds("alice", 600).build();
+    let start = suite.block_time();
+    suite
+        .pay_coupon(
+            PAYING_AGENT,
+            "c-1",
+            start,
+            start.plus_seconds(180 * DAY),
+            dec(15_000),
+            Decimal::zero(),
+        )
+        .unwrap();
+
+    let err = suite
+        .update_payment_status(PAYING_AGENT, "c-2", PaymentStatus::Failed, None)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::CouponPaymentNotFound {
+            payment_id: "c-2".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .update_payment_status(PAYING_AGENT, "c-1", PaymentStatus::Failed, Some("0xabc"))
+        .unwrap();
+    let payment = suite.coupon_payment("c-1");
+    assert_eq!(payment.payment_status, PaymentStatus::Failed);
+    assert_eq!(payment.transaction_hash.as_deref(), Some("0xabc"));
+}
+
+#[test]
+fn update_bond_rating() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite
+        .update_bond_rating(PAYING_AGENT, BondRating::AAA)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotIssuerOrTrustee {
+            action: "update bond rating".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    suite.update_bond_rating(ISSUER, BondRating::A).unwrap();
+    assert_eq!(suite.bond_info().bond_rating, BondRating::A);
+    suite.update_bond_rating(TRUSTEE, BondRating::BB).unwrap();
+    assert_eq!(suite.bond_info().bond_rating, BondRating::BB);
+}
+
+#[test]
+fn update_collateral_value() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite
+        .update_collateral_value(ISSUER, dec(2_000_000))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotTrustee {
+            action: "update collateral value".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .update_collateral_value(TRUSTEE, dec(2_000_000))
+        .unwrap();
+    assert_eq!(suite.bond_info().collateral_value, dec(2_000_000));
+}
+
+#[test]
+fn bondholder_queries() {
+    let suite = SuiteBuilder::new().with_bonds("alice", 600).build();
+
+    let holder = suite.bondholder_info("alice");
+    assert_eq!(holder.bond_balance, Uint128::new(600));
+    assert_eq!(holder.face_value_held, dec(600_000));
+    assert_eq!(suite.accrued_interest("alice"), dec(30_000));
+
+    let bond_yield = suite.bond_yield();
+    assert_eq!(bond_yield.coupon_rate, Decimal::percent(5));
+}
+
+#[test]
+fn record_queries_paginate() {
+    let mut suite = SuiteBuilder::new()
+        .with_bonds("alice", 600)
+        .with_bonds("bob", 400)
+        .build();
+    let start = suite.block_time();
+    for id in ["c-1", "c-2", "c-3"] {
+        suite
+            .pay_coupon(
+                PAYING_AGENT,
+                id,
+                start,
+                start.plus_seconds(180 * DAY),
+                dec(100),
+                Decimal::zero(),
+            )
+            .unwrap();
+    }
+    for id in ["r-1", "r-2"] {
+        suite
+            .redeem_bonds("bob", id, "bob", 10, RedemptionType::EarlyRedemption)
+            .unwrap();
+    }
+    suite
+        .record_transfer("alice", "t-1", "alice", "bob", 1)
+        .unwrap();
+    suite
+        .calculate_interest(ISSUER, "i-1", "alice", 1, 1, CalculationMethod::Actual365)
+        .unwrap();
+
+    let page = suite.all_coupon_payments(None, Some(2));
+    let ids: Vec<_> = page.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["c-1", "c-2"]);
+
+    let page = suite.all_coupon_payments(Some("c-2"), None);
+    let ids: Vec<_> = page.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["c-3"]);
+
+    let page = suite.all_redemption_records(Some("r-1"), None);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].1.redemption_id, "r-2");
+
+    assert_eq!(suite.all_bond_transfers(None, None).len(), 1);
+    assert_eq!(suite.all_interest_calculations(None, None).len(), 1);
+}
+
+#[test]
+fn record_ids_cannot_be_reused() {
+    let mut suite = SuiteBuilder::new().with_bonds("alice", 600).build();
+    let start = suite.block_time();
+    let end = start.plus_seconds(180 * DAY);
+    suite
+        .pay_coupon(PAYING_AGENT, "c-1", start, end, dec(100), dec(1_000))
+        .unwrap();
+    suite
+        .redeem_bonds("alice", "r-1", "alice", 10, RedemptionType::EarlyRedemption)
+        .unwrap();
+    suite
+        .record_transfer("alice", "t-1", "alice", "bob", 1)
+        .unwrap();
+    suite
+        .calculate_interest(ISSUER, "i-1", "alice", 1, 1, CalculationMethod::Actual365)
+        .unwrap();
+
+    let duplicate =
+        |id: &str| ContractError::Common(CommonError::DuplicateRecordId { id: id.to_string() });
+
+    let err = suite
+        .pay_coupon(PAYING_AGENT, "c-1", start, end, dec(100), dec(1_000))
+        .unwrap_err();
+    assert_eq!(duplicate("c-1"), err.downcast().unwrap());
+    assert_eq!(suite.bond_info().total_principal_repaid, dec(1_000));
+
+    let err = suite
+        .redeem_bonds("alice", "r-1", "alice", 10, RedemptionType::EarlyRedemption)
+        .unwrap_err();
+    assert_eq!(duplicate("r-1"), err.downcast().unwrap());
+    assert_eq!(suite.balance("alice"), Uint128::new(590));
+
+    let err = suite
+        .record_transfer("alice", "t-1", "alice", "bob", 2)
+        .unwrap_err();
+    assert_eq!(duplicate("t-1"), err.downcast().unwrap());
+
+    let err = suite
+        .calculate_interest(ISSUER, "i-1", "alice", 2, 1, CalculationMethod::Actual365)
+        .unwrap_err();
+    assert_eq!(duplicate("i-1"), err.downcast().unwrap());
+}
+
+#[test]
+fn record_ids_are_generated_when_omitted() {
+    let mut suite = SuiteBuilder::new().with_bonds("alice", 600).build();
+    let start = suite.block_time();
+
+    for expected in ["00000000000000000001", "00000000000000000002"] {
+        let res = suite
+            .execute(
+                PAYING_AGENT,
+                BondExecuteMsg::PayCoupon {
+                    payment_id: None,
+                    coupon_period_start: start,
+                    coupon_period_end: start.plus_seconds(180 * DAY),
+                    coupon_amount: dec(15_000),
+                    principal_amount: Decimal::zero(),
+                    payment_method: PaymentMethod::Escrow,
+                },
+            )
+            .unwrap();
+        let id: String = from_json(res.data.unwrap()).unwrap();
+        assert_eq!(id, expected);
+        assert_eq!(suite.coupon_payment(&id).coupon_amount, dec(15_000));
+    }
+}
+
+#[test]
+fn migrate_keeps_state() {
+    let mut suite = SuiteBuilder::new().with_bonds("alice", 600).build();
+    let info = suite.bond_info();
+
+    suite.migrate(ADMIN).unwrap();
+    assert_eq!(suite.bond_info(), info);
+}
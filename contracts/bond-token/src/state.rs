@@ -0,0 +1,189 @@
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+use rwa_common::pause::GUARDIAN;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// Custom state for bond specific data
+pub const BOND_INFO: Item<BondInfo> = Item::new("bond_info");
+pub const COUPON_PAYMENTS: Map<&str, CouponPayment> = Map::new("coupon_payments");
+pub const REDEMPTION_RECORDS: Map<&str, RedemptionRecord> = Map::new("redemption_records");
+pub const BOND_TRANSFERS: Map<&str, BondTransfer> = Map::new("bond_transfers");
+pub const INTEREST_CALCULATIONS: Map<&str, InterestCalculation> = Map::new("interest_calculations");
+
+// Roles kept in the shared role registry
+pub const ISSUER: &str = "issuer";
+pub const TRUSTEE: &str = "trustee";
+pub const PAYING_AGENT: &str = "paying_agent";
+pub const ROLES: &[&str] = &[ISSUER, TRUSTEE, PAYING_AGENT, GUARDIAN];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondInfo {
+    pub bond_id: String,
+    pub bond_name: String,
+    // Initial holder of the issuer role, like trustee and paying_agent below;
+    // later changes are made in the role registry
+    pub issuer: Addr,
+    pub bond_type: BondType,
+    pub face_value: Decimal,         // Face value per bond token
+    pub total_issue_amount: Uint128, // Total number of bond tokens issued
+    pub coupon_rate: Decimal,        // Annual interest rate (e.g., 0.05 for 5%)
+    pub coupon_frequency: CouponFrequency,
+    pub maturity_date: Timestamp,
+    pub issue_date: Timestamp,
+    pub currency: String, // e.g., "USD", "EUR", "uom"
+    pub bond_rating: BondRating,
+    pub collateral_type: CollateralType,
+    pub collateral_value: Decimal,
+    pub trustee: Addr,
+    pub paying_agent: Addr,
+    pub total_coupons_paid: Decimal,
+    pub total_principal_repaid: Decimal,
+    pub outstanding_principal: Decimal,
+    pub next_coupon_date: Timestamp,
+    pub accrued_interest: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum BondType {
+    Corporate,
+    Government,
+    Municipal,
+    AssetBacked,
+    Convertible,
+    ZeroCoupon,
+    FloatingRate,
+    Perpetual,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum CouponFrequency {
+    Monthly,
+    Quarterly,
+    SemiAnnually,
+    Annually,
+    AtMaturity,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum BondRating {
+    AAA,
+    AA,
+    A,
+    BBB,
+    BB,
+    B,
+    CCC,
+    CC,
+    C,
+    D,
+    Unrated,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum CollateralType {
+    RealEstate,
+    Equipment,
+    Inventory,
+    AccountsReceivable,
+    Cash,
+    Securities,
+    Commodities,
+    IntellectualProperty,
+    None,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CouponPayment {
+    pub payment_id: String,
+    pub payment_date: Timestamp,
+    pub coupon_period_start: Timestamp,
+    pub coupon_period_end: Timestamp,
+    pub coupon_amount: Decimal,
+    pub principal_amount: Decimal,
+    pub total_payment: Decimal,
+    pub payment_status: PaymentStatus,
+    pub payment_method: PaymentMethod,
+    pub transaction_hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum PaymentStatus {
+    Pending,
+    Paid,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum PaymentMethod {
+    BankTransfer,
+    CryptoTransfer,
+    TokenTransfer,
+    Escrow,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RedemptionRecord {
+    pub redemption_id: String,
+    pub redemption_date: Timestamp,
+    pub bondholder: Addr,
+    pub bonds_redeemed: Uint128,
+    pub redemption_value: Decimal,
+    pub redemption_type: RedemptionType,
+    pub redemption_reason: String,
+    pub transaction_hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum RedemptionType {
+    Maturity,
+    EarlyRedemption,
+    CallOption,
+    PutOption,
+    Default,
+    Conversion,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondTransfer {
+    pub transfer_id: String,
+    pub transfer_date: Timestamp,
+    pub from: Addr,
+    pub to: Addr,
+    pub bonds_transferred: Uint128,
+    pub transfer_price: Decimal,
+    pub transfer_type: TransferType,
+    pub transfer_reason: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum TransferType {
+    Sale,
+    Gift,
+    Inheritance,
+    CollateralAssignment,
+    Pledge,
+    Repo,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InterestCalculation {
+    pub calculation_id: String,
+    pub calculation_date: Timestamp,
+    pub bondholder: Addr,
+    pub bonds_held: Uint128,
+    pub days_held: u32,
+    pub accrued_interest: Decimal,
+    pub coupon_rate: Decimal,
+    pub calculation_method: CalculationMethod,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum CalculationMethod {
+    SimpleInterest,
+    CompoundInterest,
+    Actual365,
+    Actual360,
+    Thirty360,
+}
@@ -0,0 +1,103 @@
+//! Article 6 authorization.
+//!
+//! Credits counted towards another country's NDC, or used for CORSIA, must be
+//! authorized by the host country, which then applies a corresponding
+//! adjustment so the reduction is not counted twice. An account holding the
+//! host country authority role records, per batch, the authorization status,
+//! the Letter of Authorization and the uses it covers. Every retirement
+//! declares its use, and credits are only retired for NDC or CORSIA use from
+//! batches authorized for it. Registered projects keep no batches, so they are
+//! authorized as a whole.
+
+use cosmwasm_std::{StdResult, Storage};
+
+use crate::batches::load_batch;
+use crate::error::ContractError;
+use crate::registry::load_project;
+use crate::state::{
+    AuthorizationStatus, BatchAmount, BatchAuthorization, CreditUse, BATCH_AUTHORIZATIONS,
+    PROJECT_AUTHORIZATIONS,
+};
+
+/// Records the host country's authorization of `batch_id`, replacing any
+/// before.
+pub fn set_batch_authorization(
+    storage: &mut dyn Storage,
+    batch_id: u64,
+    authorization: &BatchAuthorization,
+) -> Result<(), ContractError> {
+    load_batch(storage, batch_id)?;
+    BATCH_AUTHORIZATIONS.save(storage, batch_id, authorization)?;
+    Ok(())
+}
+
+/// Whether credits of `batch_id` can be retired for `retirement_use`.
+pub fn is_authorized(
+    storage: &dyn Storage,
+    batch_id: u64,
+    retirement_use: CreditUse,
+) -> StdResult<bool> {
+    if retirement_use == CreditUse::Voluntary {
+        return Ok(true);
+    }
+    Ok(BATCH_AUTHORIZATIONS
+        .may_load(storage, batch_id)?
+        .is_some_and(|authorization| {
+            authorization.status == AuthorizationStatus::Authorized
+                && authorization.authorized_uses.contains(&retirement_use)
+        }))
+}
+
+/// Fails unless every batch drawn is authorized for `retirement_use`.
+pub fn ensure_authorized(
+    storage: &dyn Storage,
+    drawn: &[BatchAmount],
+    retirement_use: CreditUse,
+) -> Result<(), ContractError> {
+    for batch in drawn {
+        if !is_authorized(storage, batch.batch_id, retirement_use)? {
+            return Err(ContractError::UseNotAuthorized {
+                batch_id: batch.batch_id,
+                retirement_use,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Records the host country's authorization of the registered `project_id`,
+/// replacing any before.
+pub fn set_project_authorization(
+    storage: &mut dyn Storage,
+    project_id: &str,
+    authorization: &BatchAuthorization,
+) -> Result<(), ContractError> {
+    load_project(storage, project_id)?;
+    PROJECT_AUTHORIZATIONS.save(storage, project_id, authorization)?;
+    Ok(())
+}
+
+/// Fails unless the registered `project_id` is authorized for
+/// `retirement_use`.
+pub fn ensure_project_authorized(
+    storage: &dyn Storage,
+    project_id: &str,
+    retirement_use: CreditUse,
+) -> Result<(), ContractError> {
+    if retirement_use == CreditUse::Voluntary {
+        return Ok(());
+    }
+    let authorized = PROJECT_AUTHORIZATIONS
+        .may_load(storage, project_id)?
+        .is_some_and(|authorization| {
+            authorization.status == AuthorizationStatus::Authorized
+                && authorization.authorized_uses.contains(&retirement_use)
+        });
+    if !authorized {
+        return Err(ContractError::ProjectUseNotAuthorized {
+            project_id: project_id.to_string(),
+            retirement_use,
+        });
+    }
+    Ok(())
+}
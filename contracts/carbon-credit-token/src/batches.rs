@@ -0,0 +1,313 @@
+//! Issuance batches.
+//!
+//! Every verification that mints credits opens a batch with its own vintage,
+//! methodology version and serial range, and each holder's balance is split
+//! between the batches its credits came from.
+//!
+//! The CW20 balance stays the source of truth for how many credits an account
+//! holds. Only batched credits are stored per batch: whatever part of a balance
+//! is not is attributed to the base batch, which covers the credits issued at
+//! instantiation or before batches were tracked. Unless a batch is named,
+//! credits leave an account from the base batch first and then from the
+//! oldest batch onwards. The holders of every batch, the base batch
+//! included, are kept by batch so a batch's holders can be paged.
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw20_base::state::BALANCES;
+use cw_storage_plus::Bound;
+use rwa_common::{DEFAULT_LIMIT, MAX_LIMIT};
+
+use crate::error::ContractError;
+use crate::state::{Batch, BatchAmount, BATCHES, BATCH_BALANCES, BATCH_HOLDERS};
+
+/// Id of the batch holding credits that were never issued into a batch.
+pub const BASE_BATCH: u64 = 0;
+
+/// Opens the next batch, numbering its credits on from the previous batch.
+pub fn open_batch(
+    storage: &mut dyn Storage,
+    vintage_year: u32,
+    methodology_version: Option<String>,
+    verification_id: Option<String>,
+    credits: Uint128,
+) -> StdResult<Batch> {
+    let last = BATCHES
+        .range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?;
+    let (batch_id, first_serial) = match last {
+        Some((batch_id, batch)) => (batch_id + 1, batch.last_serial + Uint128::one()),
+        None => (BASE_BATCH + 1, Uint128::one()),
+    };
+    save_batch(
+        storage,
+        batch_id,
+        vintage_year,
+        methodology_version,
+        verification_id,
+        first_serial,
+        credits,
+    )
+}
+
+/// Records the base batch for `credits` already in circulation, unless
+/// batches are tracked already.
+pub fn open_base_batch(
+    storage: &mut dyn Storage,
+    vintage_year: u32,
+    credits: Uint128,
+) -> StdResult<()> {
+    let tracked = BATCHES
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    if credits.is_zero() || tracked {
+        return Ok(());
+    }
+    save_batch(
+        storage,
+        BASE_BATCH,
+        vintage_year,
+        None,
+        None,
+        Uint128::one(),
+        credits,
+    )?;
+    Ok(())
+}
+
+fn save_batch(
+    storage: &mut dyn Storage,
+    batch_id: u64,
+    vintage_year: u32,
+    methodology_version: Option<String>,
+    verification_id: Option<String>,
+    first_serial: Uint128,
+    credits: Uint128,
+) -> StdResult<Batch> {
+    let batch = Batch {
+        batch_id,
+        vintage_year,
+        methodology_version,
+        verification_id,
+        first_serial,
+        last_serial: first_serial
+            .checked_add(credits)?
+            .checked_sub(Uint128::one())?,
+        credits_issued: credits,
+    };
+    BATCHES.save(storage, batch_id, &batch)?;
+    Ok(batch)
+}
+
+pub fn load_batch(storage: &dyn Storage, batch_id: u64) -> Result<Batch, ContractError> {
+    BATCHES
+        .may_load(storage, batch_id)?
+        .ok_or(ContractError::BatchNotFound { batch_id })
+}
+
+/// The credits of `batch_id` held by `holder`.
+pub fn batch_balance(storage: &dyn Storage, holder: &Addr, batch_id: u64) -> StdResult<Uint128> {
+    if batch_id == BASE_BATCH {
+        return base_balance(storage, holder);
+    }
+    Ok(BATCH_BALANCES
+        .may_load(storage, (holder, batch_id))?
+        .unwrap_or_default())
+}
+
+/// The part of `holder`'s balance that is not held in any batch.
+fn base_balance(storage: &dyn Storage, holder: &Addr) -> StdResult<Uint128> {
+    let balance = BALANCES.may_load(storage, holder)?.unwrap_or_default();
+    let batched = BATCH_BALANCES
+        .prefix(holder)
+        .range(storage, None, None, Order::Ascending)
+        .try_fold(Uint128::zero(), |total, item| -> StdResult<_> {
+            Ok(total.checked_add(item?.1)?)
+        })?;
+    Ok(balance.checked_sub(batched)?)
+}
+
+/// Every holder of `batch_id` with its credits of the batch, in address order.
+pub fn batch_holders(storage: &dyn Storage, batch_id: u64) -> StdResult<Vec<(Addr, Uint128)>> {
+    BATCH_HOLDERS
+        .prefix(batch_id)
+        .range(storage, None, None, Order::Ascending)
+        .collect()
+}
+
+/// Up to `limit` of `holder`'s non-empty batch balances, in batch order,
+/// starting after `start_after`.
+pub fn batch_balances(
+    storage: &dyn Storage,
+    holder: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BatchAmount>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    holdings(storage, holder, start_after)?
+        .take(limit)
+        .collect()
+}
+
+/// `holder`'s non-empty batch balances in batch order, base batch first.
+fn holdings<'a>(
+    storage: &'a dyn Storage,
+    holder: &Addr,
+    start_after: Option<u64>,
+) -> StdResult<impl Iterator<Item = StdResult<BatchAmount>> + 'a> {
+    let base = match start_after {
+        None => Some(base_balance(storage, holder)?).filter(|base| !base.is_zero()),
+        Some(_) => None,
+    };
+    let base = base.map(|credits| {
+        Ok(BatchAmount {
+            batch_id: BASE_BATCH,
+            credits,
+        })
+    });
+
+    let batched = BATCH_BALANCES
+        .prefix(holder)
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .map(|item| item.map(|(batch_id, credits)| BatchAmount { batch_id, credits }));
+    Ok(base.into_iter().chain(batched))
+}
+
+/// Records the base batch's holders from the CW20 balances, for contracts
+/// whose base batch was opened before its holders were kept.
+pub fn track_base_holders(storage: &mut dyn Storage) -> StdResult<()> {
+    let holders = BALANCES
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for holder in holders {
+        let balance = base_balance(storage, &holder)?;
+        save_batch_balance(storage, &holder, BASE_BATCH, balance)?;
+    }
+    Ok(())
+}
+
+/// Adds the drawn batch amounts to `holder`.
+pub fn credit_batches(
+    storage: &mut dyn Storage,
+    holder: &Addr,
+    amounts: &[BatchAmount],
+) -> StdResult<()> {
+    for amount in amounts {
+        let balance = BATCH_HOLDERS
+            .may_load(storage, (amount.batch_id, holder))?
+            .unwrap_or_default()
+            .checked_add(amount.credits)?;
+        save_batch_balance(storage, holder, amount.batch_id, balance)?;
+    }
+    Ok(())
+}
+
+/// Takes `amount` out of `holder`'s batches and returns what was drawn from
+/// each.
+///
+/// With a `batch_id` all of it has to come from that batch. Without one the
+/// base batch is drawn first and then the oldest batches, and a shortfall is
+/// left for the CW20 balance check to report.
+pub fn debit_batches(
+    storage: &mut dyn Storage,
+    holder: &Addr,
+    batch_id: Option<u64>,
+    amount: Uint128,
+) -> Result<Vec<BatchAmount>, ContractError> {
+    if let Some(batch_id) = batch_id {
+        load_batch(storage, batch_id)?;
+        let available = batch_balance(storage, holder, batch_id)?;
+        if available < amount {
+            return Err(ContractError::InsufficientBatchBalance {
+                batch_id,
+                requested: amount,
+                available,
+            });
+        }
+        let drawn = vec![BatchAmount {
+            batch_id,
+            credits: amount,
+        }];
+        remove_batch_amounts(storage, holder, &drawn)?;
+        return Ok(drawn);
+    }
+
+    let (drawn, _) = debit_usable_batches(storage, holder, amount, |_, _| Ok(true))?;
+    Ok(drawn)
+}
+
+/// Takes up to `amount` out of `holder`'s batches that `usable` accepts, the
+/// base batch first and then the oldest. Returns what was drawn from each, and
+/// the credits held in the batches passed over.
+pub fn debit_usable_batches(
+    storage: &mut dyn Storage,
+    holder: &Addr,
+    amount: Uint128,
+    usable: impl Fn(&dyn Storage, u64) -> StdResult<bool>,
+) -> StdResult<(Vec<BatchAmount>, Uint128)> {
+    let mut remaining = amount;
+    let mut drawn = vec![];
+    let mut passed_over = Uint128::zero();
+    for balance in holdings(storage, holder, None)? {
+        if remaining.is_zero() {
+            break;
+        }
+        let balance = balance?;
+        if !usable(storage, balance.batch_id)? {
+            passed_over = passed_over.checked_add(balance.credits)?;
+            continue;
+        }
+        let credits = balance.credits.min(remaining);
+        remaining -= credits;
+        drawn.push(BatchAmount {
+            batch_id: balance.batch_id,
+            credits,
+        });
+    }
+    remove_batch_amounts(storage, holder, &drawn)?;
+    Ok((drawn, passed_over))
+}
+
+fn remove_batch_amounts(
+    storage: &mut dyn Storage,
+    holder: &Addr,
+    amounts: &[BatchAmount],
+) -> StdResult<()> {
+    for amount in amounts {
+        let balance = BATCH_HOLDERS
+            .load(storage, (amount.batch_id, holder))?
+            .checked_sub(amount.credits)?;
+        save_batch_balance(storage, holder, amount.batch_id, balance)?;
+    }
+    Ok(())
+}
+
+/// Saves `holder`'s credits of `batch_id`. The base batch's credits are the
+/// part of the balance not held in any batch, so only its holder is kept.
+fn save_batch_balance(
+    storage: &mut dyn Storage,
+    holder: &Addr,
+    batch_id: u64,
+    balance: Uint128,
+) -> StdResult<()> {
+    if balance.is_zero() {
+        BATCH_HOLDERS.remove(storage, (batch_id, holder));
+    } else {
+        BATCH_HOLDERS.save(storage, (batch_id, holder), &balance)?;
+    }
+    if batch_id == BASE_BATCH {
+        return Ok(());
+    }
+    if balance.is_zero() {
+        BATCH_BALANCES.remove(storage, (holder, batch_id));
+    } else {
+        BATCH_BALANCES.save(storage, (holder, batch_id), &balance)?;
+    }
+    Ok(())
+}
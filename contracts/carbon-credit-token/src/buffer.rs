@@ -0,0 +1,248 @@
+//! Buffer pool.
+//!
+//! Forestry and land-use credits can be reversed by fire, logging or other
+//! losses of the carbon they stand for. A share of every issuance, set per
+//! project type, is withheld into a buffer pool the contract holds; those
+//! credits are issued but never minted, so they cannot be traded or retired.
+//! A reported reversal cancels pool credits first, oldest batch onwards, and
+//! only what the pool cannot cover is cancelled from holders, in proportion to
+//! their balances. Holders are covered a page at a time, batch by batch, and
+//! no credits move until the last page is done.
+//!
+//! Registered projects count the share withheld from their issuance in their
+//! own `credits_buffered`, but keep no pool batches: reversals cover the
+//! contract's own project only, and never draw on those counts.
+
+use cosmwasm_std::{Addr, Decimal, Order, StdResult, Storage, Uint128, Uint256};
+use cw_storage_plus::Bound;
+use rwa_common::token::burn_tokens;
+use rwa_common::{DEFAULT_LIMIT, MAX_LIMIT};
+
+use crate::batches::debit_batches;
+use crate::error::ContractError;
+use crate::msg::BufferPoolResponse;
+use crate::state::{
+    BatchAmount, CreditAllocation, PendingReversal, ProjectType, BATCH_HOLDERS, BUFFER_POOL,
+    BUFFER_RATES, REVERSAL_CANCELLATIONS,
+};
+
+/// The share of issuance withheld for `project_type`; none unless configured.
+pub fn buffer_rate(storage: &dyn Storage, project_type: ProjectType) -> StdResult<Decimal> {
+    Ok(BUFFER_RATES
+        .may_load(storage, project_type.as_str())?
+        .unwrap_or_default())
+}
+
+/// Sets the share withheld for `project_type`, clearing it at zero.
+pub fn set_buffer_rate(
+    storage: &mut dyn Storage,
+    project_type: ProjectType,
+    rate: Decimal,
+) -> Result<(), ContractError> {
+    if rate > Decimal::one() {
+        return Err(ContractError::InvalidBufferRate { rate });
+    }
+    if rate.is_zero() {
+        BUFFER_RATES.remove(storage, project_type.as_str());
+    } else {
+        BUFFER_RATES.save(storage, project_type.as_str(), &rate)?;
+    }
+    Ok(())
+}
+
+/// Withholds `rate` of each allocation, rounded down, leaving the allocations
+/// net of it. Returns the credits withheld.
+pub fn withhold(allocations: &mut [CreditAllocation], rate: Decimal) -> Uint128 {
+    let mut withheld = Uint128::zero();
+    for allocation in allocations {
+        let share = allocation.credits * rate;
+        allocation.credits -= share;
+        withheld += share;
+    }
+    withheld
+}
+
+/// Adds `credits` of `batch_id` to the pool.
+pub fn deposit(storage: &mut dyn Storage, batch_id: u64, credits: Uint128) -> StdResult<()> {
+    if credits.is_zero() {
+        return Ok(());
+    }
+    BUFFER_POOL.update(storage, batch_id, |held| -> StdResult<_> {
+        Ok(held.unwrap_or_default().checked_add(credits)?)
+    })?;
+    Ok(())
+}
+
+/// Cancels up to `credits` of `batch_id` from the pool. Returns the credits
+/// cancelled.
+pub fn cancel_batch(
+    storage: &mut dyn Storage,
+    batch_id: u64,
+    credits: Uint128,
+) -> StdResult<Uint128> {
+    let held = BUFFER_POOL.may_load(storage, batch_id)?.unwrap_or_default();
+    let cancelled = held.min(credits);
+    if cancelled == held {
+        BUFFER_POOL.remove(storage, batch_id);
+    } else {
+        BUFFER_POOL.save(storage, batch_id, &(held - cancelled))?;
+    }
+    Ok(cancelled)
+}
+
+/// Cancels up to `credits` from the pool, oldest batch first, and returns what
+/// was cancelled from each batch.
+pub fn cancel_oldest(storage: &mut dyn Storage, credits: Uint128) -> StdResult<Vec<BatchAmount>> {
+    let pool = BUFFER_POOL
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut remaining = credits;
+    let mut cancelled = vec![];
+    for (batch_id, held) in pool {
+        if remaining.is_zero() {
+            break;
+        }
+        let credits = cancel_batch(storage, batch_id, held.min(remaining))?;
+        remaining -= credits;
+        cancelled.push(BatchAmount { batch_id, credits });
+    }
+    Ok(cancelled)
+}
+
+/// Burns the pending reversal's share from up to `limit` more batch holdings,
+/// oldest batch first and in address order within a batch. Returns the
+/// credits burned and whether every holding has been covered.
+///
+/// The running total of what is due is rounded up, so every holder loses its
+/// exact share rounded one way or the other, and the shares add up to the
+/// reversal's credits once all balances are counted. That needs the balances
+/// to stay put until the last holder is covered.
+pub fn cancel_pro_rata(
+    storage: &mut dyn Storage,
+    pending: &mut PendingReversal,
+    limit: u32,
+) -> Result<(Uint128, bool), ContractError> {
+    let limit = limit.min(MAX_LIMIT) as usize;
+    let start = pending
+        .last_holding
+        .as_ref()
+        .map(|(batch_id, holder)| Bound::exclusive((*batch_id, holder)));
+    let mut holdings = BATCH_HOLDERS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<((u64, Addr), Uint128)>>>()?;
+    let completed = holdings.len() <= limit;
+    holdings.truncate(limit);
+
+    let mut burned = Uint128::zero();
+    for ((batch_id, holder), balance) in holdings {
+        pending.balances_counted = pending.balances_counted.checked_add(balance)?;
+        let counted = pending.balances_counted;
+        let mut due = counted.multiply_ratio(pending.credits, pending.supply);
+        if !(counted.full_mul(pending.credits) % Uint256::from(pending.supply)).is_zero() {
+            due += Uint128::one();
+        }
+
+        let share = due - pending.cancelled;
+        if !share.is_zero() {
+            debit_batches(storage, &holder, Some(batch_id), share)?;
+            burn_tokens(storage, &holder, share)?;
+            REVERSAL_CANCELLATIONS.update(
+                storage,
+                (&pending.reversal_id, &holder),
+                |cancelled| -> StdResult<_> {
+                    Ok(cancelled.unwrap_or_default().checked_add(share)?)
+                },
+            )?;
+            pending.cancelled = due;
+            burned += share;
+        }
+        pending.last_holding = Some((batch_id, holder));
+    }
+    Ok((burned, completed))
+}
+
+/// Up to `limit` of the credits `reversal_id` cancelled from holders, in
+/// address order, starting after `start_after`.
+pub fn reversal_cancellations(
+    storage: &dyn Storage,
+    reversal_id: &str,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<CreditAllocation>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    REVERSAL_CANCELLATIONS
+        .prefix(reversal_id)
+        .range(
+            storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(recipient, credits)| CreditAllocation { recipient, credits }))
+        .collect()
+}
+
+/// Splits `credits` between `weights`, which add up to `total`, in proportion
+/// to each. Shares are rounded down, and the remainder goes one credit at a
+/// time to the first accounts whose share was rounded. Accounts with no share
+/// are left out.
+pub fn split_pro_rata(
+    weights: Vec<(Addr, Uint128)>,
+    total: Uint128,
+    credits: Uint128,
+) -> Vec<CreditAllocation> {
+    if credits.is_zero() || total.is_zero() {
+        return vec![];
+    }
+
+    let mut shares = Vec::with_capacity(weights.len());
+    let mut allotted = Uint128::zero();
+    for (account, weight) in weights {
+        let share = weight.multiply_ratio(credits, total);
+        let rounded = !(weight.full_mul(credits) % Uint256::from(total)).is_zero();
+        allotted += share;
+        shares.push((account, share, rounded));
+    }
+
+    // The fractions rounded away add up to the remainder, so there are always
+    // enough rounded accounts to take it
+    let mut remainder = credits - allotted;
+    for (_, share, rounded) in &mut shares {
+        if remainder.is_zero() {
+            break;
+        }
+        if *rounded {
+            *share += Uint128::one();
+            remainder -= Uint128::one();
+        }
+    }
+
+    shares
+        .into_iter()
+        .filter(|(_, share, _)| !share.is_zero())
+        .map(|(recipient, credits, _)| CreditAllocation { recipient, credits })
+        .collect()
+}
+
+/// The pool's rate for `project_type` and the credits it holds.
+pub fn buffer_pool(
+    storage: &dyn Storage,
+    project_type: ProjectType,
+) -> StdResult<BufferPoolResponse> {
+    let batches = BUFFER_POOL
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(batch_id, credits)| BatchAmount { batch_id, credits }))
+        .collect::<StdResult<Vec<_>>>()?;
+    let credits = batches.iter().try_fold(Uint128::zero(), |total, held| {
+        total.checked_add(held.credits)
+    })?;
+    Ok(BufferPoolResponse {
+        rate: buffer_rate(storage, project_type)?,
+        project_type,
+        credits,
+        batches,
+    })
+}
@@ -0,0 +1,111 @@
+//! Offset claims.
+//!
+//! Retirements are indexed by the account that made them, by the account they
+//! are claimed for and by date, so that one company's retirements over a
+//! reporting period can be summed without scanning every record. Summaries
+//! convert credits to tonnes of CO2 equivalent with the rate of the project
+//! each retirement drew on, and break the totals down by retirement purpose.
+
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{Addr, Decimal, Order, StdError, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::Bound;
+
+use crate::msg::{PurposeTotals, RetirementSummaryResponse, RetirementTotals};
+use crate::state::{
+    RetirementRecord, CARBON_CREDIT_INFO, PROJECTS, PROJECT_RETIREMENTS, RETIREMENTS,
+};
+use crate::tags::index_record;
+
+/// Records a retirement under its id, indexing it and filing it by project.
+pub fn save_retirement(
+    storage: &mut dyn Storage,
+    retirement_id: &str,
+    record: &RetirementRecord,
+) -> StdResult<()> {
+    RETIREMENTS.save(storage, retirement_id, record)?;
+    index_record(
+        storage,
+        &PROJECT_RETIREMENTS,
+        record.project_id.as_deref(),
+        retirement_id,
+    )
+}
+
+/// Indexes the retirements recorded before the records were indexed.
+pub fn reindex_retirements(storage: &mut dyn Storage) -> StdResult<()> {
+    let records = RETIREMENTS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (retirement_id, record) in records {
+        RETIREMENTS.save(storage, &retirement_id, &record)?;
+    }
+    Ok(())
+}
+
+/// The retirements claimed for `entity` from `from` up to, but not including,
+/// `to`: those it made for itself and those made with it as the beneficiary.
+pub fn retirement_summary(
+    storage: &dyn Storage,
+    entity: Addr,
+    from: Timestamp,
+    to: Timestamp,
+) -> StdResult<RetirementSummaryResponse> {
+    let mut total = RetirementTotals::default();
+    let mut by_purpose = BTreeMap::<String, RetirementTotals>::new();
+    // An empty id sorts before every retirement of the same second. The
+    // index's `prefix_range` cannot be used, as it reads the index entries as
+    // records.
+    let retirements = RETIREMENTS
+        .idx
+        .beneficiary
+        .sub_prefix(entity.clone())
+        .range(
+            storage,
+            Some(Bound::inclusive((from.seconds(), String::new()))),
+            Some(Bound::exclusive((to.seconds(), String::new()))),
+            Order::Ascending,
+        );
+    for item in retirements {
+        let (_, record) = item?;
+        let co2_equivalent = co2_equivalent(storage, &record)?;
+        total.add(record.credits_retired, co2_equivalent)?;
+        by_purpose
+            .entry(record.retirement_purpose)
+            .or_default()
+            .add(record.credits_retired, co2_equivalent)?;
+    }
+    Ok(RetirementSummaryResponse {
+        entity,
+        from,
+        to,
+        total,
+        by_purpose: by_purpose
+            .into_iter()
+            .map(|(retirement_purpose, totals)| PurposeTotals {
+                retirement_purpose,
+                totals,
+            })
+            .collect(),
+    })
+}
+
+/// The tonnes of CO2 equivalent `record` retired.
+fn co2_equivalent(storage: &dyn Storage, record: &RetirementRecord) -> StdResult<Decimal> {
+    let project = match &record.project_id {
+        Some(project_id) => PROJECTS.load(storage, project_id)?,
+        None => CARBON_CREDIT_INFO.load(storage)?,
+    };
+    let credits = Decimal::from_atomics(record.credits_retired, 0)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    Ok(credits.checked_mul(project.co2_equivalent_per_credit)?)
+}
+
+impl RetirementTotals {
+    fn add(&mut self, credits: Uint128, co2_equivalent: Decimal) -> StdResult<()> {
+        self.retirements += 1;
+        self.credits_retired = self.credits_retired.checked_add(credits)?;
+        self.co2_equivalent = self.co2_equivalent.checked_add(co2_equivalent)?;
+        Ok(())
+    }
+}
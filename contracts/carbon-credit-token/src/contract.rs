@@ -1,391 +1,2139 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
-    Uint128, Addr, Storage, CosmosMsg, BankMsg, Coin, Decimal, Timestamp,
+    from_json, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
-use cw20_base::{
-    contract::{execute as cw20_execute, instantiate as cw20_instantiate, query as cw20_query},
-    msg::{ExecuteMsg as Cw20ExecuteMsg, InstantiateMsg as Cw20InstantiateMsg, QueryMsg as Cw20QueryMsg},
-    state::{MinterData, TokenInfo},
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use cw20_base::allowances::deduct_allowance;
+use cw20_base::contract::instantiate as cw20_instantiate;
+use cw20_base::state::TOKEN_INFO;
+use cw_storage_plus::Bound;
+use retirement_certificate::msg::{
+    CertificateMetadata, ExecuteMsg as CertificateExecuteMsg,
+    InstantiateMsg as CertificateInstantiateMsg,
 };
-use cw_storage_plus::{Item, Map};
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-
-// Custom state for carbon credit specific data
-pub const CARBON_CREDIT_INFO: Item<CarbonCreditInfo> = Item::new("carbon_credit_info");
-pub const VERIFICATION_RECORDS: Map<String, VerificationRecord> = Map::new("verification_records");
-pub const RETIREMENT_RECORDS: Map<String, RetirementRecord> = Map::new("retirement_records");
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct CarbonCreditInfo {
-    pub project_id: String,
-    pub project_name: String,
-    pub project_type: String, // e.g., "renewable_energy", "forest_conservation", "carbon_capture"
-    pub verification_standard: String, // e.g., "VCS", "Gold Standard", "CAR"
-    pub vintage_year: u32,
-    pub country: String,
-    pub total_credits_issued: Uint128,
-    pub credits_retired: Uint128,
-    pub credits_available: Uint128,
-    pub co2_equivalent_per_credit: Decimal, // tons of CO2 per credit
-    pub verification_body: Addr,
-    pub project_developer: Addr,
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct VerificationRecord {
-    pub verification_id: String,
-    pub verification_date: Timestamp,
-    pub credits_verified: Uint128,
-    pub verification_body: Addr,
-    pub verification_report_url: String,
-    pub status: VerificationStatus,
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub enum VerificationStatus {
-    Pending,
-    Verified,
-    Rejected,
-    Expired,
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct RetirementRecord {
-    pub retirement_id: String,
-    pub retirement_date: Timestamp,
-    pub credits_retired: Uint128,
-    pub retirement_purpose: String,
-    pub retirement_entity: Addr,
-    pub retirement_certificate_url: String,
-}
-
-// Extended instantiate message
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct InstantiateMsg {
-    pub cw20_base: Cw20InstantiateMsg,
-    pub carbon_credit_info: CarbonCreditInfo,
-}
-
-// Extended execute messages
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum ExecuteMsg {
-    // Standard CW20 messages
-    Transfer { recipient: String, amount: Uint128 },
-    Burn { amount: Uint128 },
-    Send { contract: String, amount: Uint128, msg: Binary },
-    IncreaseAllowance { spender: String, amount: Uint128, expires: Option<cw20_base::msg::Expiration> },
-    DecreaseAllowance { spender: String, amount: Uint128, expires: Option<cw20_base::msg::Expiration> },
-    TransferFrom { owner: String, recipient: String, amount: Uint128 },
-    SendFrom { owner: String, contract: String, amount: Uint128, msg: Binary },
-    Mint { recipient: String, amount: Uint128 },
-    UpdateMinter { new_minter: Option<String> },
-    UpdateMarketing { project: Option<String>, description: Option<String>, marketing: Option<Addr> },
-    UploadLogo(LogoInfo),
-    
-    // Carbon credit specific messages
-    VerifyCredits {
-        verification_id: String,
-        credits_to_verify: Uint128,
-        verification_report_url: String,
-    },
-    RetireCredits {
-        retirement_id: String,
-        credits_to_retire: Uint128,
-        retirement_purpose: String,
-        retirement_certificate_url: String,
-    },
-    UpdateVerificationStatus {
-        verification_id: String,
-        status: VerificationStatus,
-    },
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct LogoInfo {
-    pub url: Option<String>,
-    pub upload: Option<String>,
-}
-
-// Extended query messages
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum QueryMsg {
-    // Standard CW20 queries
-    Balance { address: String },
-    TokenInfo {},
-    Minter {},
-    Allowance { owner: String, spender: String },
-    AllAllowances { owner: String, start_after: Option<String>, limit: Option<u32> },
-    AllAccounts { start_after: Option<String>, limit: Option<u32> },
-    MarketingInfo {},
-    DownloadLogo {},
-    
-    // Carbon credit specific queries
-    CarbonCreditInfo {},
-    VerificationRecord { verification_id: String },
-    AllVerificationRecords { start_after: Option<String>, limit: Option<u32> },
-    RetirementRecord { retirement_id: String },
-    AllRetirementRecords { start_after: Option<String>, limit: Option<u32> },
-    AvailableCredits {},
-    RetiredCredits {},
-}
-
-#[entry_point]
+use rwa_common::migration::rewrite_item;
+use rwa_common::roles::{seed_legacy_roles, seed_roles};
+use rwa_common::sanctions::SANCTION_SCREENING;
+use rwa_common::token::{burn_tokens, mint_tokens};
+use rwa_common::{
+    action_response, assign_indexed_record_id, assign_record_id, ensure_admin, ensure_not_paused,
+    ensure_not_sanctioned, ensure_role, ensure_upgrade, execute_cw20, execute_pause, execute_roles,
+    execute_sanctions, paginate_records, query_cw20, query_pause, query_roles, query_sanctions,
+    record_response, Cw20ExecuteMsg, Drift, PauseCategory, DEFAULT_LIMIT, MAX_LIMIT,
+};
+
+use crate::authorization::{
+    ensure_authorized, ensure_project_authorized, set_batch_authorization,
+    set_project_authorization,
+};
+use crate::batches::{
+    batch_balance, batch_balances, batch_holders, credit_batches, debit_batches, open_base_batch,
+    open_batch, track_base_holders, BASE_BATCH,
+};
+use crate::buffer::{
+    buffer_pool, buffer_rate, cancel_batch, cancel_oldest, cancel_pro_rata, deposit,
+    reversal_cancellations, set_buffer_rate, withhold,
+};
+use crate::claims::{reindex_retirements, retirement_summary, save_retirement};
+use crate::error::ContractError;
+use crate::expiry::{
+    debit_for_transfer, ensure_project_vintage_valid, ensure_vintages_valid, sweep_expired,
+};
+use crate::forward::{
+    deliver_forward, forward_position, forward_positions, issue_forward, transfer_forward,
+};
+use crate::msg::{
+    CarbonCreditExecuteMsg, CarbonCreditQueryMsg, ExecuteMsg, InstantiateMsg, IssuanceRecipient,
+    MigrateMsg, MonitoringPeriod, ProjectAmount, QueryMsg, ReceiveMsg, ReconcileSupplyResponse,
+    RetireMsg,
+};
+use crate::registry::{
+    credit_project, debit_project, is_operator, load_project, project_balance, project_balances,
+    register_project, set_operator,
+};
+use crate::serials::{
+    register_block, release_block, resolve_registry_serials, retire_lowest, retire_range,
+    serial_status,
+};
+use crate::state::{
+    BatchAmount, BatchAuthorization, CarbonCreditInfo, CreditAllocation, CreditUse,
+    PendingReversal, ProjectTags, RegistrySerials, RetirementRecord, ReversalRecord, StatusChange,
+    TagAttestation, VerificationRecord, VerificationStatus, BATCHES, BATCH_AUTHORIZATIONS,
+    CARBON_CREDIT_INFO, CERTIFICATE_COLLECTION, FORWARD_SERIES, FORWARD_TOKENS,
+    HOST_COUNTRY_AUTHORITY, PENDING_REVERSAL, PROJECTS, PROJECT_AUTHORIZATIONS, PROJECT_DEVELOPER,
+    PROJECT_RETIREMENTS, PROJECT_TAGS, PROJECT_VERIFICATIONS, RETIREMENTS, RETIREMENTS_NAMESPACE,
+    REVERSAL_RECORDS, ROLES, SENT_BATCHES, VERIFICATION_BODY, VERIFICATION_RECORDS, VINTAGE_POLICY,
+};
+use crate::tags::{
+    attest_project_tags, index_project_records, index_record, projects_by_tag, records_by_tag,
+    set_project_tags, tag_history,
+};
+
+// Version info for migration
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Reply to the instantiation of the retirement certificate collection
+const CERTIFICATE_COLLECTION_REPLY: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     // Store carbon credit specific information
     CARBON_CREDIT_INFO.save(deps.storage, &msg.carbon_credit_info)?;
-    
+
+    // Seed the role registry with the holders named in the carbon credit info
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(&admin)?,
+        None => info.sender.clone(),
+    };
+    seed_roles(
+        deps.storage,
+        &admin,
+        &initial_role_holders(&msg.carbon_credit_info),
+    )?;
+
+    if let Some(enabled) = msg.sanction_screening {
+        SANCTION_SCREENING.save(deps.storage, &enabled)?;
+    }
+
+    // The certificate collection is instantiated once the token exists
+    let certificates = match msg.certificate_code_id {
+        Some(code_id) => Some(instantiate_certificates(
+            deps.as_ref(),
+            &env,
+            code_id,
+            &msg.carbon_credit_info.project_name,
+            &msg.cw20_base.symbol,
+        )?),
+        None => None,
+    };
+
     // Initialize the base CW20 contract
-    cw20_instantiate(deps, env, info, msg.cw20_base)
+    let res = cw20_instantiate(deps.branch(), env, info, msg.cw20_base)?;
+
+    // Credits issued at instantiation make up the base batch
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+    open_base_batch(
+        deps.storage,
+        msg.carbon_credit_info.vintage_year,
+        token_info.total_supply,
+    )?;
+    track_base_holders(deps.storage)?;
+
+    // cw20-base records its own name, so ours has to be written afterwards
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(res.add_submessages(certificates))
 }
 
-#[entry_point]
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        CERTIFICATE_COLLECTION_REPLY => {
+            // The new contract's address is reported in its instantiate event
+            let res = msg.result.into_result().map_err(StdError::generic_err)?;
+            let address = res
+                .events
+                .iter()
+                .filter(|event| event.ty == "instantiate")
+                .flat_map(|event| &event.attributes)
+                .find(|attribute| attribute.key == "_contract_address")
+                .ok_or_else(|| StdError::generic_err("certificate collection address not found"))?;
+            let collection = deps.api.addr_validate(&address.value)?;
+            CERTIFICATE_COLLECTION.save(deps.storage, &collection)?;
+
+            Ok(action_response("instantiate_certificates")
+                .add_attribute("certificate_collection", collection))
+        }
+        id => Err(StdError::generic_err(format!("unknown reply id {id}")).into()),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     match msg {
         // Handle standard CW20 messages
-        ExecuteMsg::Transfer { recipient, amount } => {
-            cw20_execute(deps, env, info, Cw20ExecuteMsg::Transfer { recipient, amount })
-        }
-        ExecuteMsg::Burn { amount } => {
-            cw20_execute(deps, env, info, Cw20ExecuteMsg::Burn { amount })
-        }
-        ExecuteMsg::Send { contract, amount, msg } => {
-            cw20_execute(deps, env, info, Cw20ExecuteMsg::Send { contract, amount, msg })
-        }
-        ExecuteMsg::IncreaseAllowance { spender, amount, expires } => {
-            cw20_execute(deps, env, info, Cw20ExecuteMsg::IncreaseAllowance { spender, amount, expires })
-        }
-        ExecuteMsg::DecreaseAllowance { spender, amount, expires } => {
-            cw20_execute(deps, env, info, Cw20ExecuteMsg::DecreaseAllowance { spender, amount, expires })
-        }
-        ExecuteMsg::TransferFrom { owner, recipient, amount } => {
-            cw20_execute(deps, env, info, Cw20ExecuteMsg::TransferFrom { owner, recipient, amount })
-        }
-        ExecuteMsg::SendFrom { owner, contract, amount, msg } => {
-            cw20_execute(deps, env, info, Cw20ExecuteMsg::SendFrom { owner, contract, amount, msg })
-        }
-        ExecuteMsg::Mint { recipient, amount } => {
-            cw20_execute(deps, env, info, Cw20ExecuteMsg::Mint { recipient, amount })
-        }
-        ExecuteMsg::UpdateMinter { new_minter } => {
-            cw20_execute(deps, env, info, Cw20ExecuteMsg::UpdateMinter { new_minter })
-        }
-        ExecuteMsg::UpdateMarketing { project, description, marketing } => {
-            cw20_execute(deps, env, info, Cw20ExecuteMsg::UpdateMarketing { project, description, marketing })
-        }
-        ExecuteMsg::UploadLogo(logo_info) => {
-            cw20_execute(deps, env, info, Cw20ExecuteMsg::UploadLogo(logo_info))
+        ExecuteMsg::Cw20(msg) => {
+            // Credits only come into existence through verification
+            if let Cw20ExecuteMsg::Mint { .. } = msg {
+                return Err(ContractError::MintDisabled {});
+            }
+            // and only leave supply through a recorded retirement
+            if let Cw20ExecuteMsg::Burn { .. } = msg {
+                return Err(ContractError::BurnDisabled {});
+            }
+
+            // Honour the circuit breakers and screen everyone tokens move between
+            if let Some(category) = msg.pause_category() {
+                ensure_not_paused(deps.storage, category)?;
+            }
+            ensure_not_sanctioned(deps.as_ref(), &msg.token_parties(info.sender.as_str()))?;
+
+            // The oldest batches move first
+            if let Some((from, to, amount)) = cw20_movement(deps.as_ref(), &info.sender, &msg)? {
+                ensure_no_reversal_in_progress(deps.storage)?;
+                let sent = matches!(
+                    msg,
+                    Cw20ExecuteMsg::Send { .. } | Cw20ExecuteMsg::SendFrom { .. }
+                );
+                // Credits sent to this contract are retired by the hook, which
+                // checks their vintages for the use declared and retires
+                // exactly the batches drawn here
+                let drawn = if sent && to == env.contract.address {
+                    debit_batches(deps.storage, &from, None, amount)?
+                } else {
+                    debit_for_transfer(deps.storage, &from, None, amount, env.block.time)?
+                };
+                credit_batches(deps.storage, &to, &drawn)?;
+                // The receiving contract's hook reads the batches a send drew
+                if sent {
+                    SENT_BATCHES.save(deps.storage, &drawn)?;
+                }
+            }
+
+            Ok(execute_cw20(deps, env, info, msg)?)
         }
-        
+
+        // Handle role registry messages
+        ExecuteMsg::Roles(msg) => Ok(execute_roles(deps, info, ROLES, msg)?),
+
+        // Handle circuit breaker messages
+        ExecuteMsg::Pause(msg) => Ok(execute_pause(deps, info, msg)?),
+
+        // Handle sanction screening messages
+        ExecuteMsg::Sanctions(msg) => Ok(execute_sanctions(deps, info, msg)?),
+
         // Handle carbon credit specific messages
-        ExecuteMsg::VerifyCredits { verification_id, credits_to_verify, verification_report_url } => {
-            verify_credits(deps, env, info, verification_id, credits_to_verify, verification_report_url)
-        }
-        ExecuteMsg::RetireCredits { retirement_id, credits_to_retire, retirement_purpose, retirement_certificate_url } => {
-            retire_credits(deps, env, info, retirement_id, credits_to_retire, retirement_purpose, retirement_certificate_url)
-        }
-        ExecuteMsg::UpdateVerificationStatus { verification_id, status } => {
-            update_verification_status(deps, env, info, verification_id, status)
+        ExecuteMsg::CarbonCredit(msg) => {
+            let category = msg.pause_category();
+            if let Some(category) = category {
+                ensure_not_paused(deps.storage, category)?;
+            }
+            // Balances stay put while a reversal works through its holders
+            let moves_credits = matches!(
+                category,
+                Some(
+                    PauseCategory::Issuance | PauseCategory::Transfers | PauseCategory::Retirement
+                )
+            ) || matches!(
+                msg,
+                CarbonCreditExecuteMsg::UpdateVerificationStatus { .. }
+                    | CarbonCreditExecuteMsg::ReportReversal { .. }
+            );
+            if moves_credits {
+                ensure_no_reversal_in_progress(deps.storage)?;
+            }
+            match msg {
+                CarbonCreditExecuteMsg::VerifyCredits {
+                    verification_id,
+                    credits_to_verify,
+                    verification_report_url,
+                    recipients,
+                    vintage_year,
+                    methodology_version,
+                    registry_serials,
+                    forward_series,
+                } => verify_credits(
+                    deps,
+                    env,
+                    info,
+                    NewVerification {
+                        verification_id,
+                        credits_to_verify,
+                        verification_report_url,
+                        recipients,
+                        vintage_year,
+                        methodology_version,
+                        registry_serials,
+                        forward_series,
+                    },
+                    VerificationStatus::Verified,
+                ),
+                CarbonCreditExecuteMsg::SubmitVerification {
+                    verification_id,
+                    credits_to_verify,
+                    verification_report_url,
+                    recipients,
+                    vintage_year,
+                    methodology_version,
+                    registry_serials,
+                } => verify_credits(
+                    deps,
+                    env,
+                    info,
+                    NewVerification {
+                        verification_id,
+                        credits_to_verify,
+                        verification_report_url,
+                        recipients,
+                        vintage_year,
+                        methodology_version,
+                        registry_serials,
+                        forward_series: None,
+                    },
+                    VerificationStatus::Pending,
+                ),
+                CarbonCreditExecuteMsg::RetireCredits {
+                    retirement_id,
+                    credits_to_retire,
+                    retirement_purpose,
+                    retirement_certificate_url,
+                    batch_id,
+                    registry_serials,
+                    beneficiary,
+                    beneficiary_name,
+                    retirement_use,
+                } => retire_credits(
+                    deps,
+                    env,
+                    info,
+                    None,
+                    NewRetirement {
+                        retirement_id,
+                        credits_to_retire,
+                        retirement_purpose,
+                        retirement_certificate_url,
+                        batch_id,
+                        registry_serials,
+                        beneficiary,
+                        beneficiary_name,
+                        retirement_use: retirement_use.unwrap_or_default(),
+                        sent_batches: None,
+                    },
+                ),
+                CarbonCreditExecuteMsg::RetireCreditsFrom {
+                    owner,
+                    retirement_id,
+                    credits_to_retire,
+                    retirement_purpose,
+                    retirement_certificate_url,
+                    batch_id,
+                    registry_serials,
+                    beneficiary,
+                    beneficiary_name,
+                    retirement_use,
+                } => retire_credits(
+                    deps,
+                    env,
+                    info,
+                    Some(owner),
+                    NewRetirement {
+                        retirement_id,
+                        credits_to_retire,
+                        retirement_purpose,
+                        retirement_certificate_url,
+                        batch_id,
+                        registry_serials,
+                        beneficiary,
+                        beneficiary_name,
+                        retirement_use: retirement_use.unwrap_or_default(),
+                        sent_batches: None,
+                    },
+                ),
+                CarbonCreditExecuteMsg::TransferBatch {
+                    batch_id,
+                    recipient,
+                    amount,
+                } => transfer_batch(deps, env, info, batch_id, recipient, amount),
+                CarbonCreditExecuteMsg::UpdateVerificationStatus {
+                    verification_id,
+                    status,
+                    reason,
+                } => update_verification_status(deps, env, info, verification_id, status, reason),
+                CarbonCreditExecuteMsg::Receive(wrapper) => receive_cw20(deps, env, info, wrapper),
+                CarbonCreditExecuteMsg::ApproveForwardToken { token } => {
+                    ensure_admin(deps.storage, &info.sender, "approve forward tokens")?;
+                    let token = deps.api.addr_validate(&token)?;
+                    FORWARD_TOKENS.save(deps.storage, &token, &Empty {})?;
+                    Ok(action_response("approve_forward_token").add_attribute("token", token))
+                }
+                CarbonCreditExecuteMsg::RevokeForwardToken { token } => {
+                    ensure_admin(deps.storage, &info.sender, "revoke forward tokens")?;
+                    let token = deps.api.addr_validate(&token)?;
+                    FORWARD_TOKENS.remove(deps.storage, &token);
+                    Ok(action_response("revoke_forward_token").add_attribute("token", token))
+                }
+                CarbonCreditExecuteMsg::SetBufferRate { project_type, rate } => {
+                    ensure_admin(deps.storage, &info.sender, "set buffer rates")?;
+                    set_buffer_rate(deps.storage, project_type, rate)?;
+                    Ok(action_response("set_buffer_rate")
+                        .add_attribute("project_type", project_type.as_str())
+                        .add_attribute("rate", rate.to_string()))
+                }
+                CarbonCreditExecuteMsg::ReportReversal {
+                    reversal_id,
+                    credits,
+                    reason,
+                } => report_reversal(deps, env, info, reversal_id, credits, reason),
+                CarbonCreditExecuteMsg::ContinueReversal { limit } => {
+                    let pending = PENDING_REVERSAL
+                        .may_load(deps.storage)?
+                        .ok_or(ContractError::NoReversalInProgress {})?;
+                    let reversal_id = pending.reversal_id.clone();
+                    let (cancelled, completed) =
+                        cancel_next_holders(deps.storage, pending, limit.unwrap_or(DEFAULT_LIMIT))?;
+                    Ok(action_response("continue_reversal")
+                        .add_attribute("reversal_id", reversal_id)
+                        .add_attribute("holder_credits_cancelled", cancelled)
+                        .add_attribute("completed", completed.to_string()))
+                }
+                CarbonCreditExecuteMsg::RegisterProject { project } => {
+                    ensure_admin(deps.storage, &info.sender, "register projects")?;
+                    deps.api.addr_validate(project.verification_body.as_str())?;
+                    deps.api.addr_validate(project.project_developer.as_str())?;
+                    let project = register_project(deps.storage, project)?;
+                    Ok(action_response("register_project")
+                        .add_attribute("project_id", project.project_id)
+                        .add_attribute("verification_body", project.verification_body)
+                        .add_attribute("project_developer", project.project_developer))
+                }
+                CarbonCreditExecuteMsg::VerifyProjectCredits {
+                    project_id,
+                    verification_id,
+                    credits_to_verify,
+                    verification_report_url,
+                    recipients,
+                } => verify_project_credits(
+                    deps,
+                    env,
+                    info,
+                    project_id,
+                    NewVerification {
+                        verification_id,
+                        credits_to_verify,
+                        verification_report_url,
+                        recipients,
+                        vintage_year: None,
+                        methodology_version: None,
+                        registry_serials: None,
+                        forward_series: None,
+                    },
+                ),
+                CarbonCreditExecuteMsg::TransferProjectCredits {
+                    from,
+                    recipient,
+                    amounts,
+                } => transfer_project_credits(deps, env, info, from, recipient, amounts),
+                CarbonCreditExecuteMsg::RetireProjectCredits {
+                    project_id,
+                    retirement_id,
+                    credits_to_retire,
+                    retirement_purpose,
+                    retirement_certificate_url,
+                    beneficiary,
+                    beneficiary_name,
+                    retirement_use,
+                } => retire_project_credits(
+                    deps,
+                    env,
+                    info,
+                    project_id,
+                    NewRetirement {
+                        retirement_id,
+                        credits_to_retire,
+                        retirement_purpose,
+                        retirement_certificate_url,
+                        batch_id: None,
+                        registry_serials: None,
+                        beneficiary,
+                        beneficiary_name,
+                        retirement_use: retirement_use.unwrap_or_default(),
+                        sent_batches: None,
+                    },
+                ),
+                CarbonCreditExecuteMsg::ApproveAll { operator } => {
+                    let operator = deps.api.addr_validate(&operator)?;
+                    set_operator(deps.storage, &info.sender, &operator, true)?;
+                    Ok(action_response("approve_all")
+                        .add_attribute("owner", info.sender)
+                        .add_attribute("operator", operator))
+                }
+                CarbonCreditExecuteMsg::RevokeAll { operator } => {
+                    let operator = deps.api.addr_validate(&operator)?;
+                    set_operator(deps.storage, &info.sender, &operator, false)?;
+                    Ok(action_response("revoke_all")
+                        .add_attribute("owner", info.sender)
+                        .add_attribute("operator", operator))
+                }
+                CarbonCreditExecuteMsg::SetBatchAuthorization {
+                    batch_id,
+                    status,
+                    letter_of_authorization,
+                    authorized_uses,
+                    corresponding_adjustment,
+                } => {
+                    ensure_role(
+                        deps.storage,
+                        &info.sender,
+                        &[HOST_COUNTRY_AUTHORITY],
+                        || ContractError::NotHostCountryAuthority {
+                            action: "authorize batches".to_string(),
+                        },
+                    )?;
+                    let authorization = BatchAuthorization {
+                        status,
+                        letter_of_authorization,
+                        authorized_uses,
+                        corresponding_adjustment,
+                        updated_by: info.sender,
+                        updated_at: env.block.time,
+                    };
+                    set_batch_authorization(deps.storage, batch_id, &authorization)?;
+                    Ok(action_response("set_batch_authorization")
+                        .add_attribute("batch_id", batch_id.to_string())
+                        .add_attribute("status", format!("{:?}", authorization.status)))
+                }
+                CarbonCreditExecuteMsg::SetProjectAuthorization {
+                    project_id,
+                    status,
+                    letter_of_authorization,
+                    authorized_uses,
+                    corresponding_adjustment,
+                } => {
+                    ensure_role(
+                        deps.storage,
+                        &info.sender,
+                        &[HOST_COUNTRY_AUTHORITY],
+                        || ContractError::NotHostCountryAuthority {
+                            action: "authorize projects".to_string(),
+                        },
+                    )?;
+                    let authorization = BatchAuthorization {
+                        status,
+                        letter_of_authorization,
+                        authorized_uses,
+                        corresponding_adjustment,
+                        updated_by: info.sender,
+                        updated_at: env.block.time,
+                    };
+                    set_project_authorization(deps.storage, &project_id, &authorization)?;
+                    Ok(action_response("set_project_authorization")
+                        .add_attribute("project_id", project_id)
+                        .add_attribute("status", format!("{:?}", authorization.status)))
+                }
+                CarbonCreditExecuteMsg::IssueForward {
+                    schedule,
+                    recipients,
+                } => issue_forward_units(deps, env, info, schedule, recipients),
+                CarbonCreditExecuteMsg::TransferForward {
+                    series_id,
+                    recipient,
+                    amount,
+                } => {
+                    ensure_not_sanctioned(deps.as_ref(), &[info.sender.as_str(), &recipient])?;
+                    let to = deps.api.addr_validate(&recipient)?;
+                    transfer_forward(deps.storage, series_id, &info.sender, &to, amount)?;
+                    Ok(action_response("transfer_forward")
+                        .add_attribute("series_id", series_id.to_string())
+                        .add_attribute("from", info.sender)
+                        .add_attribute("to", to)
+                        .add_attribute("amount", amount))
+                }
+                CarbonCreditExecuteMsg::SetProjectTags { project_id, tags } => {
+                    tag_project(deps, env, info, project_id, tags)
+                }
+                CarbonCreditExecuteMsg::AttestProjectTags {
+                    project_id,
+                    revision,
+                    report_url,
+                } => attest_tags(deps, env, info, project_id, revision, report_url),
+                CarbonCreditExecuteMsg::SetVintagePolicy { policy } => {
+                    ensure_admin(deps.storage, &info.sender, "set the vintage policy")?;
+                    let mut res = action_response("set_vintage_policy");
+                    match policy {
+                        Some(policy) => {
+                            VINTAGE_POLICY.save(deps.storage, &policy)?;
+                            res = res
+                                .add_attribute("validity_years", policy.validity_years.to_string())
+                                .add_attribute(
+                                    "block_transfers",
+                                    policy.block_transfers.to_string(),
+                                );
+                        }
+                        None => VINTAGE_POLICY.remove(deps.storage),
+                    }
+                    Ok(res)
+                }
+                CarbonCreditExecuteMsg::SweepExpired { start_after, limit } => {
+                    let (swept, last_batch_id) = sweep_expired(
+                        deps.storage,
+                        env.block.time,
+                        start_after,
+                        limit.unwrap_or(DEFAULT_LIMIT),
+                    )?;
+                    let mut res = action_response("sweep_expired")
+                        .add_attribute("records_expired", swept.len().to_string());
+                    if !swept.is_empty() {
+                        res = res.add_attribute("verification_ids", swept.join(","));
+                    }
+                    // Where the next page starts
+                    if let Some(batch_id) = last_batch_id {
+                        res = res.add_attribute("last_batch_id", batch_id.to_string());
+                    }
+                    Ok(res)
+                }
+            }
         }
     }
 }
 
-#[entry_point]
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         // Handle standard CW20 queries
-        QueryMsg::Balance { address } => {
-            cw20_query(deps, env, Cw20QueryMsg::Balance { address })
-        }
-        QueryMsg::TokenInfo {} => {
-            cw20_query(deps, env, Cw20QueryMsg::TokenInfo {})
+        QueryMsg::Cw20(msg) => query_cw20(deps, env, msg),
+
+        // Handle role registry queries
+        QueryMsg::Roles(msg) => query_roles(deps, msg),
+
+        // Handle circuit breaker queries
+        QueryMsg::Pause(msg) => query_pause(deps, msg),
+
+        // Handle sanction screening queries
+        QueryMsg::Sanctions(msg) => query_sanctions(deps, msg),
+
+        // Handle carbon credit specific queries
+        QueryMsg::CarbonCredit(msg) => match msg {
+            CarbonCreditQueryMsg::CarbonCreditInfo {} => {
+                to_json_binary(&CARBON_CREDIT_INFO.load(deps.storage)?)
+            }
+            CarbonCreditQueryMsg::VerificationRecord { verification_id } => {
+                to_json_binary(&VERIFICATION_RECORDS.load(deps.storage, &verification_id)?)
+            }
+            CarbonCreditQueryMsg::AllVerificationRecords { start_after, limit } => {
+                to_json_binary(&paginate_records(
+                    deps.storage,
+                    &VERIFICATION_RECORDS,
+                    start_after.as_deref(),
+                    limit,
+                )?)
+            }
+            CarbonCreditQueryMsg::RetirementRecord { retirement_id } => {
+                to_json_binary(&RETIREMENTS.load(deps.storage, &retirement_id)?)
+            }
+            CarbonCreditQueryMsg::AllRetirementRecords { start_after, limit } => {
+                query_all_retirement_records(deps, start_after, limit)
+            }
+            CarbonCreditQueryMsg::AvailableCredits {} => query_available_credits(deps),
+            CarbonCreditQueryMsg::RetiredCredits {} => query_retired_credits(deps),
+            CarbonCreditQueryMsg::ReconcileSupply {} => query_reconcile_supply(deps),
+            CarbonCreditQueryMsg::Batch { batch_id } => {
+                to_json_binary(&BATCHES.load(deps.storage, batch_id)?)
+            }
+            CarbonCreditQueryMsg::AllBatches { start_after, limit } => {
+                query_all_batches(deps, start_after, limit)
+            }
+            CarbonCreditQueryMsg::SentBatches {} => {
+                to_json_binary(&SENT_BATCHES.may_load(deps.storage)?.unwrap_or_default())
+            }
+            CarbonCreditQueryMsg::BatchBalances {
+                address,
+                start_after,
+                limit,
+            } => {
+                let address = deps.api.addr_validate(&address)?;
+                to_json_binary(&batch_balances(deps.storage, &address, start_after, limit)?)
+            }
+            CarbonCreditQueryMsg::SerialStatus { serial, registry } => {
+                to_json_binary(&serial_status(deps.storage, registry, serial)?)
+            }
+            CarbonCreditQueryMsg::CertificateCollection {} => {
+                to_json_binary(&CERTIFICATE_COLLECTION.may_load(deps.storage)?)
+            }
+            CarbonCreditQueryMsg::CertificateRetirement { token_id } => {
+                query_certificate_retirement(deps, token_id)
+            }
+            CarbonCreditQueryMsg::ForwardTokens { start_after, limit } => {
+                query_forward_tokens(deps, start_after, limit)
+            }
+            CarbonCreditQueryMsg::BufferPool {} => {
+                let project_type = CARBON_CREDIT_INFO.load(deps.storage)?.project_type;
+                to_json_binary(&buffer_pool(deps.storage, project_type)?)
+            }
+            CarbonCreditQueryMsg::BufferRate { project_type } => {
+                to_json_binary(&buffer_rate(deps.storage, project_type)?)
+            }
+            CarbonCreditQueryMsg::ReversalRecord { reversal_id } => {
+                to_json_binary(&REVERSAL_RECORDS.load(deps.storage, &reversal_id)?)
+            }
+            CarbonCreditQueryMsg::AllReversalRecords { start_after, limit } => {
+                to_json_binary(&paginate_records(
+                    deps.storage,
+                    &REVERSAL_RECORDS,
+                    start_after.as_deref(),
+                    limit,
+                )?)
+            }
+            CarbonCreditQueryMsg::ReversalCancellations {
+                reversal_id,
+                start_after,
+                limit,
+            } => {
+                let start_after = start_after
+                    .map(|address| deps.api.addr_validate(&address))
+                    .transpose()?;
+                to_json_binary(&reversal_cancellations(
+                    deps.storage,
+                    &reversal_id,
+                    start_after,
+                    limit,
+                )?)
+            }
+            CarbonCreditQueryMsg::Project { project_id } => {
+                to_json_binary(&PROJECTS.load(deps.storage, &project_id)?)
+            }
+            CarbonCreditQueryMsg::AllProjects { start_after, limit } => to_json_binary(
+                &paginate_records(deps.storage, &PROJECTS, start_after.as_deref(), limit)?,
+            ),
+            CarbonCreditQueryMsg::ProjectBalance { owner, project_id } => {
+                let owner = deps.api.addr_validate(&owner)?;
+                to_json_binary(&project_balance(deps.storage, &owner, &project_id)?)
+            }
+            CarbonCreditQueryMsg::ProjectBalances {
+                owner,
+                start_after,
+                limit,
+            } => {
+                let owner = deps.api.addr_validate(&owner)?;
+                to_json_binary(&project_balances(deps.storage, &owner, start_after, limit)?)
+            }
+            CarbonCreditQueryMsg::IsApprovedForAll { owner, operator } => {
+                let owner = deps.api.addr_validate(&owner)?;
+                let operator = deps.api.addr_validate(&operator)?;
+                to_json_binary(&is_operator(deps.storage, &owner, &operator))
+            }
+            CarbonCreditQueryMsg::BatchAuthorization { batch_id } => {
+                to_json_binary(&BATCH_AUTHORIZATIONS.may_load(deps.storage, batch_id)?)
+            }
+            CarbonCreditQueryMsg::ProjectAuthorization { project_id } => {
+                to_json_binary(&PROJECT_AUTHORIZATIONS.may_load(deps.storage, &project_id)?)
+            }
+            CarbonCreditQueryMsg::ForwardSeries { series_id } => {
+                to_json_binary(&FORWARD_SERIES.load(deps.storage, series_id)?)
+            }
+            CarbonCreditQueryMsg::ForwardPosition { series_id, holder } => {
+                let holder = deps.api.addr_validate(&holder)?;
+                to_json_binary(&forward_position(deps.storage, series_id, &holder)?)
+            }
+            CarbonCreditQueryMsg::ForwardPositions {
+                series_id,
+                start_after,
+                limit,
+            } => {
+                let start_after = start_after
+                    .map(|address| deps.api.addr_validate(&address))
+                    .transpose()?;
+                to_json_binary(&forward_positions(
+                    deps.storage,
+                    series_id,
+                    start_after.as_ref(),
+                    limit,
+                )?)
+            }
+            CarbonCreditQueryMsg::RetirementSummary { entity, from, to } => {
+                let entity = deps.api.addr_validate(&entity)?;
+                to_json_binary(&retirement_summary(deps.storage, entity, from, to)?)
+            }
+            CarbonCreditQueryMsg::ProjectTags { project_id } => {
+                let project_id = tagged_project_id(deps.storage, project_id)?;
+                to_json_binary(&PROJECT_TAGS.may_load(deps.storage, &project_id)?)
+            }
+            CarbonCreditQueryMsg::TagHistory {
+                project_id,
+                start_after,
+                limit,
+            } => {
+                let project_id = tagged_project_id(deps.storage, project_id)?;
+                to_json_binary(&tag_history(deps.storage, &project_id, start_after, limit)?)
+            }
+            CarbonCreditQueryMsg::ProjectsByTag {
+                tag,
+                attested_only,
+                start_after,
+                limit,
+            } => to_json_binary(&projects_by_tag(
+                deps.storage,
+                &tag,
+                attested_only.unwrap_or(false),
+                start_after.as_deref(),
+                limit,
+            )?),
+            CarbonCreditQueryMsg::VerificationRecordsByTag {
+                tag,
+                attested_only,
+                start_after,
+                limit,
+            } => to_json_binary(&records_by_tag(
+                deps.storage,
+                &PROJECT_VERIFICATIONS,
+                |storage, verification_id| VERIFICATION_RECORDS.load(storage, verification_id),
+                &tag,
+                attested_only.unwrap_or(false),
+                start_after.as_deref(),
+                limit,
+            )?),
+            CarbonCreditQueryMsg::RetirementRecordsByTag {
+                tag,
+                attested_only,
+                start_after,
+                limit,
+            } => to_json_binary(&records_by_tag(
+                deps.storage,
+                &PROJECT_RETIREMENTS,
+                |storage, retirement_id| RETIREMENTS.load(storage, retirement_id),
+                &tag,
+                attested_only.unwrap_or(false),
+                start_after.as_deref(),
+                limit,
+            )?),
+            CarbonCreditQueryMsg::VintagePolicy {} => {
+                to_json_binary(&VINTAGE_POLICY.may_load(deps.storage)?)
+            }
+        },
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let from_version = ensure_upgrade(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Rewrite the carbon credit info so fields added since the stored version are
+    // persisted with their defaults
+    rewrite_item(deps.storage, &CARBON_CREDIT_INFO)?;
+
+    // Contracts instantiated before the role registry get one seeded from the
+    // carbon credit info, administered by the contract admin
+    let carbon_credit_info = CARBON_CREDIT_INFO.load(deps.storage)?;
+    seed_legacy_roles(
+        deps.branch(),
+        &env,
+        &initial_role_holders(&carbon_credit_info),
+    )?;
+
+    // Credits in circulation before batches were tracked make up the base batch
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+    open_base_batch(
+        deps.storage,
+        carbon_credit_info.vintage_year,
+        token_info.total_supply,
+    )?;
+    track_base_holders(deps.storage)?;
+
+    // Retirements recorded before the records were indexed, and records
+    // written before they were filed by project
+    reindex_retirements(deps.storage)?;
+    index_project_records(deps.storage)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(action_response("migrate")
+        .add_attribute("from_version", from_version.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+/// Instantiates the retirement certificate collection, minted by this contract
+/// and administered by its admin.
+fn instantiate_certificates(
+    deps: Deps,
+    env: &Env,
+    code_id: u64,
+    project_name: &str,
+    symbol: &str,
+) -> StdResult<SubMsg> {
+    let admin = deps
+        .querier
+        .query_wasm_contract_info(&env.contract.address)?
+        .admin;
+    let msg = WasmMsg::Instantiate {
+        admin,
+        code_id,
+        msg: to_json_binary(&CertificateInstantiateMsg {
+            name: format!("{project_name} Retirement Certificates"),
+            symbol: format!("{symbol}-RC"),
+            minter: env.contract.address.to_string(),
+        })?,
+        funds: vec![],
+        label: format!("{symbol} retirement certificates"),
+    };
+    Ok(SubMsg::reply_on_success(msg, CERTIFICATE_COLLECTION_REPLY))
+}
+
+fn initial_role_holders(carbon_credit_info: &CarbonCreditInfo) -> [(&str, &Addr); 2] {
+    [
+        (VERIFICATION_BODY, &carbon_credit_info.verification_body),
+        (PROJECT_DEVELOPER, &carbon_credit_info.project_developer),
+    ]
+}
+
+// Carbon credit specific functions
+
+// The details of a verification being recorded
+struct NewVerification {
+    verification_id: Option<String>,
+    credits_to_verify: Uint128,
+    verification_report_url: String,
+    recipients: Option<Vec<IssuanceRecipient>>,
+    vintage_year: Option<u32>,
+    methodology_version: Option<String>,
+    registry_serials: Option<RegistrySerials>,
+    forward_series: Option<u64>,
+}
+
+fn verify_credits(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    verification: NewVerification,
+    status: VerificationStatus,
+) -> Result<Response, ContractError> {
+    // Only a verification body can verify or submit credits
+    ensure_role(deps.storage, &info.sender, &[VERIFICATION_BODY], || {
+        ContractError::NotVerificationBody {
+            action: "verify credits".to_string(),
         }
-        QueryMsg::Minter {} => {
-            cw20_query(deps, env, Cw20QueryMsg::Minter {})
+    })?;
+    let carbon_credit_info = CARBON_CREDIT_INFO.load(deps.storage)?;
+
+    // Reject a reused id, or generate the next one
+    let verification_id = assign_record_id(
+        deps.storage,
+        &VERIFICATION_RECORDS,
+        verification.verification_id,
+    )?;
+
+    // Claim the registry serials, so the same block cannot be tokenized twice
+    let credits_to_verify = verification.credits_to_verify;
+    if let Some(serials) = &verification.registry_serials {
+        register_block(deps.storage, serials, credits_to_verify, &verification_id)?;
+    }
+
+    // Credits delivered against a forward series go to its holders first
+    let issued_to = match verification.forward_series {
+        Some(series_id) => {
+            let (mut issued_to, excess) =
+                deliver_forward(deps.storage, series_id, &verification_id, credits_to_verify)?;
+            if !excess.is_zero() || verification.recipients.is_some() {
+                issued_to.extend(allocate_credits(
+                    deps.as_ref(),
+                    &carbon_credit_info.project_developer,
+                    excess,
+                    verification.recipients,
+                )?);
+            }
+            issued_to
         }
-        QueryMsg::Allowance { owner, spender } => {
-            cw20_query(deps, env, Cw20QueryMsg::Allowance { owner, spender })
+        None => allocate_credits(
+            deps.as_ref(),
+            &carbon_credit_info.project_developer,
+            credits_to_verify,
+            verification.recipients,
+        )?,
+    };
+
+    // Create verification record
+    let mut verification_record = VerificationRecord {
+        verification_id: verification_id.clone(),
+        verification_date: env.block.time,
+        credits_verified: credits_to_verify,
+        verification_body: info.sender.clone(),
+        verification_report_url: verification.verification_report_url,
+        status: status.clone(),
+        issued_to,
+        history: vec![StatusChange {
+            status: status.clone(),
+            changed_at: env.block.time,
+            changed_by: info.sender,
+            reason: None,
+        }],
+        vintage_year: Some(
+            verification
+                .vintage_year
+                .unwrap_or(carbon_credit_info.vintage_year),
+        ),
+        methodology_version: verification.methodology_version,
+        batch_id: None,
+        registry_serials: verification.registry_serials,
+        buffer_credits: Uint128::zero(),
+        project_id: None,
+        forward_series: verification.forward_series,
+        vintage_expired_at: None,
+    };
+
+    // A pending record mints nothing until it is verified
+    let action = match status {
+        VerificationStatus::Verified => {
+            issue_credits(deps.branch(), &mut verification_record)?;
+            "verify_credits"
         }
-        QueryMsg::AllAllowances { owner, start_after, limit } => {
-            cw20_query(deps, env, Cw20QueryMsg::AllAllowances { owner, start_after, limit })
+        _ => "submit_verification",
+    };
+
+    VERIFICATION_RECORDS.save(deps.storage, &verification_id, &verification_record)?;
+    index_record(deps.storage, &PROJECT_VERIFICATIONS, None, &verification_id)?;
+
+    let mut res = record_response(action, "verification_id", &verification_id)?
+        .add_attribute("credits_verified", credits_to_verify);
+    if let Some(batch_id) = verification_record.batch_id {
+        res = res.add_attribute("batch_id", batch_id.to_string());
+    }
+    Ok(res)
+}
+
+/// Splits `credits` between the named recipients, or gives them all to the
+/// project developer.
+fn allocate_credits(
+    deps: Deps,
+    project_developer: &Addr,
+    credits: Uint128,
+    recipients: Option<Vec<IssuanceRecipient>>,
+) -> Result<Vec<CreditAllocation>, ContractError> {
+    let Some(recipients) = recipients else {
+        return Ok(vec![CreditAllocation {
+            recipient: project_developer.clone(),
+            credits,
+        }]);
+    };
+
+    let issued_to = recipients
+        .into_iter()
+        .map(|recipient| {
+            Ok(CreditAllocation {
+                recipient: deps.api.addr_validate(&recipient.address)?,
+                credits: recipient.credits,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let allocated = issued_to
+        .iter()
+        .try_fold(Uint128::zero(), |total, allocation| {
+            total.checked_add(allocation.credits)
+        })?;
+    if allocated != credits {
+        return Err(ContractError::RecipientsMismatch {
+            allocated,
+            verified: credits,
+        });
+    }
+    Ok(issued_to)
+}
+
+/// Mints a verified record's credits into a new batch held by its recipients,
+/// less the share withheld into the buffer pool, and counts them as issued and
+/// available or buffered.
+fn issue_credits(deps: DepsMut, record: &mut VerificationRecord) -> Result<(), ContractError> {
+    // Sanctioned accounts cannot receive credits
+    let recipient_addresses: Vec<&str> = record
+        .issued_to
+        .iter()
+        .map(|allocation| allocation.recipient.as_str())
+        .collect();
+    ensure_not_sanctioned(deps.as_ref(), &recipient_addresses)?;
+    let carbon_credit_info = CARBON_CREDIT_INFO.load(deps.storage)?;
+
+    // Records submitted before vintages were kept fall back to the project's
+    if !record.credits_verified.is_zero() {
+        let vintage_year = record
+            .vintage_year
+            .unwrap_or(carbon_credit_info.vintage_year);
+        let batch = open_batch(
+            deps.storage,
+            vintage_year,
+            record.methodology_version.clone(),
+            Some(record.verification_id.clone()),
+            record.credits_verified,
+        )?;
+        record.batch_id = Some(batch.batch_id);
+
+        // The project type's share of each allocation goes to the buffer pool
+        let rate = buffer_rate(deps.storage, carbon_credit_info.project_type)?;
+        record.buffer_credits = withhold(&mut record.issued_to, rate);
+        deposit(deps.storage, batch.batch_id, record.buffer_credits)?;
+    }
+
+    for allocation in &record.issued_to {
+        mint_tokens(deps.storage, &allocation.recipient, allocation.credits)?;
+        if let Some(batch_id) = record.batch_id {
+            let issued = [BatchAmount {
+                batch_id,
+                credits: allocation.credits,
+            }];
+            credit_batches(deps.storage, &allocation.recipient, &issued)?;
         }
-        QueryMsg::AllAccounts { start_after, limit } => {
-            cw20_query(deps, env, Cw20QueryMsg::AllAccounts { start_after, limit })
+    }
+
+    CARBON_CREDIT_INFO.update(deps.storage, |mut info| -> StdResult<_> {
+        info.total_credits_issued = info
+            .total_credits_issued
+            .checked_add(record.credits_verified)?;
+        info.credits_available = info
+            .credits_available
+            .checked_add(record.credits_verified - record.buffer_credits)?;
+        info.credits_buffered = info.credits_buffered.checked_add(record.buffer_credits)?;
+        Ok(info)
+    })?;
+    Ok(())
+}
+
+/// Takes a verified record's credits back out of circulation: its batch is
+/// burned from whoever holds it now, and what the buffer pool still holds of
+/// it is cancelled. Credits already retired stay retired. Returns the credits
+/// burned and cancelled from the pool.
+fn reverse_issuance(
+    storage: &mut dyn Storage,
+    record: &VerificationRecord,
+) -> Result<(Uint128, Uint128), ContractError> {
+    // Records from before verification minted only raised the counters
+    if record.issued_to.is_empty() {
+        withdraw_credits(storage, record.credits_verified)?;
+        return Ok((Uint128::zero(), Uint128::zero()));
+    }
+
+    // The holders of credits issued before batches were tracked are not
+    // known, so its recipients must still hold them all
+    let Some(batch_id) = record.batch_id else {
+        let burned = reverse_base_issuance(storage, record)?;
+        return Ok((burned, Uint128::zero()));
+    };
+
+    // Reversals may already have drawn on the batch's buffer credits
+    let unbuffered = cancel_batch(storage, batch_id, record.buffer_credits)?;
+    withdraw_buffered(storage, unbuffered)?;
+
+    let mut burned = Uint128::zero();
+    for (holder, credits) in batch_holders(storage, batch_id)? {
+        debit_batches(storage, &holder, Some(batch_id), credits)?;
+        burn_tokens(storage, &holder, credits)?;
+        burned = burned.checked_add(credits)?;
+    }
+
+    withdraw_credits(storage, burned)?;
+    Ok((burned, unbuffered))
+}
+
+/// Burns the credits a record issued into the base batch from its recipients,
+/// failing if they no longer hold them all. Returns the credits burned.
+fn reverse_base_issuance(
+    storage: &mut dyn Storage,
+    record: &VerificationRecord,
+) -> Result<Uint128, ContractError> {
+    let mut shortfall = Uint128::zero();
+    for allocation in &record.issued_to {
+        let balance = batch_balance(storage, &allocation.recipient, BASE_BATCH)?;
+        shortfall += allocation.credits.saturating_sub(balance);
+    }
+    if !shortfall.is_zero() {
+        return Err(ContractError::ReversalShortfall {
+            verification_id: record.verification_id.clone(),
+            shortfall,
+        });
+    }
+
+    let mut burned = Uint128::zero();
+    for allocation in &record.issued_to {
+        debit_batches(
+            storage,
+            &allocation.recipient,
+            Some(BASE_BATCH),
+            allocation.credits,
+        )?;
+        burn_tokens(storage, &allocation.recipient, allocation.credits)?;
+        burned = burned.checked_add(allocation.credits)?;
+    }
+
+    withdraw_credits(storage, burned)?;
+    Ok(burned)
+}
+
+/// Removes reversed credits from the issued and available counters.
+fn withdraw_credits(storage: &mut dyn Storage, credits: Uint128) -> Result<(), ContractError> {
+    CARBON_CREDIT_INFO.update(storage, |mut info| -> StdResult<_> {
+        info.total_credits_issued = info.total_credits_issued.checked_sub(credits)?;
+        info.credits_available = info.credits_available.checked_sub(credits)?;
+        Ok(info)
+    })?;
+    Ok(())
+}
+
+/// Removes cancelled buffer pool credits from the issued and buffered
+/// counters.
+fn withdraw_buffered(storage: &mut dyn Storage, credits: Uint128) -> Result<(), ContractError> {
+    CARBON_CREDIT_INFO.update(storage, |mut info| -> StdResult<_> {
+        info.total_credits_issued = info.total_credits_issued.checked_sub(credits)?;
+        info.credits_buffered = info.credits_buffered.checked_sub(credits)?;
+        Ok(info)
+    })?;
+    Ok(())
+}
+
+/// The account a CW20 message moves tokens out of, the account it moves them
+/// to, and the amount.
+fn cw20_movement(
+    deps: Deps,
+    sender: &Addr,
+    msg: &Cw20ExecuteMsg,
+) -> StdResult<Option<(Addr, Addr, Uint128)>> {
+    let (from, to, amount) = match msg {
+        Cw20ExecuteMsg::Transfer { recipient, amount } => (sender.clone(), recipient, *amount),
+        Cw20ExecuteMsg::Send {
+            contract, amount, ..
+        } => (sender.clone(), contract, *amount),
+        Cw20ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            amount,
+        } => (deps.api.addr_validate(owner)?, recipient, *amount),
+        Cw20ExecuteMsg::SendFrom {
+            owner,
+            contract,
+            amount,
+            ..
+        } => (deps.api.addr_validate(owner)?, contract, *amount),
+        _ => return Ok(None),
+    };
+
+    Ok(Some((from, deps.api.addr_validate(to)?, amount)))
+}
+
+fn transfer_batch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    batch_id: u64,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // Screen both ends, as for a plain transfer
+    ensure_not_sanctioned(deps.as_ref(), &[info.sender.as_str(), recipient.as_str()])?;
+    let to = deps.api.addr_validate(&recipient)?;
+
+    let drawn = debit_for_transfer(
+        deps.storage,
+        &info.sender,
+        Some(batch_id),
+        amount,
+        env.block.time,
+    )?;
+    credit_batches(deps.storage, &to, &drawn)?;
+
+    let res = execute_cw20(
+        deps,
+        env,
+        info,
+        Cw20ExecuteMsg::Transfer { recipient, amount },
+    )?;
+    Ok(res.add_attribute("batch_id", batch_id.to_string()))
+}
+
+// The details of a retirement being recorded
+struct NewRetirement {
+    retirement_id: Option<String>,
+    credits_to_retire: Uint128,
+    retirement_purpose: String,
+    retirement_certificate_url: String,
+    batch_id: Option<u64>,
+    registry_serials: Option<RegistrySerials>,
+    beneficiary: Option<String>,
+    beneficiary_name: Option<String>,
+    retirement_use: CreditUse,
+    // The batches of credits sent to the contract to be retired, which are
+    // retired rather than any others it holds
+    sent_batches: Option<Vec<BatchAmount>>,
+}
+
+/// Retires credits of the sender, or with an `owner` of that owner through the
+/// allowance it gave the sender.
+fn retire_credits(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: Option<String>,
+    retirement: NewRetirement,
+) -> Result<Response, ContractError> {
+    // Sanctioned accounts cannot retire, nor have credits retired for them
+    let mut parties = vec![info.sender.as_str()];
+    parties.extend(owner.as_deref());
+    parties.extend(retirement.beneficiary.as_deref());
+    ensure_not_sanctioned(deps.as_ref(), &parties)?;
+
+    // Retiring for an owner spends its allowance, as a transfer would
+    match owner {
+        Some(owner) => {
+            let owner = deps.api.addr_validate(&owner)?;
+            deduct_allowance(
+                deps.storage,
+                &owner,
+                &info.sender,
+                &env.block,
+                retirement.credits_to_retire,
+            )?;
+            let operator = Some(info.sender);
+            record_retirement(deps, &env, &owner, owner.clone(), operator, retirement)
         }
-        QueryMsg::MarketingInfo {} => {
-            cw20_query(deps, env, Cw20QueryMsg::MarketingInfo {})
+        None => record_retirement(
+            deps,
+            &env,
+            &info.sender,
+            info.sender.clone(),
+            None,
+            retirement,
+        ),
+    }
+}
+
+/// Retires credits sent to this contract with a `ReceiveMsg`, for the account
+/// that sent them. Credits of another carbon credit contract are forwarded to
+/// that contract instead.
+fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let ReceiveMsg::Retire(retire) = from_json(&wrapper.msg)?;
+    if info.sender != env.contract.address {
+        // Any CW20 can call the hook; only approved tokens are forwarded to
+        if !FORWARD_TOKENS.has(deps.storage, &info.sender) {
+            return Err(ContractError::ForwardTokenNotApproved {
+                token: info.sender.to_string(),
+            });
         }
-        QueryMsg::DownloadLogo {} => {
-            cw20_query(deps, env, Cw20QueryMsg::DownloadLogo {})
+        return forward_retirement(info.sender, wrapper.sender, wrapper.amount, retire);
+    }
+
+    // The batches the send drew on, recorded on its way in
+    let sent_batches = SENT_BATCHES.load(deps.storage)?;
+    SENT_BATCHES.remove(deps.storage);
+
+    // The credits were screened on their way in; who they are retired for is
+    // screened here
+    let mut parties = vec![wrapper.sender.as_str()];
+    parties.extend(retire.beneficiary.as_deref());
+    ensure_not_sanctioned(deps.as_ref(), &parties)?;
+
+    let entity = deps.api.addr_validate(&wrapper.sender)?;
+    let holder = env.contract.address.clone();
+    let retirement = NewRetirement {
+        retirement_id: retire.retirement_id,
+        credits_to_retire: wrapper.amount,
+        retirement_purpose: retire.retirement_purpose,
+        retirement_certificate_url: retire.retirement_certificate_url,
+        batch_id: None,
+        registry_serials: None,
+        beneficiary: retire.beneficiary,
+        beneficiary_name: retire.beneficiary_name,
+        retirement_use: retire.retirement_use.unwrap_or_default(),
+        sent_batches: Some(sent_batches),
+    };
+    record_retirement(deps, &env, &holder, entity, None, retirement)
+}
+
+/// Sends credits of another carbon credit contract on to that contract's own
+/// receive hook, to be retired for the account that sent them here unless a
+/// beneficiary is named.
+fn forward_retirement(
+    token: Addr,
+    sender: String,
+    amount: Uint128,
+    retire: RetireMsg,
+) -> Result<Response, ContractError> {
+    let retire = RetireMsg {
+        beneficiary: retire.beneficiary.or(Some(sender)),
+        ..retire
+    };
+    let send = Cw20ExecuteMsg::Send {
+        contract: token.to_string(),
+        amount,
+        msg: to_json_binary(&ReceiveMsg::Retire(retire))?,
+    };
+    Ok(action_response("forward_retirement")
+        .add_attribute("token", &token)
+        .add_attribute("amount", amount)
+        .add_message(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&send)?,
+            funds: vec![],
+        }))
+}
+
+/// Burns the credits of a retirement out of `holder` and records them as
+/// retired by `entity`.
+fn record_retirement(
+    deps: DepsMut,
+    env: &Env,
+    holder: &Addr,
+    entity: Addr,
+    operator: Option<Addr>,
+    retirement: NewRetirement,
+) -> Result<Response, ContractError> {
+    let NewRetirement {
+        retirement_id,
+        credits_to_retire,
+        retirement_purpose,
+        retirement_certificate_url,
+        batch_id,
+        registry_serials,
+        beneficiary,
+        beneficiary_name,
+        retirement_use,
+        sent_batches,
+    } = retirement;
+    let beneficiary = beneficiary
+        .map(|beneficiary| deps.api.addr_validate(&beneficiary))
+        .transpose()?;
+
+    // Reject a reused id, or generate the next one
+    let retirement_id = assign_indexed_record_id(
+        deps.storage,
+        &RETIREMENTS,
+        RETIREMENTS_NAMESPACE,
+        retirement_id,
+    )?;
+
+    // Named registry serials fix the batch the credits come from
+    let serial_range = match &registry_serials {
+        Some(serials) => {
+            let (serials_batch, range) = resolve_registry_serials(deps.storage, serials)?;
+            if matches!(batch_id, Some(batch_id) if batch_id != serials_batch) {
+                return Err(ContractError::SerialsNotRetirable {
+                    registry: serials.registry.clone(),
+                    serial_start: serials.serial_start,
+                    serial_end: serials.serial_end,
+                });
+            }
+            if range.last - range.first + Uint128::one() != credits_to_retire {
+                return Err(ContractError::SerialBlockMismatch {
+                    serial_start: serials.serial_start,
+                    serial_end: serials.serial_end,
+                    credits: credits_to_retire,
+                });
+            }
+            Some((serials_batch, range))
         }
-        
-        // Handle carbon credit specific queries
-        QueryMsg::CarbonCreditInfo {} => {
-            to_binary(&CARBON_CREDIT_INFO.load(deps.storage)?)
+        None => None,
+    };
+    let batch_id = serial_range
+        .as_ref()
+        .map(|(batch_id, _)| *batch_id)
+        .or(batch_id);
+
+    // Burn the credits (retirement = permanent removal), from the batches they
+    // were sent from, the named batch or the oldest ones held
+    let batches = match sent_batches {
+        Some(sent_batches) => {
+            for sent in &sent_batches {
+                debit_batches(deps.storage, holder, Some(sent.batch_id), sent.credits)?;
+            }
+            sent_batches
         }
-        QueryMsg::VerificationRecord { verification_id } => {
-            to_binary(&VERIFICATION_RECORDS.load(deps.storage, verification_id)?)
+        None => debit_batches(deps.storage, holder, batch_id, credits_to_retire)?,
+    };
+    ensure_authorized(deps.storage, &batches, retirement_use)?;
+    ensure_vintages_valid(deps.storage, &batches, retirement_use, env.block.time)?;
+    burn_tokens(deps.storage, holder, credits_to_retire)?;
+
+    // Consume the named serials, or the lowest ones left in each batch drawn
+    let serials = match serial_range {
+        Some((_, range)) => {
+            retire_range(deps.storage, &range, &retirement_id)?;
+            vec![range]
         }
-        QueryMsg::AllVerificationRecords { start_after, limit } => {
-            query_all_verification_records(deps, start_after, limit)
+        None => {
+            let mut serials = vec![];
+            for drawn in &batches {
+                serials.extend(retire_lowest(
+                    deps.storage,
+                    drawn.batch_id,
+                    drawn.credits,
+                    &retirement_id,
+                )?);
+            }
+            serials
         }
-        QueryMsg::RetirementRecord { retirement_id } => {
-            to_binary(&RETIREMENT_RECORDS.load(deps.storage, retirement_id)?)
+    };
+
+    // Create retirement record
+    let certificate_collection = CERTIFICATE_COLLECTION.may_load(deps.storage)?;
+    let retirement_record = RetirementRecord {
+        retirement_id: retirement_id.clone(),
+        retirement_date: env.block.time,
+        credits_retired: credits_to_retire,
+        retirement_purpose,
+        retirement_entity: entity,
+        retirement_certificate_url,
+        batches,
+        serials,
+        certificate_collection,
+        beneficiary,
+        beneficiary_name,
+        operator,
+        project_id: None,
+        retirement_use,
+    };
+
+    save_retirement(deps.storage, &retirement_id, &retirement_record)?;
+
+    // Update carbon credit info
+    let mut carbon_credit_info = CARBON_CREDIT_INFO.load(deps.storage)?;
+    if credits_to_retire > carbon_credit_info.credits_available {
+        return Err(ContractError::ExceedsAvailableCredits {
+            requested: credits_to_retire,
+            available: carbon_credit_info.credits_available,
+        });
+    }
+    carbon_credit_info.credits_retired += credits_to_retire;
+    carbon_credit_info.credits_available -= credits_to_retire;
+    CARBON_CREDIT_INFO.save(deps.storage, &carbon_credit_info)?;
+
+    let action = match retirement_record.operator {
+        Some(_) => "retire_credits_from",
+        None => "retire_credits",
+    };
+    let mut res = record_response(action, "retirement_id", &retirement_id)?
+        .add_attribute("credits_retired", credits_to_retire)
+        .add_attribute("retirement_entity", &retirement_record.retirement_entity)
+        .add_attribute("retirement_use", retirement_use.to_string());
+    if let Some(beneficiary) = &retirement_record.beneficiary {
+        res = res.add_attribute("beneficiary", beneficiary);
+    }
+    if let Some(operator) = &retirement_record.operator {
+        res = res.add_attribute("operator", operator);
+    }
+
+    // Mint the retirement certificate, owned by the beneficiary or else the
+    // retiring account
+    if let Some(collection) = &retirement_record.certificate_collection {
+        res = res.add_message(mint_certificate(
+            deps.storage,
+            collection,
+            &carbon_credit_info,
+            &retirement_record,
+        )?);
+    }
+    Ok(res)
+}
+
+/// The message minting the certificate of a retirement, under its id.
+fn mint_certificate(
+    storage: &dyn Storage,
+    collection: &Addr,
+    carbon_credit_info: &CarbonCreditInfo,
+    record: &RetirementRecord,
+) -> Result<WasmMsg, ContractError> {
+    let mut vintage_years = vec![];
+    for drawn in &record.batches {
+        let vintage_year = match BATCHES.may_load(storage, drawn.batch_id)? {
+            Some(batch) => batch.vintage_year,
+            None => carbon_credit_info.vintage_year,
+        };
+        if !vintage_years.contains(&vintage_year) {
+            vintage_years.push(vintage_year);
         }
-        QueryMsg::AllRetirementRecords { start_after, limit } => {
-            query_all_retirement_records(deps, start_after, limit)
+    }
+    // Registered projects keep no batches
+    if vintage_years.is_empty() {
+        vintage_years.push(carbon_credit_info.vintage_year);
+    }
+    vintage_years.sort_unstable();
+
+    let owner = record
+        .beneficiary
+        .as_ref()
+        .unwrap_or(&record.retirement_entity);
+    let beneficiary = match &record.beneficiary_name {
+        Some(name) => name.clone(),
+        None => owner.to_string(),
+    };
+    let tco2e = carbon_credit_info
+        .co2_equivalent_per_credit
+        .checked_mul(Decimal::from_ratio(record.credits_retired, 1u128))?;
+    let mint = CertificateExecuteMsg::Mint {
+        token_id: record.retirement_id.clone(),
+        owner: owner.to_string(),
+        token_uri: Some(record.retirement_certificate_url.clone()).filter(|url| !url.is_empty()),
+        extension: CertificateMetadata {
+            retirement_id: record.retirement_id.clone(),
+            project_id: carbon_credit_info.project_id.clone(),
+            vintage_years,
+            credits: record.credits_retired,
+            tco2e,
+            beneficiary,
+            retirement_purpose: record.retirement_purpose.clone(),
+        },
+    };
+    Ok(WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_json_binary(&mint)?,
+        funds: vec![],
+    })
+}
+
+fn update_verification_status(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    verification_id: String,
+    status: VerificationStatus,
+    reason: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut verification_record = VERIFICATION_RECORDS
+        .may_load(deps.storage, &verification_id)?
+        .ok_or_else(|| ContractError::VerificationRecordNotFound {
+            verification_id: verification_id.clone(),
+        })?;
+    // Verifications of registered projects are final, whoever sends this
+    if let Some(project_id) = &verification_record.project_id {
+        return Err(ContractError::ProjectRecord {
+            record_id: verification_id,
+            project_id: project_id.clone(),
+        });
+    }
+
+    // Only a verification body can update status
+    ensure_role(deps.storage, &info.sender, &[VERIFICATION_BODY], || {
+        ContractError::NotVerificationBody {
+            action: "update verification status".to_string(),
         }
-        QueryMsg::AvailableCredits {} => {
-            query_available_credits(deps)
+    })?;
+    if !verification_record.status.can_become(&status) {
+        return Err(ContractError::InvalidStatusTransition {
+            from: verification_record.status,
+            to: status,
+        });
+    }
+
+    let mut res = action_response("update_verification_status")
+        .add_attribute("verification_id", &verification_id)
+        .add_attribute("status", format!("{:?}", status));
+
+    // Verifying a pending record issues its credits, expiring a verified one
+    // takes them back
+    match status {
+        VerificationStatus::Verified => issue_credits(deps.branch(), &mut verification_record)?,
+        VerificationStatus::Expired => {
+            let (burned, unbuffered) = reverse_issuance(deps.storage, &verification_record)?;
+            res = res
+                .add_attribute("credits_burned", burned)
+                .add_attribute("buffer_credits_cancelled", unbuffered);
         }
-        QueryMsg::RetiredCredits {} => {
-            query_retired_credits(deps)
+        // A rejected record's registry serials are free to be tokenized again
+        VerificationStatus::Rejected => {
+            if let Some(serials) = &verification_record.registry_serials {
+                release_block(deps.storage, serials);
+            }
         }
+        VerificationStatus::Pending => {}
     }
+
+    verification_record.status = status.clone();
+    verification_record.history.push(StatusChange {
+        status,
+        changed_at: env.block.time,
+        changed_by: info.sender,
+        reason,
+    });
+    VERIFICATION_RECORDS.save(deps.storage, &verification_id, &verification_record)?;
+
+    Ok(res)
 }
 
-// Carbon credit specific functions
-fn verify_credits(
+fn verify_project_credits(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    verification_id: String,
-    credits_to_verify: Uint128,
-    verification_report_url: String,
-) -> StdResult<Response> {
-    // Only the verification body can verify credits
-    let carbon_credit_info = CARBON_CREDIT_INFO.load(deps.storage)?;
-    if info.sender != carbon_credit_info.verification_body {
-        return Err(cosmwasm_std::StdError::Unauthorized { msg: "Only verification body can verify credits".to_string() });
+    project_id: String,
+    verification: NewVerification,
+) -> Result<Response, ContractError> {
+    // Only the project's own verification body can verify its credits
+    let mut project = load_project(deps.storage, &project_id)?;
+    if info.sender != project.verification_body {
+        return Err(ContractError::NotVerificationBody {
+            action: format!("verify credits of project {project_id}"),
+        });
     }
-    
-    // Create verification record
+
+    // Reject a reused id, or generate the next one
+    let verification_id = assign_record_id(
+        deps.storage,
+        &VERIFICATION_RECORDS,
+        verification.verification_id,
+    )?;
+
+    // Issue the credits to the recipients' project balances, less the project
+    // type's share, which is counted as buffered
+    let credits_to_verify = verification.credits_to_verify;
+    let mut issued_to = allocate_credits(
+        deps.as_ref(),
+        &project.project_developer,
+        credits_to_verify,
+        verification.recipients,
+    )?;
+    let recipient_addresses: Vec<&str> = issued_to
+        .iter()
+        .map(|allocation| allocation.recipient.as_str())
+        .collect();
+    ensure_not_sanctioned(deps.as_ref(), &recipient_addresses)?;
+    let rate = buffer_rate(deps.storage, project.project_type)?;
+    let buffer_credits = withhold(&mut issued_to, rate);
+    for allocation in &issued_to {
+        credit_project(
+            deps.storage,
+            &allocation.recipient,
+            &project_id,
+            allocation.credits,
+        )?;
+    }
+
+    project.total_credits_issued = project
+        .total_credits_issued
+        .checked_add(credits_to_verify)?;
+    project.credits_available = project
+        .credits_available
+        .checked_add(credits_to_verify - buffer_credits)?;
+    project.credits_buffered = project.credits_buffered.checked_add(buffer_credits)?;
+    PROJECTS.save(deps.storage, &project_id, &project)?;
+
     let verification_record = VerificationRecord {
         verification_id: verification_id.clone(),
         verification_date: env.block.time,
         credits_verified: credits_to_verify,
         verification_body: info.sender.clone(),
-        verification_report_url,
+        verification_report_url: verification.verification_report_url,
         status: VerificationStatus::Verified,
+        issued_to,
+        history: vec![StatusChange {
+            status: VerificationStatus::Verified,
+            changed_at: env.block.time,
+            changed_by: info.sender,
+            reason: None,
+        }],
+        vintage_year: Some(project.vintage_year),
+        methodology_version: None,
+        batch_id: None,
+        registry_serials: None,
+        buffer_credits,
+        project_id: Some(project_id.clone()),
+        forward_series: None,
+        vintage_expired_at: None,
     };
-    
     VERIFICATION_RECORDS.save(deps.storage, &verification_id, &verification_record)?;
-    
-    // Update carbon credit info
-    let mut updated_info = carbon_credit_info;
-    updated_info.total_credits_issued += credits_to_verify;
-    updated_info.credits_available += credits_to_verify;
-    CARBON_CREDIT_INFO.save(deps.storage, &updated_info)?;
-    
-    Ok(Response::new()
-        .add_attribute("action", "verify_credits")
-        .add_attribute("verification_id", verification_id)
-        .add_attribute("credits_verified", credits_to_verify))
+    index_record(
+        deps.storage,
+        &PROJECT_VERIFICATIONS,
+        Some(&project_id),
+        &verification_id,
+    )?;
+
+    Ok(record_response(
+        "verify_project_credits",
+        "verification_id",
+        &verification_id,
+    )?
+    .add_attribute("project_id", project_id)
+    .add_attribute("credits_verified", credits_to_verify)
+    .add_attribute("buffer_credits", buffer_credits))
 }
 
-fn retire_credits(
+fn issue_forward_units(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    retirement_id: String,
-    credits_to_retire: Uint128,
-    retirement_purpose: String,
-    retirement_certificate_url: String,
-) -> StdResult<Response> {
-    // Check if user has enough credits
-    let balance = cw20_base::state::BALANCES.load(deps.storage, &info.sender)?;
-    if balance < credits_to_retire {
-        return Err(cosmwasm_std::StdError::InsufficientFunds { 
-            needed: credits_to_retire.into(), 
-            available: balance.into() 
-        });
-    }
-    
-    // Burn the credits (retirement = permanent removal)
-    cw20_base::state::BALANCES.update(deps.storage, &info.sender, |balance| -> StdResult<_> {
-        Ok(balance.unwrap_or_default().checked_sub(credits_to_retire)?)
+    schedule: Vec<MonitoringPeriod>,
+    recipients: Option<Vec<IssuanceRecipient>>,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.storage, &info.sender, &[PROJECT_DEVELOPER], || {
+        ContractError::NotProjectDeveloper {
+            action: "issue forward units".to_string(),
+        }
     })?;
-    
-    // Update total supply
-    cw20_base::state::TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
-        info.total_supply = info.total_supply.checked_sub(credits_to_retire)?;
-        Ok(info)
+    let units = schedule.iter().try_fold(Uint128::zero(), |total, period| {
+        total.checked_add(period.expected_credits)
     })?;
-    
-    // Create retirement record
+    let issued_to = allocate_credits(deps.as_ref(), &info.sender, units, recipients)?;
+    let recipient_addresses: Vec<&str> = issued_to
+        .iter()
+        .map(|allocation| allocation.recipient.as_str())
+        .collect();
+    ensure_not_sanctioned(deps.as_ref(), &recipient_addresses)?;
+
+    let series = issue_forward(
+        deps.storage,
+        env.block.time,
+        info.sender,
+        schedule,
+        &issued_to,
+    )?;
+    Ok(
+        record_response("issue_forward", "series_id", &series.series_id.to_string())?
+            .add_attribute("units_issued", series.units_issued),
+    )
+}
+
+fn tag_project(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    project_id: Option<String>,
+    tags: ProjectTags,
+) -> Result<Response, ContractError> {
+    // A registered project's developer is the one named at registration, the
+    // contract's own is whoever holds the role
+    let project = match project_id {
+        Some(project_id) => {
+            let project = load_project(deps.storage, &project_id)?;
+            if info.sender != project.project_developer {
+                return Err(ContractError::NotProjectDeveloper {
+                    action: format!("tag project {project_id}"),
+                });
+            }
+            project
+        }
+        None => {
+            ensure_role(deps.storage, &info.sender, &[PROJECT_DEVELOPER], || {
+                ContractError::NotProjectDeveloper {
+                    action: "tag the project".to_string(),
+                }
+            })?;
+            CARBON_CREDIT_INFO.load(deps.storage)?
+        }
+    };
+    let project_id = project.project_id;
+
+    let record = set_project_tags(
+        deps.storage,
+        &project_id,
+        project.project_type,
+        tags,
+        info.sender,
+        env.block.time,
+    )?;
+    Ok(action_response("set_project_tags")
+        .add_attribute("project_id", project_id)
+        .add_attribute("revision", record.revision.to_string()))
+}
+
+fn attest_tags(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    project_id: Option<String>,
+    revision: u32,
+    report_url: String,
+) -> Result<Response, ContractError> {
+    let project_id = match project_id {
+        Some(project_id) => {
+            if info.sender != load_project(deps.storage, &project_id)?.verification_body {
+                return Err(ContractError::NotVerificationBody {
+                    action: format!("attest the tags of project {project_id}"),
+                });
+            }
+            project_id
+        }
+        None => {
+            ensure_role(deps.storage, &info.sender, &[VERIFICATION_BODY], || {
+                ContractError::NotVerificationBody {
+                    action: "attest the project tags".to_string(),
+                }
+            })?;
+            CARBON_CREDIT_INFO.load(deps.storage)?.project_id
+        }
+    };
+
+    let record = attest_project_tags(
+        deps.storage,
+        &project_id,
+        revision,
+        TagAttestation {
+            attested_by: info.sender,
+            attested_at: env.block.time,
+            report_url,
+        },
+    )?;
+    Ok(action_response("attest_project_tags")
+        .add_attribute("project_id", project_id)
+        .add_attribute("attested_revision", revision.to_string())
+        .add_attribute("revision", record.revision.to_string()))
+}
+
+/// The id of a registered project, or of the contract's own without one.
+fn tagged_project_id(storage: &dyn Storage, project_id: Option<String>) -> StdResult<String> {
+    match project_id {
+        Some(project_id) => Ok(project_id),
+        None => Ok(CARBON_CREDIT_INFO.load(storage)?.project_id),
+    }
+}
+
+fn transfer_project_credits(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from: Option<String>,
+    recipient: String,
+    amounts: Vec<ProjectAmount>,
+) -> Result<Response, ContractError> {
+    // Another owner's credits move only for an operator it approved
+    let from = match from {
+        Some(from) => deps.api.addr_validate(&from)?,
+        None => info.sender.clone(),
+    };
+    if !is_operator(deps.storage, &from, &info.sender) {
+        return Err(ContractError::NotProjectOperator {
+            owner: from.to_string(),
+            operator: info.sender.to_string(),
+        });
+    }
+    ensure_not_sanctioned(
+        deps.as_ref(),
+        &[info.sender.as_str(), from.as_str(), recipient.as_str()],
+    )?;
+    let to = deps.api.addr_validate(&recipient)?;
+
+    let mut res = action_response("transfer_project_credits")
+        .add_attribute("from", &from)
+        .add_attribute("to", &to);
+    for amount in amounts {
+        let project = load_project(deps.storage, &amount.project_id)?;
+        ensure_project_vintage_valid(deps.storage, &project, None, env.block.time)?;
+        debit_project(deps.storage, &from, &amount.project_id, amount.amount)?;
+        credit_project(deps.storage, &to, &amount.project_id, amount.amount)?;
+        res = res
+            .add_attribute("project_id", amount.project_id)
+            .add_attribute("amount", amount.amount);
+    }
+    Ok(res)
+}
+
+fn retire_project_credits(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    project_id: String,
+    retirement: NewRetirement,
+) -> Result<Response, ContractError> {
+    // Sanctioned accounts cannot retire, nor have credits retired for them
+    let mut parties = vec![info.sender.as_str()];
+    parties.extend(retirement.beneficiary.as_deref());
+    ensure_not_sanctioned(deps.as_ref(), &parties)?;
+
+    let mut project = load_project(deps.storage, &project_id)?;
+    let beneficiary = retirement
+        .beneficiary
+        .map(|beneficiary| deps.api.addr_validate(&beneficiary))
+        .transpose()?;
+
+    // Reject a reused id, or generate the next one
+    let retirement_id = assign_indexed_record_id(
+        deps.storage,
+        &RETIREMENTS,
+        RETIREMENTS_NAMESPACE,
+        retirement.retirement_id,
+    )?;
+
+    // The same use and vintage checks as retiring batched credits
+    let retirement_use = retirement.retirement_use;
+    ensure_project_authorized(deps.storage, &project_id, retirement_use)?;
+    ensure_project_vintage_valid(deps.storage, &project, Some(retirement_use), env.block.time)?;
+
+    let credits_to_retire = retirement.credits_to_retire;
+    debit_project(deps.storage, &info.sender, &project_id, credits_to_retire)?;
+    project.credits_retired = project.credits_retired.checked_add(credits_to_retire)?;
+    project.credits_available = project.credits_available.checked_sub(credits_to_retire)?;
+    PROJECTS.save(deps.storage, &project_id, &project)?;
+
     let retirement_record = RetirementRecord {
         retirement_id: retirement_id.clone(),
         retirement_date: env.block.time,
         credits_retired: credits_to_retire,
-        retirement_purpose,
-        retirement_entity: info.sender.clone(),
-        retirement_certificate_url,
+        retirement_purpose: retirement.retirement_purpose,
+        retirement_entity: info.sender,
+        retirement_certificate_url: retirement.retirement_certificate_url,
+        batches: vec![],
+        serials: vec![],
+        certificate_collection: CERTIFICATE_COLLECTION.may_load(deps.storage)?,
+        beneficiary,
+        beneficiary_name: retirement.beneficiary_name,
+        operator: None,
+        project_id: Some(project_id.clone()),
+        retirement_use,
     };
-    
-    RETIREMENT_RECORDS.save(deps.storage, &retirement_id, &retirement_record)?;
-    
-    // Update carbon credit info
-    let mut carbon_credit_info = CARBON_CREDIT_INFO.load(deps.storage)?;
-    carbon_credit_info.credits_retired += credits_to_retire;
-    carbon_credit_info.credits_available = carbon_credit_info.credits_available.checked_sub(credits_to_retire)?;
-    CARBON_CREDIT_INFO.save(deps.storage, &carbon_credit_info)?;
-    
-    Ok(Response::new()
-        .add_attribute("action", "retire_credits")
-        .add_attribute("retirement_id", retirement_id)
+    save_retirement(deps.storage, &retirement_id, &retirement_record)?;
+
+    let mut res = record_response("retire_project_credits", "retirement_id", &retirement_id)?
+        .add_attribute("project_id", project_id)
         .add_attribute("credits_retired", credits_to_retire)
-        .add_attribute("retirement_entity", info.sender))
+        .add_attribute("retirement_entity", &retirement_record.retirement_entity)
+        .add_attribute("retirement_use", retirement_use.to_string());
+    if let Some(beneficiary) = &retirement_record.beneficiary {
+        res = res.add_attribute("beneficiary", beneficiary);
+    }
+
+    // The certificate carries the registered project's details
+    if let Some(collection) = &retirement_record.certificate_collection {
+        res = res.add_message(mint_certificate(
+            deps.storage,
+            collection,
+            &project,
+            &retirement_record,
+        )?);
+    }
+    Ok(res)
 }
 
-fn update_verification_status(
+fn report_reversal(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    verification_id: String,
-    status: VerificationStatus,
-) -> StdResult<Response> {
-    // Only the verification body can update status
+    reversal_id: Option<String>,
+    credits: Uint128,
+    reason: String,
+) -> Result<Response, ContractError> {
+    // Only a verification body can report a reversal
+    ensure_role(deps.storage, &info.sender, &[VERIFICATION_BODY], || {
+        ContractError::NotVerificationBody {
+            action: "report reversals".to_string(),
+        }
+    })?;
+
+    // Reject a reused id, or generate the next one
+    let reversal_id = assign_record_id(deps.storage, &REVERSAL_RECORDS, reversal_id)?;
+
     let carbon_credit_info = CARBON_CREDIT_INFO.load(deps.storage)?;
-    if info.sender != carbon_credit_info.verification_body {
-        return Err(cosmwasm_std::StdError::Unauthorized { msg: "Only verification body can update status".to_string() });
+    let held = TOKEN_INFO.load(deps.storage)?.total_supply;
+    let coverable = carbon_credit_info.credits_buffered.checked_add(held)?;
+    if credits > coverable {
+        return Err(ContractError::ReversalExceedsCredits {
+            requested: credits,
+            buffered: carbon_credit_info.credits_buffered,
+            held,
+        });
     }
-    
-    let mut verification_record = VERIFICATION_RECORDS.load(deps.storage, &verification_id)?;
-    verification_record.status = status;
-    VERIFICATION_RECORDS.save(deps.storage, &verification_id, &verification_record)?;
-    
-    Ok(Response::new()
-        .add_attribute("action", "update_verification_status")
-        .add_attribute("verification_id", verification_id)
-        .add_attribute("status", format!("{:?}", status)))
+
+    // The buffer pool covers what it can, the holders the rest
+    let buffer_cancelled = cancel_oldest(deps.storage, credits)?;
+    let from_buffer = buffer_cancelled
+        .iter()
+        .try_fold(Uint128::zero(), |total, drawn| {
+            total.checked_add(drawn.credits)
+        })?;
+    withdraw_buffered(deps.storage, from_buffer)?;
+
+    let from_holders = credits - from_buffer;
+    REVERSAL_RECORDS.save(
+        deps.storage,
+        &reversal_id,
+        &ReversalRecord {
+            reversal_id: reversal_id.clone(),
+            reported_at: env.block.time,
+            reported_by: info.sender,
+            credits_reversed: credits,
+            reason,
+            buffer_cancelled,
+            holder_credits: from_holders,
+            in_progress: !from_holders.is_zero(),
+        },
+    )?;
+
+    // Holders are covered a page at a time, starting with the first
+    let mut completed = true;
+    if !from_holders.is_zero() {
+        let pending = PendingReversal {
+            reversal_id: reversal_id.clone(),
+            credits: from_holders,
+            supply: held,
+            balances_counted: Uint128::zero(),
+            cancelled: Uint128::zero(),
+            last_holding: None,
+        };
+        (_, completed) = cancel_next_holders(deps.storage, pending, DEFAULT_LIMIT)?;
+    }
+
+    Ok(
+        record_response("report_reversal", "reversal_id", &reversal_id)?
+            .add_attribute("credits_reversed", credits)
+            .add_attribute("buffer_credits_cancelled", from_buffer)
+            .add_attribute("holder_credits_cancelled", from_holders)
+            .add_attribute("completed", completed.to_string()),
+    )
+}
+
+/// Cancels the pending reversal's share from the next `limit` holders, and
+/// closes it once every holder is covered. Returns the credits cancelled and
+/// whether the reversal is complete.
+fn cancel_next_holders(
+    storage: &mut dyn Storage,
+    mut pending: PendingReversal,
+    limit: u32,
+) -> Result<(Uint128, bool), ContractError> {
+    let (cancelled, completed) = cancel_pro_rata(storage, &mut pending, limit)?;
+    withdraw_credits(storage, cancelled)?;
+    if completed {
+        PENDING_REVERSAL.remove(storage);
+        REVERSAL_RECORDS.update(storage, &pending.reversal_id, |record| -> StdResult<_> {
+            let mut record = record.ok_or_else(|| StdError::not_found("reversal record"))?;
+            record.in_progress = false;
+            Ok(record)
+        })?;
+    } else {
+        PENDING_REVERSAL.save(storage, &pending)?;
+    }
+    Ok((cancelled, completed))
 }
 
-fn query_all_verification_records(
+/// Fails while a reversal is still cancelling holders' credits, which relies
+/// on their balances staying put.
+fn ensure_no_reversal_in_progress(storage: &dyn Storage) -> Result<(), ContractError> {
+    match PENDING_REVERSAL.may_load(storage)? {
+        Some(pending) => Err(ContractError::ReversalInProgress {
+            reversal_id: pending.reversal_id,
+        }),
+        None => Ok(()),
+    }
+}
+
+fn query_forward_tokens(
     deps: Deps,
     start_after: Option<String>,
     limit: Option<u32>,
 ) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(30).min(30) as usize;
+    let start_after = start_after
+        .map(|token| deps.api.addr_validate(&token))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let tokens = FORWARD_TOKENS
+        .keys(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&tokens)
+}
+
+fn query_all_batches(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start = start_after.map(Bound::exclusive);
-    
-    let records: StdResult<Vec<_>> = VERIFICATION_RECORDS
-        .range(deps.storage, start, None, cosmwasm_std::Order::Ascending)
+    let batches = BATCHES
+        .range(deps.storage, start, None, Order::Ascending)
         .take(limit)
-        .collect();
-    
-    to_binary(&records?)
+        .map(|item| item.map(|(_, batch)| batch))
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&batches)
 }
 
 fn query_all_retirement_records(
@@ -393,25 +2141,55 @@ fn query_all_retirement_records(
     start_after: Option<String>,
     limit: Option<u32>,
 ) -> StdResult<Binary> {
-    let limit = limit.unwrap_or(30).min(30) as usize;
-    let start = start_after.map(Bound::exclusive);
-    
-    let records: StdResult<Vec<_>> = RETIREMENT_RECORDS
-        .range(deps.storage, start, None, cosmwasm_std::Order::Ascending)
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let records = RETIREMENTS
+        .range(deps.storage, start, None, Order::Ascending)
         .take(limit)
-        .collect();
-    
-    to_binary(&records?)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&records)
+}
+
+fn query_certificate_retirement(deps: Deps, token_id: String) -> StdResult<Binary> {
+    // Certificates are minted under the id of the retirement they attest
+    let record = RETIREMENTS
+        .may_load(deps.storage, &token_id)?
+        .filter(|record| record.certificate_collection.is_some())
+        .ok_or_else(|| StdError::not_found(format!("retirement certificate {token_id}")))?;
+    to_json_binary(&record)
 }
 
 fn query_available_credits(deps: Deps) -> StdResult<Binary> {
     let carbon_credit_info = CARBON_CREDIT_INFO.load(deps.storage)?;
-    to_binary(&carbon_credit_info.credits_available)
+    to_json_binary(&carbon_credit_info.credits_available)
 }
 
 fn query_retired_credits(deps: Deps) -> StdResult<Binary> {
     let carbon_credit_info = CARBON_CREDIT_INFO.load(deps.storage)?;
-    to_binary(&carbon_credit_info.credits_retired)
+    to_json_binary(&carbon_credit_info.credits_retired)
 }
 
-use cosmwasm_std::Bound;
+fn query_reconcile_supply(deps: Deps) -> StdResult<Binary> {
+    let carbon_credit_info = CARBON_CREDIT_INFO.load(deps.storage)?;
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+
+    // Every available credit should be backed by exactly one token, and every
+    // credit ever issued is either still available, retired or buffered
+    let supply = Drift::between(
+        carbon_credit_info.credits_available,
+        token_info.total_supply,
+    );
+    let issuance = Drift::between(
+        carbon_credit_info.total_credits_issued,
+        carbon_credit_info
+            .credits_available
+            .checked_add(carbon_credit_info.credits_retired)?
+            .checked_add(carbon_credit_info.credits_buffered)?,
+    );
+
+    to_json_binary(&ReconcileSupplyResponse {
+        reconciled: supply.is_zero() && issuance.is_zero(),
+        supply,
+        issuance,
+    })
+}
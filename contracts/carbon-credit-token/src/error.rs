@@ -0,0 +1,205 @@
+//! Every variant renders as `<Code>: <description>`, where `<Code>` is the
+//! variant name. Codes are part of the contract's interface and never change
+//! meaning; clients should match on the part before the first colon.
+
+use cosmwasm_std::{Decimal, OverflowError, StdError, Uint128};
+use rwa_common::CommonError;
+use thiserror::Error;
+
+use crate::state::{CreditUse, VerificationStatus};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("Std: {0}")]
+    Std(#[from] StdError),
+
+    #[error("Overflow: {0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Cw20Base: {0}")]
+    Cw20Base(#[from] cw20_base::ContractError),
+
+    #[error(transparent)]
+    Common(#[from] CommonError),
+
+    #[error("NotVerificationBody: only the verification body can {action}")]
+    NotVerificationBody { action: String },
+
+    #[error("VerificationRecordNotFound: no verification record with id {verification_id}")]
+    VerificationRecordNotFound { verification_id: String },
+
+    #[error("ExceedsAvailableCredits: requested {requested}, available {available}")]
+    ExceedsAvailableCredits {
+        requested: Uint128,
+        available: Uint128,
+    },
+
+    #[error("RecipientsMismatch: recipients are allocated {allocated} credits, {verified} were verified")]
+    RecipientsMismatch {
+        allocated: Uint128,
+        verified: Uint128,
+    },
+
+    #[error("InvalidStatusTransition: a {from:?} verification cannot become {to:?}")]
+    InvalidStatusTransition {
+        from: VerificationStatus,
+        to: VerificationStatus,
+    },
+
+    #[error("ReversalShortfall: verification {verification_id} cannot be reversed, its recipients no longer hold {shortfall} of its credits")]
+    ReversalShortfall {
+        verification_id: String,
+        shortfall: Uint128,
+    },
+
+    #[error("BatchNotFound: no batch with id {batch_id}")]
+    BatchNotFound { batch_id: u64 },
+
+    #[error("InsufficientBatchBalance: requested {requested} from batch {batch_id}, available {available}")]
+    InsufficientBatchBalance {
+        batch_id: u64,
+        requested: Uint128,
+        available: Uint128,
+    },
+
+    #[error("MintDisabled: credits are only issued through VerifyCredits")]
+    MintDisabled {},
+
+    #[error("BurnDisabled: credits only leave supply through a retirement")]
+    BurnDisabled {},
+
+    #[error("SerialBlockMismatch: serials {serial_start} to {serial_end} do not number {credits} credits")]
+    SerialBlockMismatch {
+        serial_start: Uint128,
+        serial_end: Uint128,
+        credits: Uint128,
+    },
+
+    #[error("SerialBlockTokenized: {registry} serials {serial_start} to {serial_end} overlap those tokenized by verification {verification_id}")]
+    SerialBlockTokenized {
+        registry: String,
+        serial_start: Uint128,
+        serial_end: Uint128,
+        verification_id: String,
+    },
+
+    #[error("SerialsNotRetirable: {registry} serials {serial_start} to {serial_end} are not within one verified block")]
+    SerialsNotRetirable {
+        registry: String,
+        serial_start: Uint128,
+        serial_end: Uint128,
+    },
+
+    #[error("SerialsAlreadyRetired: serial {serial} was retired by {retirement_id}")]
+    SerialsAlreadyRetired {
+        serial: Uint128,
+        retirement_id: String,
+    },
+
+    #[error("InvalidBufferRate: a buffer rate of {rate} is above 1")]
+    InvalidBufferRate { rate: Decimal },
+
+    #[error("ReversalExceedsCredits: reversing {requested} credits, {buffered} are buffered and {held} held")]
+    ReversalExceedsCredits {
+        requested: Uint128,
+        buffered: Uint128,
+        held: Uint128,
+    },
+
+    #[error("ReversalInProgress: credits cannot move until reversal {reversal_id} has covered every holder")]
+    ReversalInProgress { reversal_id: String },
+
+    #[error("NoReversalInProgress: no reversal is cancelling holders' credits")]
+    NoReversalInProgress {},
+
+    #[error("ProjectExists: project {project_id} is already registered")]
+    ProjectExists { project_id: String },
+
+    #[error("ProjectNotFound: no registered project with id {project_id}")]
+    ProjectNotFound { project_id: String },
+
+    #[error("InsufficientProjectBalance: requested {requested} of project {project_id}, available {available}")]
+    InsufficientProjectBalance {
+        project_id: String,
+        requested: Uint128,
+        available: Uint128,
+    },
+
+    #[error("NotProjectOperator: {operator} may not move the project credits of {owner}")]
+    NotProjectOperator { owner: String, operator: String },
+
+    #[error("ProjectRecord: record {record_id} belongs to registered project {project_id}")]
+    ProjectRecord {
+        record_id: String,
+        project_id: String,
+    },
+
+    #[error("NotProjectDeveloper: only the project developer can {action}")]
+    NotProjectDeveloper { action: String },
+
+    #[error("InvalidMonitoringSchedule: {reason}")]
+    InvalidMonitoringSchedule { reason: String },
+
+    #[error("ForwardSeriesNotFound: no forward series with id {series_id}")]
+    ForwardSeriesNotFound { series_id: u64 },
+
+    #[error("ForwardSeriesSettled: every period of forward series {series_id} has been delivered")]
+    ForwardSeriesSettled { series_id: u64 },
+
+    #[error("InsufficientForwardUnits: requested {requested} of forward series {series_id}, available {available}")]
+    InsufficientForwardUnits {
+        series_id: u64,
+        requested: Uint128,
+        available: Uint128,
+    },
+
+    #[error("NotHostCountryAuthority: only the host country authority can {action}")]
+    NotHostCountryAuthority { action: String },
+
+    #[error("UseNotAuthorized: batch {batch_id} is not authorized for {retirement_use} use")]
+    UseNotAuthorized {
+        batch_id: u64,
+        retirement_use: CreditUse,
+    },
+
+    #[error(
+        "ProjectUseNotAuthorized: project {project_id} is not authorized for {retirement_use} use"
+    )]
+    ProjectUseNotAuthorized {
+        project_id: String,
+        retirement_use: CreditUse,
+    },
+
+    #[error("InvalidProjectTags: {reason}")]
+    InvalidProjectTags { reason: String },
+
+    #[error("ProjectNotTagged: project {project_id} has no tags")]
+    ProjectNotTagged { project_id: String },
+
+    #[error("StaleTagRevision: the tags of project {project_id} are at revision {current}, not {revision}")]
+    StaleTagRevision {
+        project_id: String,
+        revision: u32,
+        current: u32,
+    },
+
+    #[error("VintageExpired: the {vintage_year} vintage of batch {batch_id} has expired")]
+    VintageExpired { batch_id: u64, vintage_year: u32 },
+
+    #[error(
+        "ProjectVintageExpired: the {vintage_year} vintage of project {project_id} has expired"
+    )]
+    ProjectVintageExpired {
+        project_id: String,
+        vintage_year: u32,
+    },
+
+    #[error("ExpiredCreditsNotTransferable: requested {requested}, but only {transferable} of the credits held are of unexpired vintages")]
+    ExpiredCreditsNotTransferable {
+        requested: Uint128,
+        transferable: Uint128,
+    },
+
+    #[error("ForwardTokenNotApproved: credits of {token} are not forwarded to be retired")]
+    ForwardTokenNotApproved { token: String },
+}
@@ -0,0 +1,190 @@
+//! Vintage expiry.
+//!
+//! Some standards and compliance schemes only accept credits of recent
+//! vintages. Under the contract's vintage policy a batch's credits stay valid
+//! through the end of its vintage year plus the validity window, and expire
+//! when the next year starts. Expired credits are still held, and can still be
+//! retired for voluntary use, but not for NDC or CORSIA use; the policy can
+//! also keep them from being transferred, in which case transfers pass over
+//! expired batches. Registered projects keep no batches, and their credits
+//! expire with the project's vintage. Anyone can sweep the verification records
+//! of expired vintages to record when they expired.
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::Bound;
+use rwa_common::MAX_LIMIT;
+
+use crate::batches::{debit_batches, debit_usable_batches, load_batch};
+use crate::error::ContractError;
+use crate::state::{
+    BatchAmount, CarbonCreditInfo, CreditUse, VerificationStatus, BATCHES, VERIFICATION_RECORDS,
+    VINTAGE_POLICY,
+};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// When credits of `vintage_year` expire under a window of `validity_years`.
+pub fn vintage_expires_at(vintage_year: u32, validity_years: u32) -> Timestamp {
+    year_start(
+        vintage_year
+            .saturating_add(validity_years)
+            .saturating_add(1),
+    )
+}
+
+/// Whether the credits of `batch_id` have expired at `now`; they never do
+/// without a vintage policy.
+pub fn is_expired(storage: &dyn Storage, batch_id: u64, now: Timestamp) -> StdResult<bool> {
+    let Some(policy) = VINTAGE_POLICY.may_load(storage)? else {
+        return Ok(false);
+    };
+    let batch = BATCHES.load(storage, batch_id)?;
+    Ok(now >= vintage_expires_at(batch.vintage_year, policy.validity_years))
+}
+
+/// Fails if credits retired for `retirement_use` were drawn from an expired
+/// batch. Voluntary retirements take any vintage.
+pub fn ensure_vintages_valid(
+    storage: &dyn Storage,
+    drawn: &[BatchAmount],
+    retirement_use: CreditUse,
+    now: Timestamp,
+) -> Result<(), ContractError> {
+    if retirement_use == CreditUse::Voluntary {
+        return Ok(());
+    }
+    for batch in drawn {
+        ensure_not_expired(storage, batch.batch_id, now)?;
+    }
+    Ok(())
+}
+
+/// Takes `amount` out of `holder`'s batches for a transfer to another
+/// account, from `batch_id` or the oldest batches held. When the policy blocks
+/// transfers of expired vintages, a named batch must not have expired and the
+/// oldest unexpired batches are drawn instead.
+pub fn debit_for_transfer(
+    storage: &mut dyn Storage,
+    holder: &Addr,
+    batch_id: Option<u64>,
+    amount: Uint128,
+    now: Timestamp,
+) -> Result<Vec<BatchAmount>, ContractError> {
+    let blocked = VINTAGE_POLICY
+        .may_load(storage)?
+        .is_some_and(|policy| policy.block_transfers);
+    if !blocked {
+        return debit_batches(storage, holder, batch_id, amount);
+    }
+    if let Some(batch_id) = batch_id {
+        load_batch(storage, batch_id)?;
+        ensure_not_expired(storage, batch_id, now)?;
+        return debit_batches(storage, holder, Some(batch_id), amount);
+    }
+
+    let (drawn, expired) = debit_usable_batches(storage, holder, amount, |storage, batch_id| {
+        Ok(!is_expired(storage, batch_id, now)?)
+    })?;
+    let transferable = drawn.iter().try_fold(Uint128::zero(), |total, batch| {
+        total.checked_add(batch.credits)
+    })?;
+    // A balance too low either way is left for the CW20 balance check
+    if transferable < amount && transferable.checked_add(expired)? >= amount {
+        return Err(ContractError::ExpiredCreditsNotTransferable {
+            requested: amount,
+            transferable,
+        });
+    }
+    Ok(drawn)
+}
+
+/// Records when their vintage expired on the verified records of up to
+/// `limit` batches after `start_after` whose vintage has expired at `now`.
+/// Returns the ids of the records marked, and the last batch looked at.
+pub fn sweep_expired(
+    storage: &mut dyn Storage,
+    now: Timestamp,
+    start_after: Option<u64>,
+    limit: u32,
+) -> StdResult<(Vec<String>, Option<u64>)> {
+    let Some(policy) = VINTAGE_POLICY.may_load(storage)? else {
+        return Ok((vec![], None));
+    };
+    let limit = limit.min(MAX_LIMIT) as usize;
+    let batches = BATCHES
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let last_batch_id = batches.last().map(|(batch_id, _)| *batch_id);
+
+    let mut swept = vec![];
+    for (_, batch) in batches {
+        let expires_at = vintage_expires_at(batch.vintage_year, policy.validity_years);
+        let Some(verification_id) = batch.verification_id else {
+            continue;
+        };
+        if now < expires_at {
+            continue;
+        }
+        let mut record = VERIFICATION_RECORDS.load(storage, &verification_id)?;
+        if record.status != VerificationStatus::Verified || record.vintage_expired_at.is_some() {
+            continue;
+        }
+        record.vintage_expired_at = Some(expires_at);
+        VERIFICATION_RECORDS.save(storage, &verification_id, &record)?;
+        swept.push(verification_id);
+    }
+    Ok((swept, last_batch_id))
+}
+
+/// Fails if credits of the registered `project` are retired for
+/// `retirement_use`, or transferred while the policy blocks it, once its
+/// vintage has expired. Voluntary retirements take any vintage.
+pub fn ensure_project_vintage_valid(
+    storage: &dyn Storage,
+    project: &CarbonCreditInfo,
+    retirement_use: Option<CreditUse>,
+    now: Timestamp,
+) -> Result<(), ContractError> {
+    let Some(policy) = VINTAGE_POLICY.may_load(storage)? else {
+        return Ok(());
+    };
+    let checked = match retirement_use {
+        Some(retirement_use) => retirement_use != CreditUse::Voluntary,
+        None => policy.block_transfers,
+    };
+    if checked && now >= vintage_expires_at(project.vintage_year, policy.validity_years) {
+        return Err(ContractError::ProjectVintageExpired {
+            project_id: project.project_id.clone(),
+            vintage_year: project.vintage_year,
+        });
+    }
+    Ok(())
+}
+
+fn ensure_not_expired(
+    storage: &dyn Storage,
+    batch_id: u64,
+    now: Timestamp,
+) -> Result<(), ContractError> {
+    if is_expired(storage, batch_id, now)? {
+        return Err(ContractError::VintageExpired {
+            batch_id,
+            vintage_year: BATCHES.load(storage, batch_id)?.vintage_year,
+        });
+    }
+    Ok(())
+}
+
+/// The start of 1 January of `year`, UTC.
+fn year_start(year: u32) -> Timestamp {
+    let leap_days = |year: u64| year / 4 - year / 100 + year / 400;
+    let year = u64::from(year.max(1970));
+    let days = 365 * (year - 1970) + leap_days(year - 1) - leap_days(1969);
+    Timestamp::from_seconds(days * SECONDS_PER_DAY)
+}
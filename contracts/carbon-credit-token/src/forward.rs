@@ -0,0 +1,227 @@
+//! Forward issuance.
+//!
+//! A developer can sell reductions it expects to verify later by issuing
+//! forward units against a monitoring schedule. Forward units are not credits:
+//! they are kept per series and holder outside the CW20 balances, and cannot
+//! be retired. A verification delivered against the series settles its next
+//! monitoring period: that period's units are converted one for one into the
+//! verified credits, split between the holders in proportion to their units.
+//! When fewer credits are verified than the period expected, every holder
+//! receives its share of what was verified and the rest of its converted
+//! units are written off as shortfall.
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::Bound;
+use rwa_common::{DEFAULT_LIMIT, MAX_LIMIT};
+
+use crate::buffer::split_pro_rata;
+use crate::error::ContractError;
+use crate::msg::MonitoringPeriod;
+use crate::state::{
+    CreditAllocation, ForwardPeriod, ForwardPosition, ForwardSeries, FORWARD_POSITIONS,
+    FORWARD_SERIES,
+};
+
+/// Opens a series of forward units, as many as the schedule expects, held by
+/// the allocations.
+pub fn issue_forward(
+    storage: &mut dyn Storage,
+    issued_at: Timestamp,
+    issued_by: Addr,
+    schedule: Vec<MonitoringPeriod>,
+    issued_to: &[CreditAllocation],
+) -> Result<ForwardSeries, ContractError> {
+    if schedule.is_empty() {
+        return Err(ContractError::InvalidMonitoringSchedule {
+            reason: "it has no periods".to_string(),
+        });
+    }
+    let mut periods: Vec<ForwardPeriod> = Vec::with_capacity(schedule.len());
+    let mut units_issued = Uint128::zero();
+    for period in schedule {
+        if period.expected_credits.is_zero() {
+            return Err(ContractError::InvalidMonitoringSchedule {
+                reason: format!("the period ending {} expects no credits", period.period_end),
+            });
+        }
+        if let Some(previous) = periods.last() {
+            if period.period_end <= previous.period_end {
+                return Err(ContractError::InvalidMonitoringSchedule {
+                    reason: format!(
+                        "the period ending {} does not follow the one ending {}",
+                        period.period_end, previous.period_end
+                    ),
+                });
+            }
+        }
+        units_issued = units_issued.checked_add(period.expected_credits)?;
+        periods.push(ForwardPeriod {
+            period_end: period.period_end,
+            expected_credits: period.expected_credits,
+            verification_id: None,
+            credits_delivered: Uint128::zero(),
+        });
+    }
+
+    let series_id = FORWARD_SERIES
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(1, |last| last + 1);
+    let series = ForwardSeries {
+        series_id,
+        issued_at,
+        issued_by,
+        periods,
+        units_issued,
+        units_outstanding: units_issued,
+        credits_delivered: Uint128::zero(),
+    };
+    FORWARD_SERIES.save(storage, series_id, &series)?;
+    for allocation in issued_to {
+        credit_forward(
+            storage,
+            series_id,
+            &allocation.recipient,
+            allocation.credits,
+        )?;
+    }
+    Ok(series)
+}
+
+/// Moves `amount` forward units of the series from `from` to `to`.
+pub fn transfer_forward(
+    storage: &mut dyn Storage,
+    series_id: u64,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    load_series(storage, series_id)?;
+    let mut position = forward_position(storage, series_id, from)?;
+    if position.forward < amount {
+        return Err(ContractError::InsufficientForwardUnits {
+            series_id,
+            requested: amount,
+            available: position.forward,
+        });
+    }
+    position.forward -= amount;
+    FORWARD_POSITIONS.save(storage, (series_id, from), &position)?;
+    credit_forward(storage, series_id, to, amount)
+}
+
+/// Settles the series' next period with the `credits` of a verification.
+/// Returns what each holder receives of them, and the credits in excess of
+/// what the period expected.
+pub fn deliver_forward(
+    storage: &mut dyn Storage,
+    series_id: u64,
+    verification_id: &str,
+    credits: Uint128,
+) -> Result<(Vec<CreditAllocation>, Uint128), ContractError> {
+    let mut series = load_series(storage, series_id)?;
+    let Some(period) = series
+        .periods
+        .iter_mut()
+        .find(|period| period.verification_id.is_none())
+    else {
+        return Err(ContractError::ForwardSeriesSettled { series_id });
+    };
+    let expected = period.expected_credits;
+    let delivered = credits.min(expected);
+    period.verification_id = Some(verification_id.to_string());
+    period.credits_delivered = delivered;
+
+    // Every holder converts its share of the period's units, and receives its
+    // share of the credits delivered for them
+    let holders = FORWARD_POSITIONS
+        .prefix(series_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(holder, position)| (holder, position.forward)))
+        .collect::<StdResult<Vec<_>>>()?;
+    let converted = split_pro_rata(holders, series.units_outstanding, expected);
+    let delivered_to = split_pro_rata(
+        converted
+            .iter()
+            .map(|share| (share.recipient.clone(), share.credits))
+            .collect(),
+        expected,
+        delivered,
+    );
+    for share in &converted {
+        let received = delivered_to
+            .iter()
+            .find(|delivery| delivery.recipient == share.recipient)
+            .map_or(Uint128::zero(), |delivery| delivery.credits);
+        FORWARD_POSITIONS.update(
+            storage,
+            (series_id, &share.recipient),
+            |position| -> StdResult<_> {
+                let mut position = position.unwrap_or_default();
+                position.forward = position.forward.checked_sub(share.credits)?;
+                position.delivered = position.delivered.checked_add(received)?;
+                position.shortfall = position.shortfall.checked_add(share.credits - received)?;
+                Ok(position)
+            },
+        )?;
+    }
+
+    series.units_outstanding = series.units_outstanding.checked_sub(expected)?;
+    series.credits_delivered = series.credits_delivered.checked_add(delivered)?;
+    FORWARD_SERIES.save(storage, series_id, &series)?;
+    Ok((delivered_to, credits - delivered))
+}
+
+pub fn load_series(storage: &dyn Storage, series_id: u64) -> Result<ForwardSeries, ContractError> {
+    FORWARD_SERIES
+        .may_load(storage, series_id)?
+        .ok_or(ContractError::ForwardSeriesNotFound { series_id })
+}
+
+/// `holder`'s position in the series; nothing for an account that never held
+/// its units.
+pub fn forward_position(
+    storage: &dyn Storage,
+    series_id: u64,
+    holder: &Addr,
+) -> StdResult<ForwardPosition> {
+    Ok(FORWARD_POSITIONS
+        .may_load(storage, (series_id, holder))?
+        .unwrap_or_default())
+}
+
+/// Up to `limit` positions in the series, in holder address order, starting
+/// after `start_after`.
+pub fn forward_positions(
+    storage: &dyn Storage,
+    series_id: u64,
+    start_after: Option<&Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, ForwardPosition)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    FORWARD_POSITIONS
+        .prefix(series_id)
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect()
+}
+
+fn credit_forward(
+    storage: &mut dyn Storage,
+    series_id: u64,
+    holder: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    FORWARD_POSITIONS.update(storage, (series_id, holder), |position| -> StdResult<_> {
+        let mut position = position.unwrap_or_default();
+        position.forward = position.forward.checked_add(amount)?;
+        Ok(position)
+    })?;
+    Ok(())
+}
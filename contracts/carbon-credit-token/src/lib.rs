@@ -1,7 +1,21 @@
+pub mod authorization;
+pub mod batches;
+pub mod buffer;
+pub mod claims;
 pub mod contract;
+pub mod error;
+pub mod expiry;
+pub mod forward;
 pub mod msg;
+pub mod registry;
+pub mod serials;
 pub mod state;
+pub mod tags;
 
-pub use contract::{instantiate, execute, query};
-pub use msg::{InstantiateMsg, ExecuteMsg, QueryMsg};
-pub use state::{CarbonCreditInfo, VerificationRecord, RetirementRecord};
+#[cfg(test)]
+mod multitest;
+
+pub use crate::contract::{execute, instantiate, migrate, query, reply};
+pub use crate::error::ContractError;
+pub use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+pub use crate::state::{Batch, CarbonCreditInfo, RetirementRecord, VerificationRecord};
@@ -0,0 +1,662 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use rwa_common::{
+    Cw20ExecuteMsg, Cw20QueryMsg, Drift, PauseCategory, PauseExecuteMsg, PauseQueryMsg,
+    RoleExecuteMsg, RoleQueryMsg, SanctionExecuteMsg, SanctionQueryMsg,
+};
+
+use crate::state::{
+    AuthorizationStatus, Batch, BatchAmount, BatchAuthorization, CarbonCreditInfo, CoBenefit,
+    CreditAllocation, CreditUse, ForwardPosition, ForwardSeries, Methodology, ProjectTagRecord,
+    ProjectTags, ProjectType, RegistrySerials, RetirementRecord, ReversalRecord, Sdg,
+    VerificationRecord, VerificationStatus, VintagePolicy,
+};
+
+// Extended instantiate message
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub cw20_base: Cw20InstantiateMsg,
+    pub carbon_credit_info: CarbonCreditInfo,
+    /// Administrator of the role registry, defaulting to the sender
+    pub admin: Option<String>,
+    /// Whether token movements are screened against the sanction blacklist,
+    /// defaulting to true
+    pub sanction_screening: Option<bool>,
+    /// Code of the retirement certificate collection to instantiate alongside
+    /// the token; without one retirements mint no certificates
+    pub certificate_code_id: Option<u64>,
+}
+
+// Extended execute messages: the standard CW20 messages, the shared role, pause
+// and sanction messages, and the carbon credit specific ones, sharing the same JSON
+// namespace
+#[cw_serde]
+#[serde(untagged)]
+pub enum ExecuteMsg {
+    Cw20(Cw20ExecuteMsg),
+    Roles(RoleExecuteMsg),
+    Pause(PauseExecuteMsg),
+    Sanctions(SanctionExecuteMsg),
+    CarbonCredit(CarbonCreditExecuteMsg),
+}
+
+// Carbon credit specific messages. Messages that create a record take an
+// optional id: without one the next generated id is used. Either way the id is
+// returned in the response data.
+#[cw_serde]
+pub enum CarbonCreditExecuteMsg {
+    VerifyCredits {
+        verification_id: Option<String>,
+        credits_to_verify: Uint128,
+        verification_report_url: String,
+        /// Who the verified credits are minted to, defaulting to the project
+        /// developer. The amounts must add up to `credits_to_verify`.
+        recipients: Option<Vec<IssuanceRecipient>>,
+        /// Vintage of the issued batch, defaulting to the project's
+        vintage_year: Option<u32>,
+        methodology_version: Option<String>,
+        /// The registry serials of the verified credits, one per credit. A
+        /// block overlapping one already tokenized is rejected.
+        registry_serials: Option<RegistrySerials>,
+        /// A forward series the credits are delivered against. They settle
+        /// its next monitoring period: up to the credits that period expected
+        /// go to the series' holders, and `recipients` split the rest.
+        forward_series: Option<u64>,
+    },
+    /// Records a verification as pending; its credits are minted once the
+    /// status moves to `Verified`
+    SubmitVerification {
+        verification_id: Option<String>,
+        credits_to_verify: Uint128,
+        verification_report_url: String,
+        recipients: Option<Vec<IssuanceRecipient>>,
+        vintage_year: Option<u32>,
+        methodology_version: Option<String>,
+        registry_serials: Option<RegistrySerials>,
+    },
+    /// Retires credits from `batch_id`, or from the oldest batches held. The
+    /// lowest serials not yet retired are consumed unless `registry_serials`
+    /// names the ones to retire.
+    RetireCredits {
+        retirement_id: Option<String>,
+        credits_to_retire: Uint128,
+        retirement_purpose: String,
+        retirement_certificate_url: String,
+        batch_id: Option<u64>,
+        registry_serials: Option<RegistrySerials>,
+        /// Who the retirement is claimed for, when not the retiring account;
+        /// the certificate is minted to the beneficiary
+        beneficiary: Option<String>,
+        /// The beneficiary's name as shown on the certificate
+        beneficiary_name: Option<String>,
+        /// What the retirement is counted towards, defaulting to voluntary.
+        /// NDC and CORSIA use take batches the host country authorized for it.
+        retirement_use: Option<CreditUse>,
+    },
+    /// Retires credits of `owner` within the allowance it gave the sender, as
+    /// `TransferFrom` moves them
+    RetireCreditsFrom {
+        owner: String,
+        retirement_id: Option<String>,
+        credits_to_retire: Uint128,
+        retirement_purpose: String,
+        retirement_certificate_url: String,
+        batch_id: Option<u64>,
+        registry_serials: Option<RegistrySerials>,
+        beneficiary: Option<String>,
+        beneficiary_name: Option<String>,
+        retirement_use: Option<CreditUse>,
+    },
+    /// Transfers credits of one batch; a plain CW20 transfer moves the oldest
+    /// batches held
+    TransferBatch {
+        batch_id: u64,
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Moves a record along Pending -> Verified / Rejected, or Verified ->
+    /// Expired. Expiry burns the record's batch from whoever holds it now and
+    /// cancels what the buffer pool still holds of it; credits already retired
+    /// stay retired. Unlike `ReportReversal`, which cancels holders' credits a
+    /// page at a time and leaves the rest to `ContinueReversal`, expiry burns
+    /// from every holder at once, and it fails while a reversal is still
+    /// working through its holders.
+    UpdateVerificationStatus {
+        verification_id: String,
+        status: VerificationStatus,
+        reason: Option<String>,
+    },
+    /// CW20 receive hook: credits of this contract sent to it with a
+    /// `ReceiveMsg` are retired for the sender, and credits of an approved
+    /// carbon credit contract are forwarded to that contract to be retired
+    /// there
+    Receive(Cw20ReceiveMsg),
+    /// Trusts the carbon credit token contract `token` to retire the credits
+    /// the receive hook forwards to it. Role admin only.
+    ApproveForwardToken {
+        token: String,
+    },
+    /// Stops forwarding credits of `token`. Role admin only.
+    RevokeForwardToken {
+        token: String,
+    },
+    /// Sets the share of every issuance of `project_type` projects withheld
+    /// into the buffer pool. Role admin only.
+    SetBufferRate {
+        project_type: ProjectType,
+        rate: Decimal,
+    },
+    /// Records the loss of `credits` worth of stored carbon of the contract's
+    /// own project. Buffer pool credits are cancelled first, then holders'
+    /// credits pro-rata, starting with the first page of holders. Registered
+    /// projects' buffered credits are never drawn on.
+    ReportReversal {
+        reversal_id: Option<String>,
+        credits: Uint128,
+        reason: String,
+    },
+    /// Cancels the pending reversal's share from the next `limit` holders.
+    /// Anyone can send it; credits cannot move until every holder is covered.
+    ContinueReversal {
+        limit: Option<u32>,
+    },
+    /// Registers a project held in registry mode, with its own verification
+    /// body, developer and counters. Its counters start at zero whatever is
+    /// passed. Role admin only.
+    RegisterProject {
+        project: CarbonCreditInfo,
+    },
+    /// Verifies credits of a registered project and issues them to its
+    /// developer, or split between the listed `recipients`. Sent by the
+    /// project's verification body.
+    VerifyProjectCredits {
+        project_id: String,
+        verification_id: Option<String>,
+        credits_to_verify: Uint128,
+        verification_report_url: String,
+        recipients: Option<Vec<IssuanceRecipient>>,
+    },
+    /// Moves credits of one or more registered projects from `from`,
+    /// defaulting to the sender, to `recipient`. Moving another owner's
+    /// credits takes its operator approval.
+    TransferProjectCredits {
+        from: Option<String>,
+        recipient: String,
+        amounts: Vec<ProjectAmount>,
+    },
+    /// Retires the sender's credits of a registered project
+    RetireProjectCredits {
+        project_id: String,
+        retirement_id: Option<String>,
+        credits_to_retire: Uint128,
+        retirement_purpose: String,
+        retirement_certificate_url: String,
+        beneficiary: Option<String>,
+        beneficiary_name: Option<String>,
+        /// What the retirement is counted towards, defaulting to voluntary.
+        /// NDC and CORSIA use take a project the host country authorized for
+        /// it, of an unexpired vintage.
+        retirement_use: Option<CreditUse>,
+    },
+    /// Lets `operator` move all of the sender's registered project credits
+    ApproveAll {
+        operator: String,
+    },
+    RevokeAll {
+        operator: String,
+    },
+    /// Issues forward units, as many as the monitoring schedule expects, to
+    /// the project developer or split between the listed `recipients`. Sent
+    /// by the project developer.
+    IssueForward {
+        schedule: Vec<MonitoringPeriod>,
+        recipients: Option<Vec<IssuanceRecipient>>,
+    },
+    /// Moves forward units of a series from the sender to `recipient`
+    TransferForward {
+        series_id: u64,
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Records the host country's Article 6 authorization of a batch: its
+    /// status, Letter of Authorization reference and the uses beyond
+    /// voluntary it covers. Sent by the host country authority.
+    SetBatchAuthorization {
+        batch_id: u64,
+        status: AuthorizationStatus,
+        letter_of_authorization: Option<String>,
+        authorized_uses: Vec<CreditUse>,
+        corresponding_adjustment: bool,
+    },
+    /// Records the host country's Article 6 authorization of a registered
+    /// project, whose credits keep no batches. Sent by the host country
+    /// authority.
+    SetProjectAuthorization {
+        project_id: String,
+        status: AuthorizationStatus,
+        letter_of_authorization: Option<String>,
+        authorized_uses: Vec<CreditUse>,
+        corresponding_adjustment: bool,
+    },
+    /// Replaces the tags of a registered project, or of the contract's own
+    /// without `project_id`, as a new revision awaiting attestation. Sent by
+    /// the project developer.
+    SetProjectTags {
+        project_id: Option<String>,
+        tags: ProjectTags,
+    },
+    /// Attests the current revision of a project's tags. Sent by the
+    /// project's verification body.
+    AttestProjectTags {
+        project_id: Option<String>,
+        revision: u32,
+        report_url: String,
+    },
+    /// Sets how long vintages stay valid, or with no policy lets them stay
+    /// valid for good. Role admin only.
+    SetVintagePolicy {
+        policy: Option<VintagePolicy>,
+    },
+    /// Records on the verified records of up to `limit` batches after
+    /// `start_after` when their vintage expired under the vintage policy.
+    /// Unlike an expiry by the verification body, neither the status nor the
+    /// credits change: their holders keep them, but cannot retire them for
+    /// NDC or CORSIA use. Anyone can sweep.
+    SweepExpired {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// The payload of a CW20 `Send` to the receive hook.
+#[cw_serde]
+pub enum ReceiveMsg {
+    Retire(RetireMsg),
+}
+
+#[cw_serde]
+pub struct RetireMsg {
+    pub retirement_id: Option<String>,
+    pub retirement_purpose: String,
+    pub retirement_certificate_url: String,
+    /// Who the retirement is claimed for, defaulting to the sender of the
+    /// credits
+    pub beneficiary: Option<String>,
+    pub beneficiary_name: Option<String>,
+    /// What the retirement is counted towards, defaulting to voluntary
+    pub retirement_use: Option<CreditUse>,
+}
+
+/// An amount of a registered project's credits.
+#[cw_serde]
+pub struct ProjectAmount {
+    pub project_id: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct IssuanceRecipient {
+    pub address: String,
+    pub credits: Uint128,
+}
+
+/// A tag to filter projects and their records by.
+#[cw_serde]
+pub enum ProjectTag {
+    ProjectType(ProjectType),
+    Methodology(Methodology),
+    Sdg(Sdg),
+    CoBenefit(CoBenefit),
+}
+
+/// A monitoring period of a forward series, and the credits it is expected to
+/// verify.
+#[cw_serde]
+pub struct MonitoringPeriod {
+    pub period_end: Timestamp,
+    pub expected_credits: Uint128,
+}
+
+impl From<RoleExecuteMsg> for ExecuteMsg {
+    fn from(msg: RoleExecuteMsg) -> Self {
+        ExecuteMsg::Roles(msg)
+    }
+}
+
+impl From<SanctionExecuteMsg> for ExecuteMsg {
+    fn from(msg: SanctionExecuteMsg) -> Self {
+        ExecuteMsg::Sanctions(msg)
+    }
+}
+
+impl From<PauseExecuteMsg> for ExecuteMsg {
+    fn from(msg: PauseExecuteMsg) -> Self {
+        ExecuteMsg::Pause(msg)
+    }
+}
+
+impl CarbonCreditExecuteMsg {
+    /// The circuit breaker that stops this message, if any. Operator
+    /// approvals, like CW20 allowances, are never paused.
+    pub fn pause_category(&self) -> Option<PauseCategory> {
+        let category = match self {
+            CarbonCreditExecuteMsg::VerifyCredits { .. }
+            | CarbonCreditExecuteMsg::VerifyProjectCredits { .. }
+            | CarbonCreditExecuteMsg::IssueForward { .. } => PauseCategory::Issuance,
+            CarbonCreditExecuteMsg::SubmitVerification { .. } => PauseCategory::RecordKeeping,
+            CarbonCreditExecuteMsg::RetireCredits { .. }
+            | CarbonCreditExecuteMsg::RetireCreditsFrom { .. }
+            | CarbonCreditExecuteMsg::RetireProjectCredits { .. }
+            | CarbonCreditExecuteMsg::Receive(_) => PauseCategory::Retirement,
+            CarbonCreditExecuteMsg::TransferBatch { .. }
+            | CarbonCreditExecuteMsg::TransferProjectCredits { .. }
+            | CarbonCreditExecuteMsg::TransferForward { .. } => PauseCategory::Transfers,
+            CarbonCreditExecuteMsg::UpdateVerificationStatus {
+                status: VerificationStatus::Verified,
+                ..
+            } => PauseCategory::Issuance,
+            CarbonCreditExecuteMsg::UpdateVerificationStatus { .. }
+            | CarbonCreditExecuteMsg::ApproveForwardToken { .. }
+            | CarbonCreditExecuteMsg::RevokeForwardToken { .. }
+            | CarbonCreditExecuteMsg::SetBufferRate { .. }
+            | CarbonCreditExecuteMsg::ReportReversal { .. }
+            | CarbonCreditExecuteMsg::ContinueReversal { .. }
+            | CarbonCreditExecuteMsg::RegisterProject { .. }
+            | CarbonCreditExecuteMsg::SetBatchAuthorization { .. }
+            | CarbonCreditExecuteMsg::SetProjectAuthorization { .. }
+            | CarbonCreditExecuteMsg::SetProjectTags { .. }
+            | CarbonCreditExecuteMsg::AttestProjectTags { .. }
+            | CarbonCreditExecuteMsg::SetVintagePolicy { .. }
+            | CarbonCreditExecuteMsg::SweepExpired { .. } => PauseCategory::RecordKeeping,
+            CarbonCreditExecuteMsg::ApproveAll { .. }
+            | CarbonCreditExecuteMsg::RevokeAll { .. } => return None,
+        };
+        Some(category)
+    }
+}
+
+impl From<CarbonCreditExecuteMsg> for ExecuteMsg {
+    fn from(msg: CarbonCreditExecuteMsg) -> Self {
+        ExecuteMsg::CarbonCredit(msg)
+    }
+}
+
+// Extended query messages
+#[cw_serde]
+#[derive(QueryResponses)]
+#[serde(untagged)]
+#[query_responses(nested)]
+pub enum QueryMsg {
+    Cw20(Cw20QueryMsg),
+    Roles(RoleQueryMsg),
+    Pause(PauseQueryMsg),
+    Sanctions(SanctionQueryMsg),
+    CarbonCredit(CarbonCreditQueryMsg),
+}
+
+// Carbon credit specific queries
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum CarbonCreditQueryMsg {
+    #[returns(CarbonCreditInfo)]
+    CarbonCreditInfo {},
+    #[returns(VerificationRecord)]
+    VerificationRecord { verification_id: String },
+    #[returns(Vec<(String, VerificationRecord)>)]
+    AllVerificationRecords {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(RetirementRecord)]
+    RetirementRecord { retirement_id: String },
+    #[returns(Vec<(String, RetirementRecord)>)]
+    AllRetirementRecords {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Uint128)]
+    AvailableCredits {},
+    #[returns(Uint128)]
+    RetiredCredits {},
+    #[returns(ReconcileSupplyResponse)]
+    ReconcileSupply {},
+    #[returns(Batch)]
+    Batch { batch_id: u64 },
+    #[returns(Vec<Batch>)]
+    AllBatches {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// The batches the last CW20 send drew its credits from, for the
+    /// receiving contract to read in its receive hook
+    #[returns(Vec<BatchAmount>)]
+    SentBatches {},
+    /// An account's balance split by batch, the base batch first
+    #[returns(Vec<BatchAmount>)]
+    BatchBalances {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Where a serial stands, in the numbering of `registry` or, without one,
+    /// the contract's own
+    #[returns(SerialStatusResponse)]
+    SerialStatus {
+        serial: Uint128,
+        registry: Option<String>,
+    },
+    /// The retirement certificate collection, if there is one
+    #[returns(Option<Addr>)]
+    CertificateCollection {},
+    /// The retirement a certificate of the collection attests
+    #[returns(RetirementRecord)]
+    CertificateRetirement { token_id: String },
+    /// The carbon credit token contracts the receive hook forwards credits to,
+    /// in address order
+    #[returns(Vec<Addr>)]
+    ForwardTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The project's buffer rate and the credits held in the buffer pool
+    #[returns(BufferPoolResponse)]
+    BufferPool {},
+    /// The buffer rate of any project type
+    #[returns(Decimal)]
+    BufferRate { project_type: ProjectType },
+    #[returns(ReversalRecord)]
+    ReversalRecord { reversal_id: String },
+    #[returns(Vec<(String, ReversalRecord)>)]
+    AllReversalRecords {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The credits a reversal cancelled from each holder, in address order
+    #[returns(Vec<CreditAllocation>)]
+    ReversalCancellations {
+        reversal_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// A registered project with its counters
+    #[returns(CarbonCreditInfo)]
+    Project { project_id: String },
+    #[returns(Vec<(String, CarbonCreditInfo)>)]
+    AllProjects {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Uint128)]
+    ProjectBalance { owner: String, project_id: String },
+    /// An owner's balances of registered projects, in project id order
+    #[returns(Vec<ProjectAmount>)]
+    ProjectBalances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(bool)]
+    IsApprovedForAll { owner: String, operator: String },
+    /// The host country's authorization of a batch, if it has recorded one
+    #[returns(Option<BatchAuthorization>)]
+    BatchAuthorization { batch_id: u64 },
+    /// The host country's authorization of a registered project, if it has
+    /// recorded one
+    #[returns(Option<BatchAuthorization>)]
+    ProjectAuthorization { project_id: String },
+    #[returns(ForwardSeries)]
+    ForwardSeries { series_id: u64 },
+    /// A holder's units of a forward series still to be delivered, the
+    /// credits delivered for its units, and the units written off
+    #[returns(ForwardPosition)]
+    ForwardPosition { series_id: u64, holder: String },
+    /// Every holder's position in a forward series, in address order
+    #[returns(Vec<(Addr, ForwardPosition)>)]
+    ForwardPositions {
+        series_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The retirements claimed for `entity`, as retirement entity or named
+    /// beneficiary, dated from `from` up to but not including `to`
+    #[returns(RetirementSummaryResponse)]
+    RetirementSummary {
+        entity: String,
+        from: Timestamp,
+        to: Timestamp,
+    },
+    /// The current tags of a registered project, or of the contract's own
+    /// without `project_id`, if it has any
+    #[returns(Option<ProjectTagRecord>)]
+    ProjectTags { project_id: Option<String> },
+    /// Every revision of a project's tags, oldest first
+    #[returns(Vec<ProjectTagRecord>)]
+    TagHistory {
+        project_id: Option<String>,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// The projects carrying `tag`, in project id order. With
+    /// `attested_only`, those whose current tags are attested.
+    #[returns(Vec<(String, ProjectTagRecord)>)]
+    ProjectsByTag {
+        tag: ProjectTag,
+        attested_only: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The verification records of projects carrying `tag`, in id order
+    #[returns(Vec<(String, VerificationRecord)>)]
+    VerificationRecordsByTag {
+        tag: ProjectTag,
+        attested_only: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The retirement records of projects carrying `tag`, in id order
+    #[returns(Vec<(String, RetirementRecord)>)]
+    RetirementRecordsByTag {
+        tag: ProjectTag,
+        attested_only: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Option<VintagePolicy>)]
+    VintagePolicy {},
+}
+
+impl From<RoleQueryMsg> for QueryMsg {
+    fn from(msg: RoleQueryMsg) -> Self {
+        QueryMsg::Roles(msg)
+    }
+}
+
+impl From<SanctionQueryMsg> for QueryMsg {
+    fn from(msg: SanctionQueryMsg) -> Self {
+        QueryMsg::Sanctions(msg)
+    }
+}
+
+impl From<PauseQueryMsg> for QueryMsg {
+    fn from(msg: PauseQueryMsg) -> Self {
+        QueryMsg::Pause(msg)
+    }
+}
+
+impl From<CarbonCreditQueryMsg> for QueryMsg {
+    fn from(msg: CarbonCreditQueryMsg) -> Self {
+        QueryMsg::CarbonCredit(msg)
+    }
+}
+
+#[cw_serde]
+pub struct ReconcileSupplyResponse {
+    /// `credits_available` against the CW20 total supply
+    pub supply: Drift<Uint128>,
+    /// `total_credits_issued` against `credits_available + credits_retired +
+    /// credits_buffered`
+    pub issuance: Drift<Uint128>,
+    pub reconciled: bool,
+}
+
+#[cw_serde]
+pub struct SerialStatusResponse {
+    /// The batch the serial was issued in, if it was
+    pub batch_id: Option<u64>,
+    pub state: SerialState,
+    /// When the vintage of the serial's batch expired, once a sweep recorded it
+    pub vintage_expired_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub enum SerialState {
+    /// Tokenized by a verification still pending
+    Pending { verification_id: String },
+    /// Issued and held by some account
+    Held,
+    Retired {
+        retirement_id: String,
+        retirement_entity: Addr,
+    },
+    /// Reversed by the expiry of its verification
+    Expired { verification_id: String },
+}
+
+#[cw_serde]
+pub struct BufferPoolResponse {
+    pub project_type: ProjectType,
+    pub rate: Decimal,
+    pub credits: Uint128,
+    /// The credits held by batch, oldest first
+    pub batches: Vec<BatchAmount>,
+}
+
+#[cw_serde]
+pub struct RetirementSummaryResponse {
+    pub entity: Addr,
+    pub from: Timestamp,
+    pub to: Timestamp,
+    pub total: RetirementTotals,
+    /// The totals by retirement purpose, in purpose order
+    pub by_purpose: Vec<PurposeTotals>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct RetirementTotals {
+    pub retirements: u32,
+    pub credits_retired: Uint128,
+    /// Tonnes of CO2 equivalent, at each project's `co2_equivalent_per_credit`
+    pub co2_equivalent: Decimal,
+}
+
+#[cw_serde]
+pub struct PurposeTotals {
+    pub retirement_purpose: String,
+    pub totals: RetirementTotals,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,158 @@
+//! Article 6 authorization: NDC and CORSIA retirements drawn only from batches
+//! the host country authorized for them.
+
+use cosmwasm_std::Addr;
+
+use super::suite::{Suite, SuiteBuilder, ADMIN, DEVELOPER, VERIFIER};
+use crate::error::ContractError;
+use crate::state::{AuthorizationStatus, CreditUse, HOST_COUNTRY_AUTHORITY};
+
+const AUTHORITY: &str = "host-authority";
+
+/// The developer holds batch 1 of 1000 credits and batch 2 of 500.
+fn setup() -> Suite {
+    let mut suite = SuiteBuilder::new().build();
+    suite.verify_credits(VERIFIER, "v-1", 1_000).unwrap();
+    suite.verify_credits(VERIFIER, "v-2", 500).unwrap();
+    suite
+        .grant_role(ADMIN, HOST_COUNTRY_AUTHORITY, AUTHORITY)
+        .unwrap();
+    suite
+}
+
+#[test]
+fn batches_are_authorized_by_the_host_country_authority() {
+    let mut suite = setup();
+
+    let err = suite
+        .set_batch_authorization(
+            DEVELOPER,
+            1,
+            AuthorizationStatus::Authorized,
+            &[CreditUse::Ndc],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotHostCountryAuthority {
+            action: "authorize batches".to_string()
+        },
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .set_batch_authorization(
+            AUTHORITY,
+            9,
+            AuthorizationStatus::Authorized,
+            &[CreditUse::Ndc],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::BatchNotFound { batch_id: 9 },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .set_batch_authorization(
+            AUTHORITY,
+            1,
+            AuthorizationStatus::Authorized,
+            &[CreditUse::Ndc, CreditUse::Corsia],
+        )
+        .unwrap();
+    let authorization = suite.batch_authorization(1).unwrap();
+    assert_eq!(authorization.status, AuthorizationStatus::Authorized);
+    assert_eq!(
+        authorization.letter_of_authorization.as_deref(),
+        Some("LoA-1")
+    );
+    assert_eq!(
+        authorization.authorized_uses,
+        [CreditUse::Ndc, CreditUse::Corsia]
+    );
+    assert!(authorization.corresponding_adjustment);
+    assert_eq!(authorization.updated_by, Addr::unchecked(AUTHORITY));
+    assert_eq!(suite.batch_authorization(2), None);
+}
+
+#[test]
+fn retirements_need_an_authorization_for_their_use() {
+    let mut suite = setup();
+    suite
+        .set_batch_authorization(
+            AUTHORITY,
+            1,
+            AuthorizationStatus::Authorized,
+            &[CreditUse::Ndc],
+        )
+        .unwrap();
+    suite
+        .set_batch_authorization(
+            AUTHORITY,
+            2,
+            AuthorizationStatus::Authorized,
+            &[CreditUse::Corsia],
+        )
+        .unwrap();
+
+    // Retirements draw on the oldest batch unless one is named
+    suite
+        .retire_for_use(DEVELOPER, "r-1", 100, None, CreditUse::Ndc)
+        .unwrap();
+    assert_eq!(
+        suite.retirement_record("r-1").retirement_use,
+        CreditUse::Ndc
+    );
+    let err = suite
+        .retire_for_use(DEVELOPER, "r-2", 100, None, CreditUse::Corsia)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::UseNotAuthorized {
+            batch_id: 1,
+            retirement_use: CreditUse::Corsia,
+        },
+        err.downcast().unwrap()
+    );
+    suite
+        .retire_for_use(DEVELOPER, "r-2", 100, Some(2), CreditUse::Corsia)
+        .unwrap();
+    let err = suite
+        .retire_for_use(DEVELOPER, "r-3", 100, Some(2), CreditUse::Ndc)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::UseNotAuthorized {
+            batch_id: 2,
+            retirement_use: CreditUse::Ndc,
+        },
+        err.downcast().unwrap()
+    );
+
+    // Voluntary retirements need no authorization
+    suite
+        .retire_for_use(DEVELOPER, "r-3", 100, Some(2), CreditUse::Voluntary)
+        .unwrap();
+    suite.retire_credits(DEVELOPER, "r-4", 100).unwrap();
+    assert_eq!(
+        suite.retirement_record("r-4").retirement_use,
+        CreditUse::Voluntary
+    );
+
+    // A revoked authorization no longer covers its uses
+    suite
+        .set_batch_authorization(
+            AUTHORITY,
+            1,
+            AuthorizationStatus::Revoked,
+            &[CreditUse::Ndc],
+        )
+        .unwrap();
+    let err = suite
+        .retire_for_use(DEVELOPER, "r-5", 100, None, CreditUse::Ndc)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::UseNotAuthorized {
+            batch_id: 1,
+            retirement_use: CreditUse::Ndc,
+        },
+        err.downcast().unwrap()
+    );
+}
@@ -0,0 +1,135 @@
+//! Issuance batches: per-batch balances, the oldest-first default for plain
+//! transfers and retirements, and transfers and retirements of a named batch.
+
+use cosmwasm_std::Uint128;
+
+use super::suite::{Suite, SuiteBuilder, DEVELOPER, VERIFIER};
+use crate::error::ContractError;
+use crate::state::{BatchAmount, VerificationStatus};
+
+/// 100 credits from instantiation in the base batch, then 300 of vintage 2021
+/// in batch 1 and 200 of the project's vintage in batch 2, all held by the
+/// developer.
+fn setup() -> Suite {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 100).build();
+    suite.verify_vintage(VERIFIER, "v-1", 300, 2021).unwrap();
+    suite.verify_credits(VERIFIER, "v-2", 200).unwrap();
+    suite
+}
+
+#[test]
+fn verifications_open_batches_with_their_own_vintage() {
+    let suite = setup();
+
+    let batches = suite.all_batches();
+    let ids: Vec<_> = batches.iter().map(|batch| batch.batch_id).collect();
+    assert_eq!(ids, [0, 1, 2]);
+
+    let base = &batches[0];
+    assert_eq!(base.vintage_year, 2023);
+    assert_eq!(base.verification_id, None);
+    assert_eq!(
+        (base.first_serial, base.last_serial),
+        (Uint128::new(1), Uint128::new(100))
+    );
+
+    let batch = suite.batch(1);
+    assert_eq!(batch.vintage_year, 2021);
+    assert_eq!(batch.methodology_version.as_deref(), Some("VM0007 v1.6"));
+    assert_eq!(batch.verification_id.as_deref(), Some("v-1"));
+    assert_eq!(
+        (batch.first_serial, batch.last_serial),
+        (Uint128::new(101), Uint128::new(400))
+    );
+    assert_eq!(suite.verification_record("v-1").batch_id, Some(1));
+
+    // Without a vintage the project's applies
+    let batch = suite.batch(2);
+    assert_eq!(batch.vintage_year, 2023);
+    assert_eq!(
+        (batch.first_serial, batch.last_serial),
+        (Uint128::new(401), Uint128::new(600))
+    );
+
+    assert_eq!(
+        suite.batch_balances(DEVELOPER),
+        [(0, 100), (1, 300), (2, 200)]
+    );
+}
+
+#[test]
+fn plain_transfers_and_retirements_take_the_oldest_batches_first() {
+    let mut suite = setup();
+
+    suite.transfer(DEVELOPER, "buyer", 250).unwrap();
+    assert_eq!(suite.batch_balances("buyer"), [(0, 100), (1, 150)]);
+    assert_eq!(suite.batch_balances(DEVELOPER), [(1, 150), (2, 200)]);
+
+    suite.retire_credits("buyer", "r-1", 120).unwrap();
+    assert_eq!(suite.batch_balances("buyer"), [(1, 130)]);
+    assert_eq!(
+        suite.retirement_record("r-1").batches,
+        vec![
+            BatchAmount {
+                batch_id: 0,
+                credits: Uint128::new(100),
+            },
+            BatchAmount {
+                batch_id: 1,
+                credits: Uint128::new(20),
+            },
+        ]
+    );
+
+    suite.transfer(DEVELOPER, "broker", 200).unwrap();
+    assert_eq!(suite.batch_balances(DEVELOPER), [(2, 150)]);
+    assert_eq!(suite.batch_balances("broker"), [(1, 150), (2, 50)]);
+}
+
+#[test]
+fn transfers_and_retirements_can_name_a_batch() {
+    let mut suite = setup();
+
+    suite.transfer_batch(DEVELOPER, 2, "buyer", 150).unwrap();
+    assert_eq!(suite.batch_balances("buyer"), [(2, 150)]);
+    assert_eq!(suite.balance("buyer"), Uint128::new(150));
+    assert_eq!(
+        suite.batch_balances(DEVELOPER),
+        [(0, 100), (1, 300), (2, 50)]
+    );
+
+    let err = suite.retire_batch("buyer", "r-1", 1, 10).unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientBatchBalance {
+            batch_id: 1,
+            requested: Uint128::new(10),
+            available: Uint128::zero(),
+        },
+        err.downcast().unwrap()
+    );
+    let err = suite.transfer_batch(DEVELOPER, 9, "buyer", 10).unwrap_err();
+    assert_eq!(
+        ContractError::BatchNotFound { batch_id: 9 },
+        err.downcast().unwrap()
+    );
+
+    suite.retire_batch("buyer", "r-1", 2, 100).unwrap();
+    assert_eq!(suite.batch_balances("buyer"), [(2, 50)]);
+    assert_eq!(suite.retired_credits(), Uint128::new(100));
+
+    // The base batch can be named too
+    suite.retire_batch(DEVELOPER, "r-2", 0, 100).unwrap();
+    assert_eq!(suite.batch_balances(DEVELOPER), [(1, 300), (2, 50)]);
+}
+
+#[test]
+fn expiry_burns_from_the_expired_batch() {
+    let mut suite = setup();
+
+    suite
+        .update_verification_status(VERIFIER, "v-1", VerificationStatus::Expired)
+        .unwrap();
+    assert_eq!(suite.batch_balances(DEVELOPER), [(0, 100), (2, 200)]);
+    assert_eq!(suite.total_supply(), Uint128::new(300));
+    assert!(suite.reconcile_supply().reconciled);
+}
@@ -0,0 +1,249 @@
+//! The buffer pool: the share of issuance withheld per project type, and
+//! reversals covered by the pool before holders.
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use rwa_common::CommonError;
+
+use super::suite::{SuiteBuilder, ADMIN, DEVELOPER, VERIFIER};
+use crate::error::ContractError;
+use crate::state::{BatchAmount, CreditAllocation, ProjectType, VerificationStatus};
+
+const FOREST: ProjectType = ProjectType::ForestConservation;
+
+fn held(batch_id: u64, credits: u128) -> BatchAmount {
+    BatchAmount {
+        batch_id,
+        credits: Uint128::new(credits),
+    }
+}
+
+fn allocation(holder: &str, credits: u128) -> CreditAllocation {
+    CreditAllocation {
+        recipient: Addr::unchecked(holder),
+        credits: Uint128::new(credits),
+    }
+}
+
+#[test]
+fn issuance_withholds_the_project_type_buffer_rate() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite
+        .set_buffer_rate(DEVELOPER, FOREST, Decimal::percent(20))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "set buffer rates".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .set_buffer_rate(ADMIN, FOREST, Decimal::percent(150))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidBufferRate {
+            rate: Decimal::percent(150)
+        },
+        err.downcast().unwrap()
+    );
+    suite
+        .set_buffer_rate(ADMIN, ProjectType::RenewableEnergy, Decimal::percent(50))
+        .unwrap();
+    suite
+        .set_buffer_rate(ADMIN, FOREST, Decimal::percent(20))
+        .unwrap();
+
+    suite
+        .verify_credits_to(VERIFIER, "v-1", &[(DEVELOPER, 600), ("buyer", 401)])
+        .unwrap();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(480));
+    assert_eq!(suite.balance("buyer"), Uint128::new(321));
+
+    // Recipients are recorded with what they received
+    let record = suite.verification_record("v-1");
+    assert_eq!(record.buffer_credits, Uint128::new(200));
+    assert_eq!(
+        record.issued_to,
+        [allocation(DEVELOPER, 480), allocation("buyer", 321)]
+    );
+
+    let info = suite.carbon_credit_info();
+    assert_eq!(info.total_credits_issued, Uint128::new(1_001));
+    assert_eq!(info.credits_available, Uint128::new(801));
+    assert_eq!(info.credits_buffered, Uint128::new(200));
+    assert!(suite.reconcile_supply().reconciled);
+
+    let pool = suite.buffer_pool();
+    assert_eq!(pool.project_type, FOREST);
+    assert_eq!(pool.rate, Decimal::percent(20));
+    assert_eq!(pool.credits, Uint128::new(200));
+    assert_eq!(pool.batches, [held(1, 200)]);
+}
+
+#[test]
+fn reversals_cancel_buffer_credits_before_holders_pro_rata() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    suite
+        .set_buffer_rate(ADMIN, FOREST, Decimal::percent(10))
+        .unwrap();
+    suite.verify_credits(VERIFIER, "v-1", 1_000).unwrap();
+    suite.transfer(DEVELOPER, "buyer", 500).unwrap();
+
+    let err = suite.report_reversal(DEVELOPER, "rev-1", 60).unwrap_err();
+    assert_eq!(
+        ContractError::NotVerificationBody {
+            action: "report reversals".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    // The pool covers a small reversal on its own
+    suite.report_reversal(VERIFIER, "rev-1", 60).unwrap();
+    let reversal = suite.reversal_record("rev-1");
+    assert_eq!(reversal.buffer_cancelled, [held(1, 60)]);
+    assert!(reversal.holder_credits.is_zero());
+    assert!(suite.reversal_cancellations("rev-1").is_empty());
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(1_400));
+    assert_eq!(suite.buffer_pool().credits, Uint128::new(40));
+
+    // Holders cover what it cannot, by balance: of 97 credits buyer owes 25.53
+    // and developer 71.47, and the credit lost to rounding down is taken from
+    // the first of them
+    suite.report_reversal(VERIFIER, "rev-2", 137).unwrap();
+    let reversal = suite.reversal_record("rev-2");
+    assert_eq!(reversal.credits_reversed, Uint128::new(137));
+    assert_eq!(reversal.buffer_cancelled, [held(1, 40)]);
+    assert_eq!(reversal.holder_credits, Uint128::new(97));
+    assert!(!reversal.in_progress);
+    assert_eq!(
+        suite.reversal_cancellations("rev-2"),
+        [allocation("buyer", 26), allocation(DEVELOPER, 71)]
+    );
+    assert_eq!(suite.balance("buyer"), Uint128::new(474));
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(1_329));
+    assert_eq!(
+        suite.batch_balances(DEVELOPER),
+        [(0, 474), (1, 855)],
+        "every batch bears its share"
+    );
+
+    let info = suite.carbon_credit_info();
+    assert_eq!(info.total_credits_issued, Uint128::new(1_803));
+    assert_eq!(info.credits_available, Uint128::new(1_803));
+    assert_eq!(info.credits_buffered, Uint128::zero());
+    assert!(suite.reconcile_supply().reconciled);
+    assert!(suite.buffer_pool().batches.is_empty());
+
+    let err = suite.report_reversal(VERIFIER, "rev-3", 2_000).unwrap_err();
+    assert_eq!(
+        ContractError::ReversalExceedsCredits {
+            requested: Uint128::new(2_000),
+            buffered: Uint128::zero(),
+            held: Uint128::new(1_803),
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn holders_are_cancelled_a_page_at_a_time() {
+    let mut suite = SuiteBuilder::new().build();
+    let holders: Vec<String> = (0..40)
+        .map(|holder| format!("holder-{holder:02}"))
+        .collect();
+    let recipients: Vec<(&str, u128)> =
+        holders.iter().map(|holder| (holder.as_str(), 10)).collect();
+    suite
+        .verify_credits_to(VERIFIER, "v-1", &recipients)
+        .unwrap();
+
+    // The first page is cancelled with the report
+    suite.report_reversal(VERIFIER, "rev-1", 200).unwrap();
+    let reversal = suite.reversal_record("rev-1");
+    assert_eq!(reversal.holder_credits, Uint128::new(200));
+    assert!(reversal.in_progress);
+    assert_eq!(suite.balance("holder-29"), Uint128::new(5));
+    assert_eq!(suite.balance("holder-30"), Uint128::new(10));
+    assert_eq!(
+        suite.carbon_credit_info().credits_available,
+        Uint128::new(250)
+    );
+
+    // Balances stay put until the last holder is covered
+    let err = suite.transfer("holder-30", "holder-00", 10).unwrap_err();
+    assert_eq!(
+        ContractError::ReversalInProgress {
+            reversal_id: "rev-1".to_string()
+        },
+        err.downcast().unwrap()
+    );
+    let err = suite.report_reversal(VERIFIER, "rev-2", 10).unwrap_err();
+    assert_eq!(
+        ContractError::ReversalInProgress {
+            reversal_id: "rev-1".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    // Anyone can work through the rest
+    suite.continue_reversal("anyone").unwrap();
+    assert!(!suite.reversal_record("rev-1").in_progress);
+    assert_eq!(suite.balance("holder-39"), Uint128::new(5));
+    assert_eq!(
+        suite.reversal_cancellations("rev-1")[0],
+        allocation("holder-00", 5)
+    );
+    let info = suite.carbon_credit_info();
+    assert_eq!(info.total_credits_issued, Uint128::new(200));
+    assert_eq!(info.credits_available, Uint128::new(200));
+    assert!(suite.reconcile_supply().reconciled);
+
+    let err = suite.continue_reversal("anyone").unwrap_err();
+    assert_eq!(
+        ContractError::NoReversalInProgress {},
+        err.downcast().unwrap()
+    );
+    suite.transfer("holder-30", "holder-00", 5).unwrap();
+}
+
+#[test]
+fn expiry_cancels_what_the_pool_still_holds_of_the_batch() {
+    let mut suite = SuiteBuilder::new().build();
+    suite
+        .set_buffer_rate(ADMIN, FOREST, Decimal::percent(10))
+        .unwrap();
+    suite.verify_credits(VERIFIER, "v-1", 1_000).unwrap();
+    suite.verify_credits(VERIFIER, "v-2", 500).unwrap();
+
+    // The oldest batch's buffer credits go first
+    suite.report_reversal(VERIFIER, "rev-1", 120).unwrap();
+    assert_eq!(suite.buffer_pool().batches, [held(2, 30)]);
+
+    suite
+        .update_verification_status(VERIFIER, "v-2", VerificationStatus::Expired)
+        .unwrap();
+    assert!(suite.buffer_pool().batches.is_empty());
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(900));
+
+    let info = suite.carbon_credit_info();
+    assert_eq!(info.total_credits_issued, Uint128::new(900));
+    assert_eq!(info.credits_buffered, Uint128::zero());
+    assert!(suite.reconcile_supply().reconciled);
+}
+
+#[test]
+fn reversals_cover_the_base_batch_holders_kept_through_a_migration() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    suite.verify_credits(VERIFIER, "v-1", 1_000).unwrap();
+    suite.transfer(DEVELOPER, "buyer", 500).unwrap();
+
+    suite.migrate(ADMIN).unwrap();
+    suite.report_reversal(VERIFIER, "rev-1", 200).unwrap();
+    assert_eq!(
+        suite.reversal_cancellations("rev-1"),
+        [allocation("buyer", 50), allocation(DEVELOPER, 150)]
+    );
+    assert_eq!(suite.batch_balances("buyer"), [(0, 450)]);
+    assert_eq!(suite.batch_balances(DEVELOPER), [(0, 450), (1, 900)]);
+    assert!(suite.reconcile_supply().reconciled);
+}
@@ -0,0 +1,122 @@
+//! Retirement certificates: one soulbound CW721 token per retirement, minted
+//! into the collection the contract instantiates, and resolvable back to the
+//! retirement it attests.
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use retirement_certificate::msg::{CertificateMetadata, ExecuteMsg as CertificateExecuteMsg};
+
+use super::suite::{Suite, SuiteBuilder, DEVELOPER, VERIFIER};
+
+/// 100 credits of the project's 2023 vintage from instantiation, then 300 of
+/// vintage 2021, all held by the developer.
+fn setup() -> Suite {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 100).build();
+    suite.verify_vintage(VERIFIER, "v-1", 300, 2021).unwrap();
+    suite
+}
+
+#[test]
+fn retirements_mint_a_certificate_to_the_retiring_account() {
+    let mut suite = setup();
+
+    suite.retire_credits(DEVELOPER, "r-1", 150).unwrap();
+    let (owner, info) = suite.certificate("r-1");
+    assert_eq!(owner, DEVELOPER);
+    assert_eq!(
+        info.token_uri.as_deref(),
+        Some("https://registry.example/r-1")
+    );
+    assert_eq!(
+        info.extension,
+        CertificateMetadata {
+            retirement_id: "r-1".to_string(),
+            project_id: "VCS-674".to_string(),
+            vintage_years: vec![2021, 2023],
+            credits: Uint128::new(150),
+            tco2e: Decimal::from_ratio(150u128, 1u128),
+            beneficiary: DEVELOPER.to_string(),
+            retirement_purpose: "2024 scope 1 offset".to_string(),
+        }
+    );
+}
+
+#[test]
+fn certificates_go_to_the_beneficiary_under_its_name() {
+    let mut suite = setup();
+    suite.increase_allowance(DEVELOPER, "broker", 100).unwrap();
+
+    suite
+        .retire_for(DEVELOPER, "r-1", 10, "acme", Some("Acme Corp"))
+        .unwrap();
+    let (owner, info) = suite.certificate("r-1");
+    assert_eq!(owner, "acme");
+    assert_eq!(info.extension.beneficiary, "Acme Corp");
+
+    // Without a name the beneficiary's address is shown
+    suite
+        .retire_credits_from("broker", DEVELOPER, "r-2", 10, Some("acme"))
+        .unwrap();
+    let (owner, info) = suite.certificate("r-2");
+    assert_eq!(owner, "acme");
+    assert_eq!(info.extension.beneficiary, "acme");
+
+    // And without a beneficiary the certificate is the retiring account's
+    suite
+        .retire_credits_from("broker", DEVELOPER, "r-3", 10, None)
+        .unwrap();
+    assert_eq!(suite.certificate("r-3").0, DEVELOPER);
+}
+
+#[test]
+fn certificates_cannot_be_transferred() {
+    let mut suite = setup();
+    suite.retire_credits(DEVELOPER, "r-1", 10).unwrap();
+
+    let collection = suite.certificate_collection().unwrap();
+    let err = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(DEVELOPER),
+            collection,
+            &CertificateExecuteMsg::TransferNft {
+                recipient: "buyer".to_string(),
+                token_id: "r-1".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        retirement_certificate::ContractError::Soulbound {},
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.certificate("r-1").0, DEVELOPER);
+}
+
+#[test]
+fn certificates_resolve_to_their_retirement() {
+    let mut suite = setup();
+    suite.retire_credits(DEVELOPER, "r-1", 10).unwrap();
+
+    let record = suite.certificate_retirement("r-1").unwrap();
+    assert_eq!(record, suite.retirement_record("r-1"));
+    assert_eq!(
+        record.certificate_collection,
+        suite.certificate_collection()
+    );
+
+    suite.certificate_retirement("r-2").unwrap_err();
+}
+
+#[test]
+fn without_a_collection_retirements_mint_no_certificate() {
+    let mut suite = SuiteBuilder::new()
+        .with_credits(DEVELOPER, 100)
+        .without_certificates()
+        .build();
+    assert_eq!(suite.certificate_collection(), None);
+
+    suite.retire_credits(DEVELOPER, "r-1", 10).unwrap();
+    assert_eq!(suite.retirement_record("r-1").certificate_collection, None);
+    suite.certificate_retirement("r-1").unwrap_err();
+}
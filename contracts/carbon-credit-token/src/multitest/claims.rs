@@ -0,0 +1,130 @@
+//! Offset claims: retirements summed per claimant over a reporting period.
+
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+
+use super::suite::{Suite, SuiteBuilder, ADMIN, DEVELOPER};
+use crate::msg::{PurposeTotals, RetirementTotals};
+use crate::state::{CarbonCreditInfo, ProjectType};
+
+const KATINGAN: &str = "VCS-1477";
+const JAN_2024: Timestamp = Timestamp::from_seconds(1_704_067_200);
+const JAN_2025: Timestamp = Timestamp::from_seconds(1_735_689_600);
+
+fn totals(retirements: u32, credits_retired: u128, co2_equivalent: u128) -> RetirementTotals {
+    RetirementTotals {
+        retirements,
+        credits_retired: Uint128::new(credits_retired),
+        co2_equivalent: Decimal::from_ratio(co2_equivalent, 1u128),
+    }
+}
+
+/// The developer holds 1000 of the contract's credits and 200 of a registered
+/// project rated at 1.5 tCO2e per credit; acme holds 100 of its own.
+fn setup() -> Suite {
+    let mut suite = SuiteBuilder::new()
+        .with_credits(DEVELOPER, 1_000)
+        .with_credits("acme", 100)
+        .build();
+    suite
+        .register_project(
+            ADMIN,
+            CarbonCreditInfo {
+                project_id: KATINGAN.to_string(),
+                project_name: "Katingan Mentaya".to_string(),
+                project_type: ProjectType::ForestConservation,
+                verification_standard: "VCS".to_string(),
+                vintage_year: 2021,
+                country: "Indonesia".to_string(),
+                total_credits_issued: Uint128::zero(),
+                credits_retired: Uint128::zero(),
+                credits_available: Uint128::zero(),
+                co2_equivalent_per_credit: Decimal::percent(150),
+                credits_buffered: Uint128::zero(),
+                verification_body: Addr::unchecked("katingan-vvb"),
+                project_developer: Addr::unchecked(DEVELOPER),
+            },
+        )
+        .unwrap();
+    suite
+        .verify_project_credits("katingan-vvb", KATINGAN, "v-1", 200)
+        .unwrap();
+    suite
+}
+
+#[test]
+fn retirements_are_summed_per_claimant_over_a_period() {
+    let mut suite = setup();
+
+    suite.set_block_time(JAN_2024.minus_seconds(1));
+    suite
+        .retire_for_purpose(DEVELOPER, "r-1", 10, "2023 scope 1 offset", None)
+        .unwrap();
+
+    suite.set_block_time(JAN_2024.plus_days(60));
+    suite
+        .retire_for_purpose(DEVELOPER, "r-2", 100, "Scope 3 business travel", None)
+        .unwrap();
+    // Retired by the developer, claimed by acme
+    suite
+        .retire_for_purpose(DEVELOPER, "r-3", 40, "Scope 1", Some("acme"))
+        .unwrap();
+    suite
+        .retire_for_purpose("acme", "r-4", 25, "Scope 1", None)
+        .unwrap();
+
+    suite.set_block_time(JAN_2024.plus_days(150));
+    suite
+        .retire_project_credits(DEVELOPER, KATINGAN, "r-5", 20)
+        .unwrap();
+
+    // The period ends before its `to`
+    suite.set_block_time(JAN_2025);
+    suite
+        .retire_for_purpose(DEVELOPER, "r-6", 5, "2025 scope 1 offset", None)
+        .unwrap();
+
+    let summary = suite.retirement_summary(DEVELOPER, JAN_2024, JAN_2025);
+    assert_eq!(summary.entity, Addr::unchecked(DEVELOPER));
+    assert_eq!(summary.total, totals(2, 120, 130));
+    assert_eq!(
+        summary.by_purpose,
+        [
+            PurposeTotals {
+                retirement_purpose: "Carbon offset".to_string(),
+                totals: totals(1, 20, 30),
+            },
+            PurposeTotals {
+                retirement_purpose: "Scope 3 business travel".to_string(),
+                totals: totals(1, 100, 100),
+            },
+        ]
+    );
+
+    let summary = suite.retirement_summary("acme", JAN_2024, JAN_2025);
+    assert_eq!(summary.total, totals(2, 65, 65));
+    assert_eq!(
+        summary.by_purpose,
+        [PurposeTotals {
+            retirement_purpose: "Scope 1".to_string(),
+            totals: totals(2, 65, 65),
+        }]
+    );
+
+    let summary = suite.retirement_summary("nobody", JAN_2024, JAN_2025);
+    assert_eq!(summary.total, RetirementTotals::default());
+    assert!(summary.by_purpose.is_empty());
+}
+
+#[test]
+fn migrate_reindexes_retirements_without_counting_them_twice() {
+    let mut suite = setup();
+    suite.set_block_time(JAN_2024.plus_days(1));
+    suite.retire_credits(DEVELOPER, "r-1", 100).unwrap();
+    suite
+        .retire_project_credits(DEVELOPER, KATINGAN, "r-2", 10)
+        .unwrap();
+
+    suite.migrate(ADMIN).unwrap();
+    let summary = suite.retirement_summary(DEVELOPER, JAN_2024, JAN_2025);
+    assert_eq!(summary.total, totals(2, 110, 115));
+}
@@ -0,0 +1,198 @@
+//! Vintage expiry: credits of vintages older than the policy's window kept
+//! from compliance retirements, and from transfers if the policy says so.
+
+use cosmwasm_std::{Event, Timestamp, Uint128};
+use rwa_common::CommonError;
+
+use super::suite::{Suite, SuiteBuilder, ADMIN, DEVELOPER, VERIFIER};
+use crate::error::ContractError;
+use crate::msg::{ReceiveMsg, RetireMsg, SerialState};
+use crate::state::{
+    AuthorizationStatus, CreditUse, VerificationStatus, VintagePolicy, HOST_COUNTRY_AUTHORITY,
+};
+
+const JAN_2022: Timestamp = Timestamp::from_seconds(1_640_995_200);
+const JAN_2025: Timestamp = Timestamp::from_seconds(1_735_689_600);
+
+/// The developer holds batch 1 of 100 credits of the 2016 vintage and batch 2
+/// of 100 of the 2022 vintage, both authorized for NDC use.
+fn setup() -> Suite {
+    let mut suite = SuiteBuilder::new().build();
+    suite.verify_vintage(VERIFIER, "v-1", 100, 2016).unwrap();
+    suite.verify_vintage(VERIFIER, "v-2", 100, 2022).unwrap();
+    suite
+        .grant_role(ADMIN, HOST_COUNTRY_AUTHORITY, "host-authority")
+        .unwrap();
+    for batch_id in [1, 2] {
+        suite
+            .set_batch_authorization(
+                "host-authority",
+                batch_id,
+                AuthorizationStatus::Authorized,
+                &[CreditUse::Ndc],
+            )
+            .unwrap();
+    }
+    suite
+}
+
+#[test]
+fn compliance_retirements_need_an_unexpired_vintage() {
+    let mut suite = setup();
+
+    let err = suite.set_vintage_policy(DEVELOPER, 5, false).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "set the vintage policy".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+    suite.set_vintage_policy(ADMIN, 5, false).unwrap();
+    assert_eq!(
+        suite.vintage_policy(),
+        Some(VintagePolicy {
+            validity_years: 5,
+            block_transfers: false,
+        })
+    );
+
+    // The 2016 vintage stays valid through 2021
+    suite.set_block_time(JAN_2022.minus_seconds(1));
+    suite
+        .retire_for_use(DEVELOPER, "r-1", 10, Some(1), CreditUse::Ndc)
+        .unwrap();
+
+    suite.set_block_time(JAN_2022);
+    let err = suite
+        .retire_for_use(DEVELOPER, "r-2", 10, None, CreditUse::Ndc)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::VintageExpired {
+            batch_id: 1,
+            vintage_year: 2016,
+        },
+        err.downcast().unwrap()
+    );
+    suite
+        .retire_for_use(DEVELOPER, "r-2", 10, Some(2), CreditUse::Ndc)
+        .unwrap();
+
+    // Expired credits can still be retired for voluntary use, and moved
+    suite
+        .retire_for_use(DEVELOPER, "r-3", 10, Some(1), CreditUse::Voluntary)
+        .unwrap();
+    suite.transfer(DEVELOPER, "buyer", 30).unwrap();
+    assert_eq!(suite.batch_balances("buyer"), [(1, 30)]);
+}
+
+#[test]
+fn transfers_pass_over_expired_vintages_when_blocked() {
+    let mut suite = setup();
+    suite.set_vintage_policy(ADMIN, 5, true).unwrap();
+    suite.set_block_time(JAN_2025);
+
+    let err = suite.transfer(DEVELOPER, "buyer", 150).unwrap_err();
+    assert_eq!(
+        ContractError::ExpiredCreditsNotTransferable {
+            requested: Uint128::new(150),
+            transferable: Uint128::new(100),
+        },
+        err.downcast().unwrap()
+    );
+    suite.transfer(DEVELOPER, "buyer", 60).unwrap();
+    assert_eq!(suite.batch_balances("buyer"), [(2, 60)]);
+    assert_eq!(suite.batch_balances(DEVELOPER), [(1, 100), (2, 40)]);
+
+    let err = suite.transfer_batch(DEVELOPER, 1, "buyer", 10).unwrap_err();
+    assert_eq!(
+        ContractError::VintageExpired {
+            batch_id: 1,
+            vintage_year: 2016,
+        },
+        err.downcast().unwrap()
+    );
+
+    // Sending credits to be retired is not a transfer
+    let contract = suite.contract.clone();
+    suite
+        .send(
+            DEVELOPER,
+            &contract,
+            50,
+            &ReceiveMsg::Retire(RetireMsg {
+                retirement_id: Some("r-1".to_string()),
+                retirement_purpose: "2024 scope 1 offset".to_string(),
+                retirement_certificate_url: "https://registry.example/r-1".to_string(),
+                beneficiary: None,
+                beneficiary_name: None,
+                retirement_use: None,
+            }),
+        )
+        .unwrap();
+    assert_eq!(suite.batch_balances(DEVELOPER), [(1, 50), (2, 40)]);
+    assert!(suite.reconcile_supply().reconciled);
+
+    // Transferring credits to the contract without retiring them is a transfer
+    suite.transfer(DEVELOPER, contract.as_str(), 30).unwrap();
+    assert_eq!(suite.batch_balances(contract.as_str()), [(2, 30)]);
+}
+
+#[test]
+fn anyone_can_sweep_expired_vintages() {
+    let mut suite = setup();
+    suite.set_block_time(JAN_2025);
+
+    // Without a policy nothing expires
+    suite.sweep_expired("anyone", None, None).unwrap();
+    assert_eq!(suite.verification_record("v-1").vintage_expired_at, None);
+
+    // A page at a time, from the oldest batch
+    suite.set_vintage_policy(ADMIN, 5, false).unwrap();
+    let res = suite.sweep_expired("anyone", None, Some(1)).unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm")
+            .add_attribute("records_expired", "1")
+            .add_attribute("last_batch_id", "1")
+    ));
+    let record = suite.verification_record("v-1");
+    assert_eq!(record.vintage_expired_at, Some(JAN_2022));
+    suite.sweep_expired("anyone", Some(1), None).unwrap();
+    assert_eq!(suite.verification_record("v-2").vintage_expired_at, None);
+
+    // The sweep changes neither the status nor the credits
+    assert_eq!(record.status, VerificationStatus::Verified);
+    assert_eq!(record.history.len(), 1);
+    assert_eq!(suite.serial_status(1, None).state, SerialState::Held);
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(200));
+    assert!(suite.reconcile_supply().reconciled);
+
+    // Records already swept are left alone, and the verification body can
+    // still expire them
+    suite.sweep_expired("anyone", None, None).unwrap();
+    assert_eq!(suite.verification_record("v-1"), record);
+    suite
+        .update_verification_status(VERIFIER, "v-1", VerificationStatus::Expired)
+        .unwrap();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(100));
+    assert!(suite.reconcile_supply().reconciled);
+}
+
+#[test]
+fn serial_status_shows_when_a_swept_vintage_expired() {
+    let mut suite = setup();
+    suite.set_vintage_policy(ADMIN, 5, false).unwrap();
+    suite.set_block_time(JAN_2025);
+    assert_eq!(suite.serial_status(1, None).vintage_expired_at, None);
+
+    suite.sweep_expired("anyone", None, None).unwrap();
+    let status = suite.serial_status(1, None);
+    assert_eq!(status.state, SerialState::Held);
+    assert_eq!(status.vintage_expired_at, Some(JAN_2022));
+    assert_eq!(suite.serial_status(101, None).vintage_expired_at, None);
+
+    // Retired serials keep showing when their vintage expired
+    suite.retire_credits(DEVELOPER, "r-1", 10).unwrap();
+    let status = suite.serial_status(1, None);
+    assert!(matches!(status.state, SerialState::Retired { .. }));
+    assert_eq!(status.vintage_expired_at, Some(JAN_2022));
+}
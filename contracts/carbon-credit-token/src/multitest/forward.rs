@@ -0,0 +1,171 @@
+//! Forward issuance: units sold ahead of verification, converted into credits
+//! as the monitoring periods they were issued against are verified.
+
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+
+use super::suite::{Suite, SuiteBuilder, DEVELOPER, VERIFIER};
+use crate::error::ContractError;
+use crate::state::ForwardPosition;
+
+const END_2025: Timestamp = Timestamp::from_seconds(1_735_689_600);
+const END_2026: Timestamp = Timestamp::from_seconds(1_767_225_600);
+
+fn position(forward: u128, delivered: u128, shortfall: u128) -> ForwardPosition {
+    ForwardPosition {
+        forward: Uint128::new(forward),
+        delivered: Uint128::new(delivered),
+        shortfall: Uint128::new(shortfall),
+    }
+}
+
+/// Forward series 1 of two 500 credit periods; the developer sold 600 units to
+/// buyer-a and 300 to buyer-b and kept 100.
+fn setup() -> Suite {
+    let mut suite = SuiteBuilder::new().build();
+    suite
+        .issue_forward(DEVELOPER, &[(END_2025, 500), (END_2026, 500)], None)
+        .unwrap();
+    suite
+        .transfer_forward(DEVELOPER, 1, "buyer-a", 600)
+        .unwrap();
+    suite
+        .transfer_forward(DEVELOPER, 1, "buyer-b", 300)
+        .unwrap();
+    suite
+}
+
+#[test]
+fn forward_units_are_issued_by_the_developer_and_are_not_credits() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite
+        .issue_forward(VERIFIER, &[(END_2025, 500)], None)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotProjectDeveloper {
+            action: "issue forward units".to_string()
+        },
+        err.downcast().unwrap()
+    );
+    let err = suite.issue_forward(DEVELOPER, &[], None).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMonitoringSchedule {
+            reason: "it has no periods".to_string()
+        },
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .issue_forward(DEVELOPER, &[(END_2026, 500), (END_2025, 500)], None)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMonitoringSchedule {
+            reason: format!(
+                "the period ending {END_2025} does not follow the one ending {END_2026}"
+            )
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .issue_forward(
+            DEVELOPER,
+            &[(END_2025, 500), (END_2026, 500)],
+            Some(&[("buyer-a", 600), (DEVELOPER, 400)]),
+        )
+        .unwrap();
+    let series = suite.forward_series(1);
+    assert_eq!(series.issued_by, Addr::unchecked(DEVELOPER));
+    assert_eq!(series.units_issued, Uint128::new(1_000));
+    assert_eq!(series.units_outstanding, Uint128::new(1_000));
+    assert_eq!(suite.forward_position(1, "buyer-a"), (600, 0, 0));
+
+    // Forward units move between holders, but are neither tokens nor credits
+    suite
+        .transfer_forward("buyer-a", 1, "buyer-b", 200)
+        .unwrap();
+    let err = suite
+        .transfer_forward("buyer-a", 1, "buyer-b", 401)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientForwardUnits {
+            series_id: 1,
+            requested: Uint128::new(401),
+            available: Uint128::new(400),
+        },
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.forward_position(1, "buyer-b"), (200, 0, 0));
+    assert_eq!(suite.balance("buyer-b"), Uint128::zero());
+    assert_eq!(suite.total_supply(), Uint128::zero());
+    assert_eq!(suite.available_credits(), Uint128::zero());
+    assert!(suite.reconcile_supply().reconciled);
+}
+
+#[test]
+fn verifications_convert_forward_units_one_for_one() {
+    let mut suite = setup();
+
+    // The first period's 500 units convert in proportion to the units held
+    suite.verify_forward(VERIFIER, "v-1", 500, 1).unwrap();
+    assert_eq!(suite.balance("buyer-a"), Uint128::new(300));
+    assert_eq!(suite.balance("buyer-b"), Uint128::new(150));
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(50));
+    assert_eq!(suite.forward_position(1, "buyer-a"), (300, 300, 0));
+    let series = suite.forward_series(1);
+    assert_eq!(series.units_outstanding, Uint128::new(500));
+    assert_eq!(series.periods[0].verification_id.as_deref(), Some("v-1"));
+    assert_eq!(suite.verification_record("v-1").forward_series, Some(1));
+
+    // Credits beyond what the period expected go to the developer
+    suite.verify_forward(VERIFIER, "v-2", 600, 1).unwrap();
+    assert_eq!(suite.balance("buyer-a"), Uint128::new(600));
+    assert_eq!(suite.balance("buyer-b"), Uint128::new(300));
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(200));
+    assert_eq!(
+        suite.forward_positions(1),
+        [
+            (Addr::unchecked("buyer-a"), position(0, 600, 0)),
+            (Addr::unchecked("buyer-b"), position(0, 300, 0)),
+            (Addr::unchecked(DEVELOPER), position(0, 100, 0)),
+        ]
+    );
+    assert_eq!(suite.available_credits(), Uint128::new(1_100));
+    assert!(suite.reconcile_supply().reconciled);
+
+    // Delivered credits are retirable like any other
+    suite.retire_credits("buyer-a", "r-1", 600).unwrap();
+
+    let err = suite.verify_forward(VERIFIER, "v-3", 100, 1).unwrap_err();
+    assert_eq!(
+        ContractError::ForwardSeriesSettled { series_id: 1 },
+        err.downcast().unwrap()
+    );
+    let err = suite.verify_forward(VERIFIER, "v-3", 100, 2).unwrap_err();
+    assert_eq!(
+        ContractError::ForwardSeriesNotFound { series_id: 2 },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn a_shortfall_is_shared_pro_rata() {
+    let mut suite = setup();
+
+    // 401 of the 500 expected: 240.6 to buyer-a, 120.3 to buyer-b and 40.1 to
+    // the developer, the remaining credit going to the first one rounded
+    suite.verify_forward(VERIFIER, "v-1", 401, 1).unwrap();
+    assert_eq!(suite.forward_position(1, "buyer-a"), (300, 241, 59));
+    assert_eq!(suite.forward_position(1, "buyer-b"), (150, 120, 30));
+    assert_eq!(suite.forward_position(1, DEVELOPER), (50, 40, 10));
+    assert_eq!(suite.total_supply(), Uint128::new(401));
+
+    let series = suite.forward_series(1);
+    assert_eq!(series.units_outstanding, Uint128::new(500));
+    assert_eq!(series.credits_delivered, Uint128::new(401));
+    assert_eq!(series.periods[0].credits_delivered, Uint128::new(401));
+
+    // The shortfall does not carry over to the next period
+    suite.verify_forward(VERIFIER, "v-2", 500, 1).unwrap();
+    assert_eq!(suite.forward_position(1, "buyer-a"), (0, 541, 59));
+    assert_eq!(suite.balance("buyer-a"), Uint128::new(541));
+}
@@ -0,0 +1,164 @@
+//! Property tests: random sequences of operations run against the contract
+//! and against a plain model of its counters, which must agree after every
+//! step, including on the drift reported by `ReconcileSupply`.
+
+use proptest::prelude::*;
+
+use super::suite::{Suite, SuiteBuilder, DEVELOPER, VERIFIER};
+
+const HOLDERS: [&str; 3] = [DEVELOPER, "buyer", "broker"];
+const INITIAL_CREDITS: u128 = 5_000;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Verify {
+        holder: usize,
+        credits: u128,
+    },
+    Retire {
+        holder: usize,
+        credits: u128,
+    },
+    Transfer {
+        from: usize,
+        to: usize,
+        amount: u128,
+    },
+    Burn {
+        holder: usize,
+        amount: u128,
+    },
+    Mint {
+        holder: usize,
+        amount: u128,
+    },
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let holder = 0..HOLDERS.len();
+    prop_oneof![
+        (holder.clone(), 1..1_000u128).prop_map(|(holder, credits)| Op::Verify { holder, credits }),
+        (holder.clone(), 1..2_000u128).prop_map(|(holder, credits)| Op::Retire { holder, credits }),
+        (holder.clone(), holder.clone(), 1..2_000u128)
+            .prop_map(|(from, to, amount)| Op::Transfer { from, to, amount }),
+        (holder.clone(), 1..500u128).prop_map(|(holder, amount)| Op::Burn { holder, amount }),
+        (holder, 1..500u128).prop_map(|(holder, amount)| Op::Mint { holder, amount }),
+    ]
+}
+
+/// What the contract should hold after the operations it accepted.
+#[derive(Debug)]
+struct Model {
+    balances: [u128; 3],
+    supply: u128,
+    issued: u128,
+    available: u128,
+    retired: u128,
+}
+
+impl Model {
+    fn new() -> Self {
+        Model {
+            balances: [INITIAL_CREDITS, 0, 0],
+            supply: INITIAL_CREDITS,
+            issued: INITIAL_CREDITS,
+            available: INITIAL_CREDITS,
+            retired: 0,
+        }
+    }
+
+    /// Applies `op` and returns whether the contract is expected to accept it.
+    fn apply(&mut self, op: &Op) -> bool {
+        match *op {
+            Op::Verify { holder, credits } => {
+                self.balances[holder] += credits;
+                self.supply += credits;
+                self.issued += credits;
+                self.available += credits;
+                true
+            }
+            Op::Retire { holder, credits } => {
+                if self.balances[holder] < credits || self.available < credits {
+                    return false;
+                }
+                self.balances[holder] -= credits;
+                self.supply -= credits;
+                self.available -= credits;
+                self.retired += credits;
+                true
+            }
+            Op::Transfer { from, to, amount } => {
+                if self.balances[from] < amount {
+                    return false;
+                }
+                self.balances[from] -= amount;
+                self.balances[to] += amount;
+                true
+            }
+            // Credits are only issued through verification, and only leave
+            // supply through a retirement
+            Op::Burn { .. } | Op::Mint { .. } => false,
+        }
+    }
+}
+
+fn execute(suite: &mut Suite, step: usize, op: &Op) -> bool {
+    let res = match *op {
+        Op::Verify { holder: 0, credits } => {
+            suite.verify_credits(VERIFIER, &format!("v-{step}"), credits)
+        }
+        Op::Verify { holder, credits } => suite.verify_credits_to(
+            VERIFIER,
+            &format!("v-{step}"),
+            &[(HOLDERS[holder], credits)],
+        ),
+        Op::Retire { holder, credits } => {
+            suite.retire_credits(HOLDERS[holder], &format!("r-{step}"), credits)
+        }
+        Op::Transfer { from, to, amount } => suite.transfer(HOLDERS[from], HOLDERS[to], amount),
+        Op::Burn { holder, amount } => suite.burn(HOLDERS[holder], amount),
+        Op::Mint { holder, amount } => suite.mint(DEVELOPER, HOLDERS[holder], amount),
+    };
+    res.is_ok()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn counters_follow_the_model(ops in prop::collection::vec(op(), 1..40)) {
+        let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, INITIAL_CREDITS).build();
+        let mut model = Model::new();
+
+        for (step, op) in ops.iter().enumerate() {
+            let expected = model.apply(op);
+            prop_assert_eq!(execute(&mut suite, step, op), expected, "{:?}", op);
+
+            let info = suite.carbon_credit_info();
+            prop_assert_eq!(info.total_credits_issued.u128(), model.issued);
+            prop_assert_eq!(info.credits_available.u128(), model.available);
+            prop_assert_eq!(info.credits_retired.u128(), model.retired);
+            prop_assert_eq!(suite.total_supply().u128(), model.supply);
+            for (holder, balance) in HOLDERS.iter().zip(model.balances) {
+                prop_assert_eq!(suite.balance(holder).u128(), balance);
+
+                // Every credit held belongs to exactly one batch
+                let batched: u128 = suite
+                    .batch_balances(holder)
+                    .iter()
+                    .map(|(_, credits)| credits)
+                    .sum();
+                prop_assert_eq!(batched, balance);
+            }
+
+            // Issued credits are conserved, and every available credit is
+            // backed by a token
+            prop_assert_eq!(model.issued, model.available + model.retired);
+            prop_assert_eq!(model.available, model.supply);
+            let report = suite.reconcile_supply();
+            prop_assert!(report.issuance.is_zero());
+            prop_assert!(report.supply.is_zero());
+            prop_assert!(report.reconciled);
+        }
+    }
+}
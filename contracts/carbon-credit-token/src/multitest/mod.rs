@@ -0,0 +1,15 @@
+mod authorization;
+mod batches;
+mod buffer;
+mod certificates;
+mod claims;
+mod expiry;
+mod forward;
+mod invariants;
+mod receive;
+mod registry;
+mod sanctions;
+mod serials;
+mod suite;
+mod tags;
+mod tests;
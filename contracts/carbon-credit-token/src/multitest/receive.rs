@@ -0,0 +1,211 @@
+//! The CW20 receive hook: credits sent to the contract with a `Retire`
+//! payload are retired in the same transaction, and credits of an approved
+//! carbon credit contract are forwarded there to be retired.
+
+use cosmwasm_std::{to_json_binary, Addr, Uint128};
+use cw20::BalanceResponse;
+use cw_multi_test::Executor;
+use rwa_common::{Cw20ExecuteMsg, Cw20QueryMsg};
+
+use rwa_common::CommonError;
+
+use super::suite::{Suite, SuiteBuilder, ADMIN, DEVELOPER, VERIFIER};
+use crate::error::ContractError;
+use crate::msg::{CarbonCreditQueryMsg, ExecuteMsg, QueryMsg, ReceiveMsg, RetireMsg};
+use crate::state::RetirementRecord;
+
+fn retire_msg(retirement_id: &str, beneficiary: Option<&str>) -> ReceiveMsg {
+    ReceiveMsg::Retire(RetireMsg {
+        retirement_id: Some(retirement_id.to_string()),
+        retirement_purpose: "2024 scope 1 offset".to_string(),
+        retirement_certificate_url: format!("https://registry.example/{retirement_id}"),
+        beneficiary: beneficiary.map(str::to_string),
+        beneficiary_name: Some("Acme Corp".to_string()),
+        retirement_use: None,
+    })
+}
+
+#[test]
+fn sent_credits_are_retired_for_the_sender() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    let contract = suite.contract.clone();
+
+    suite
+        .send(DEVELOPER, &contract, 300, &retire_msg("r-1", Some("acme")))
+        .unwrap();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(700));
+    assert_eq!(suite.balance(contract.as_str()), Uint128::zero());
+    assert_eq!(suite.total_supply(), Uint128::new(700));
+    assert_eq!(suite.retired_credits(), Uint128::new(300));
+    assert!(suite.reconcile_supply().reconciled);
+
+    let record = suite.retirement_record("r-1");
+    assert_eq!(record.credits_retired, Uint128::new(300));
+    assert_eq!(record.retirement_entity, Addr::unchecked(DEVELOPER));
+    assert_eq!(record.beneficiary, Some(Addr::unchecked("acme")));
+    assert_eq!(record.beneficiary_name.as_deref(), Some("Acme Corp"));
+
+    let (owner, info) = suite.certificate("r-1");
+    assert_eq!(owner, "acme");
+    assert_eq!(info.extension.beneficiary, "Acme Corp");
+}
+
+#[test]
+fn only_the_sent_batches_are_retired() {
+    let mut suite = SuiteBuilder::new().build();
+    suite.verify_credits(VERIFIER, "v-1", 100).unwrap();
+    suite.verify_credits(VERIFIER, "v-2", 100).unwrap();
+    let contract = suite.contract.clone();
+
+    // Batch 1 is transferred to the contract rather than sent to be retired
+    suite.transfer(DEVELOPER, contract.as_str(), 100).unwrap();
+    suite
+        .send(DEVELOPER, &contract, 60, &retire_msg("r-1", None))
+        .unwrap();
+
+    let record = suite.retirement_record("r-1");
+    assert_eq!(record.batches.len(), 1);
+    assert_eq!(record.batches[0].batch_id, 2);
+    assert_eq!(suite.batch_balances(contract.as_str()), [(1, 100)]);
+    assert_eq!(suite.batch_balances(DEVELOPER), [(2, 40)]);
+    assert!(suite.reconcile_supply().reconciled);
+}
+
+#[test]
+fn a_send_without_a_retire_payload_is_rejected() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    let contract = suite.contract.clone();
+
+    suite
+        .execute(
+            DEVELOPER,
+            ExecuteMsg::Cw20(Cw20ExecuteMsg::Send {
+                contract: contract.to_string(),
+                amount: Uint128::new(100),
+                msg: to_json_binary(&"offset").unwrap(),
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(1_000));
+    assert_eq!(suite.retired_credits(), Uint128::zero());
+}
+
+/// Instantiates a second carbon credit contract, for another project, in the
+/// suite's app.
+fn other_project(suite: &mut Suite) -> Addr {
+    let msg = SuiteBuilder::new()
+        .with_credits(DEVELOPER, 500)
+        .instantiate_msg();
+    suite
+        .app
+        .instantiate_contract(
+            suite.code_id,
+            Addr::unchecked(ADMIN),
+            &msg,
+            &[],
+            "other-carbon-credit-token",
+            None,
+        )
+        .unwrap()
+}
+
+#[test]
+fn credits_of_another_project_are_forwarded_to_it() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    let forwarder = suite.contract.clone();
+    let other = other_project(&mut suite);
+    suite.approve_forward_token(ADMIN, &other).unwrap();
+
+    let send = ExecuteMsg::Cw20(Cw20ExecuteMsg::Send {
+        contract: forwarder.to_string(),
+        amount: Uint128::new(200),
+        msg: to_json_binary(&retire_msg("r-1", None)).unwrap(),
+    });
+    suite
+        .app
+        .execute_contract(Addr::unchecked(DEVELOPER), other.clone(), &send, &[])
+        .unwrap();
+
+    // Retired by the other project, for the account that sent the credits
+    let record: RetirementRecord = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &other,
+            &QueryMsg::from(CarbonCreditQueryMsg::RetirementRecord {
+                retirement_id: "r-1".to_string(),
+            }),
+        )
+        .unwrap();
+    assert_eq!(record.credits_retired, Uint128::new(200));
+    assert_eq!(record.retirement_entity, forwarder);
+    assert_eq!(record.beneficiary, Some(Addr::unchecked(DEVELOPER)));
+
+    for (contract, expected) in [(&other, 300), (&forwarder, 1_000)] {
+        let balance: BalanceResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(
+                contract,
+                &QueryMsg::Cw20(Cw20QueryMsg::Balance {
+                    address: DEVELOPER.to_string(),
+                }),
+            )
+            .unwrap();
+        assert_eq!(balance.balance, Uint128::new(expected));
+    }
+
+    // The forwarder's own project is untouched
+    assert_eq!(suite.retired_credits(), Uint128::zero());
+}
+
+#[test]
+fn only_approved_tokens_are_forwarded() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    let forwarder = suite.contract.clone();
+    let other = other_project(&mut suite);
+    let send = ExecuteMsg::Cw20(Cw20ExecuteMsg::Send {
+        contract: forwarder.to_string(),
+        amount: Uint128::new(200),
+        msg: to_json_binary(&retire_msg("r-1", None)).unwrap(),
+    });
+
+    // Any CW20 can call the hook, so an unapproved token is not forwarded to
+    let err = suite
+        .app
+        .execute_contract(Addr::unchecked(DEVELOPER), other.clone(), &send, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ForwardTokenNotApproved {
+            token: other.to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = suite.approve_forward_token(DEVELOPER, &other).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "approve forward tokens".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+    suite.approve_forward_token(ADMIN, &other).unwrap();
+    assert_eq!(suite.forward_tokens(), vec![other.clone()]);
+    suite
+        .app
+        .execute_contract(Addr::unchecked(DEVELOPER), other.clone(), &send, &[])
+        .unwrap();
+
+    suite.revoke_forward_token(ADMIN, &other).unwrap();
+    assert!(suite.forward_tokens().is_empty());
+    let err = suite
+        .app
+        .execute_contract(Addr::unchecked(DEVELOPER), other.clone(), &send, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ForwardTokenNotApproved {
+            token: other.to_string()
+        },
+        err.downcast().unwrap()
+    );
+}
@@ -0,0 +1,343 @@
+//! Registry mode: projects registered alongside the contract's own, each with
+//! its own parties and counters, and credits held per owner and project.
+
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use rwa_common::CommonError;
+
+use super::suite::{Suite, SuiteBuilder, ADMIN, DEVELOPER, VERIFIER};
+use crate::error::ContractError;
+use crate::state::{
+    AuthorizationStatus, CarbonCreditInfo, CreditUse, ProjectType, VerificationStatus,
+    HOST_COUNTRY_AUTHORITY,
+};
+
+const KATINGAN: &str = "VCS-1477";
+const KASIGAU: &str = "VCS-612";
+
+fn project(project_id: &str, verifier: &str, developer: &str) -> CarbonCreditInfo {
+    CarbonCreditInfo {
+        project_id: project_id.to_string(),
+        project_name: format!("Project {project_id}"),
+        project_type: ProjectType::ForestConservation,
+        verification_standard: "VCS".to_string(),
+        vintage_year: 2021,
+        country: "Kenya".to_string(),
+        total_credits_issued: Uint128::zero(),
+        credits_retired: Uint128::zero(),
+        credits_available: Uint128::zero(),
+        co2_equivalent_per_credit: Decimal::one(),
+        credits_buffered: Uint128::zero(),
+        verification_body: Addr::unchecked(verifier),
+        project_developer: Addr::unchecked(developer),
+    }
+}
+
+/// Two registered projects with their own verifiers and developers, 1000
+/// Katingan and 500 Kasigau credits issued.
+fn setup() -> Suite {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 100).build();
+    suite
+        .register_project(ADMIN, project(KATINGAN, "katingan-vvb", "katingan-dev"))
+        .unwrap();
+    suite
+        .register_project(ADMIN, project(KASIGAU, "kasigau-vvb", "kasigau-dev"))
+        .unwrap();
+    suite
+        .verify_project_credits("katingan-vvb", KATINGAN, "v-1", 1_000)
+        .unwrap();
+    suite
+        .verify_project_credits("kasigau-vvb", KASIGAU, "v-2", 500)
+        .unwrap();
+    suite
+}
+
+#[test]
+fn projects_are_registered_by_the_role_admin() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite
+        .register_project(DEVELOPER, project(KATINGAN, VERIFIER, DEVELOPER))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "register projects".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+
+    // Counters passed in are ignored
+    let mut katingan = project(KATINGAN, "katingan-vvb", "katingan-dev");
+    katingan.credits_available = Uint128::new(1_000);
+    suite.register_project(ADMIN, katingan).unwrap();
+    assert_eq!(suite.project(KATINGAN).credits_available, Uint128::zero());
+
+    // Project ids are unique, the contract's own included
+    for project_id in [KATINGAN, "VCS-674"] {
+        let err = suite
+            .register_project(ADMIN, project(project_id, VERIFIER, DEVELOPER))
+            .unwrap_err();
+        assert_eq!(
+            ContractError::ProjectExists {
+                project_id: project_id.to_string()
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    suite
+        .register_project(ADMIN, project(KASIGAU, "kasigau-vvb", "kasigau-dev"))
+        .unwrap();
+    let project_ids: Vec<_> = suite
+        .all_projects()
+        .into_iter()
+        .map(|(project_id, _)| project_id)
+        .collect();
+    assert_eq!(project_ids, [KATINGAN, KASIGAU]);
+}
+
+#[test]
+fn project_credits_are_issued_moved_and_retired_per_project() {
+    let mut suite = setup();
+
+    // Each project is verified by its own verification body only
+    let err = suite
+        .verify_project_credits(VERIFIER, KATINGAN, "v-3", 10)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotVerificationBody {
+            action: format!("verify credits of project {KATINGAN}")
+        },
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .verify_project_credits("kasigau-vvb", "VCS-0", "v-3", 10)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ProjectNotFound {
+            project_id: "VCS-0".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    assert_eq!(
+        suite.project_balance("katingan-dev", KATINGAN).u128(),
+        1_000
+    );
+    assert_eq!(suite.project_balance("katingan-dev", KASIGAU).u128(), 0);
+    assert_eq!(
+        suite.verification_record("v-1").project_id.as_deref(),
+        Some(KATINGAN)
+    );
+    // The contract's own token is untouched
+    assert_eq!(suite.total_supply(), Uint128::new(100));
+    assert_eq!(
+        suite.carbon_credit_info().total_credits_issued,
+        Uint128::new(100)
+    );
+
+    suite
+        .transfer_project_credits("katingan-dev", None, "buyer", &[(KATINGAN, 300)])
+        .unwrap();
+    suite
+        .transfer_project_credits("kasigau-dev", None, "buyer", &[(KASIGAU, 200)])
+        .unwrap();
+    let err = suite
+        .transfer_project_credits("buyer", None, "broker", &[(KATINGAN, 100), (KASIGAU, 201)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientProjectBalance {
+            project_id: KASIGAU.to_string(),
+            requested: Uint128::new(201),
+            available: Uint128::new(200),
+        },
+        err.downcast().unwrap()
+    );
+    suite
+        .transfer_project_credits("buyer", None, "broker", &[(KATINGAN, 100), (KASIGAU, 50)])
+        .unwrap();
+    assert_eq!(
+        suite.project_balances("buyer"),
+        [(KATINGAN.to_string(), 200), (KASIGAU.to_string(), 150)]
+    );
+
+    suite
+        .retire_project_credits("buyer", KATINGAN, "r-1", 60)
+        .unwrap();
+    let record = suite.retirement_record("r-1");
+    assert_eq!(record.project_id.as_deref(), Some(KATINGAN));
+    assert_eq!(record.retirement_entity, Addr::unchecked("buyer"));
+    assert_eq!(suite.project_balance("buyer", KATINGAN).u128(), 140);
+
+    let katingan = suite.project(KATINGAN);
+    assert_eq!(katingan.total_credits_issued, Uint128::new(1_000));
+    assert_eq!(katingan.credits_retired, Uint128::new(60));
+    assert_eq!(katingan.credits_available, Uint128::new(940));
+    assert_eq!(suite.project(KASIGAU).credits_retired, Uint128::zero());
+
+    // The certificate names the registered project
+    let (owner, info) = suite.certificate("r-1");
+    assert_eq!(owner, "buyer");
+    assert_eq!(info.extension.project_id, KATINGAN);
+    assert_eq!(info.extension.vintage_years, [2021]);
+
+    // Registered projects' verifications are final
+    let err = suite
+        .update_verification_status("katingan-vvb", "v-1", VerificationStatus::Expired)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ProjectRecord {
+            record_id: "v-1".to_string(),
+            project_id: KATINGAN.to_string(),
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn operators_move_all_of_an_owners_project_credits() {
+    let mut suite = setup();
+
+    let err = suite
+        .transfer_project_credits("broker", Some("katingan-dev"), "buyer", &[(KATINGAN, 10)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotProjectOperator {
+            owner: "katingan-dev".to_string(),
+            operator: "broker".to_string(),
+        },
+        err.downcast().unwrap()
+    );
+
+    suite.approve_all("katingan-dev", "broker").unwrap();
+    assert!(suite.is_approved_for_all("katingan-dev", "broker"));
+    suite
+        .transfer_project_credits("broker", Some("katingan-dev"), "buyer", &[(KATINGAN, 10)])
+        .unwrap();
+    assert_eq!(suite.project_balance("buyer", KATINGAN).u128(), 10);
+
+    suite.revoke_all("katingan-dev", "broker").unwrap();
+    assert!(!suite.is_approved_for_all("katingan-dev", "broker"));
+    suite
+        .transfer_project_credits("broker", Some("katingan-dev"), "buyer", &[(KATINGAN, 10)])
+        .unwrap_err();
+}
+
+#[test]
+fn project_issuance_withholds_the_buffer_share() {
+    let mut suite = setup();
+    suite
+        .set_buffer_rate(ADMIN, ProjectType::ForestConservation, Decimal::percent(10))
+        .unwrap();
+
+    suite
+        .verify_project_credits("katingan-vvb", KATINGAN, "v-3", 200)
+        .unwrap();
+    assert_eq!(
+        suite.verification_record("v-3").buffer_credits,
+        Uint128::new(20)
+    );
+    assert_eq!(
+        suite.project_balance("katingan-dev", KATINGAN).u128(),
+        1_180
+    );
+    let katingan = suite.project(KATINGAN);
+    assert_eq!(katingan.total_credits_issued, Uint128::new(1_200));
+    assert_eq!(katingan.credits_available, Uint128::new(1_180));
+    assert_eq!(katingan.credits_buffered, Uint128::new(20));
+}
+
+#[test]
+fn project_credits_are_retired_for_the_uses_their_project_is_authorized_for() {
+    let mut suite = setup();
+    suite
+        .grant_role(ADMIN, HOST_COUNTRY_AUTHORITY, "host-authority")
+        .unwrap();
+
+    let err = suite
+        .retire_project_credits_for_use("katingan-dev", KATINGAN, "r-1", 10, CreditUse::Ndc)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ProjectUseNotAuthorized {
+            project_id: KATINGAN.to_string(),
+            retirement_use: CreditUse::Ndc,
+        },
+        err.downcast().unwrap()
+    );
+
+    // Only the host country authority authorizes projects
+    let err = suite
+        .set_project_authorization(
+            "katingan-dev",
+            KATINGAN,
+            AuthorizationStatus::Authorized,
+            &[CreditUse::Ndc],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotHostCountryAuthority {
+            action: "authorize projects".to_string()
+        },
+        err.downcast().unwrap()
+    );
+    suite
+        .set_project_authorization(
+            "host-authority",
+            KATINGAN,
+            AuthorizationStatus::Authorized,
+            &[CreditUse::Ndc],
+        )
+        .unwrap();
+    assert_eq!(
+        suite
+            .project_authorization(KATINGAN)
+            .unwrap()
+            .authorized_uses,
+        [CreditUse::Ndc]
+    );
+    assert_eq!(suite.project_authorization(KASIGAU), None);
+
+    suite
+        .retire_project_credits_for_use("katingan-dev", KATINGAN, "r-1", 10, CreditUse::Ndc)
+        .unwrap();
+    assert_eq!(
+        suite.retirement_record("r-1").retirement_use,
+        CreditUse::Ndc
+    );
+}
+
+#[test]
+fn project_vintages_expire_like_batches() {
+    let mut suite = setup();
+    suite
+        .grant_role(ADMIN, HOST_COUNTRY_AUTHORITY, "host-authority")
+        .unwrap();
+    suite
+        .set_project_authorization(
+            "host-authority",
+            KATINGAN,
+            AuthorizationStatus::Authorized,
+            &[CreditUse::Ndc],
+        )
+        .unwrap();
+
+    // Once the 2021 vintage expires it is only retired for voluntary use, and
+    // the policy keeps it from moving
+    suite.set_vintage_policy(ADMIN, 3, true).unwrap();
+    suite.set_block_time(Timestamp::from_seconds(1_735_689_600));
+    let expired = ContractError::ProjectVintageExpired {
+        project_id: KATINGAN.to_string(),
+        vintage_year: 2021,
+    };
+    let err = suite
+        .retire_project_credits_for_use("katingan-dev", KATINGAN, "r-1", 10, CreditUse::Ndc)
+        .unwrap_err();
+    assert_eq!(expired, err.downcast().unwrap());
+    let err = suite
+        .transfer_project_credits("katingan-dev", None, "buyer", &[(KATINGAN, 10)])
+        .unwrap_err();
+    assert_eq!(expired, err.downcast().unwrap());
+    suite
+        .retire_project_credits("katingan-dev", KATINGAN, "r-1", 10)
+        .unwrap();
+    assert_eq!(suite.project(KATINGAN).credits_retired, Uint128::new(10));
+}
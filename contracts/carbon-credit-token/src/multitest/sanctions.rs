@@ -0,0 +1,206 @@
+//! Sanction screening. Multi-test does not serve the sanction module's
+//! stargate query, so these tests call the entry points directly and let
+//! `MockSanctionQuerier` answer the blacklist.
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{OwnedDeps, Response, Uint128};
+use rwa_common::sanctions::testing::{mock_dependencies_with_blacklist, MockSanctionQuerier};
+use rwa_common::{CommonError, Cw20ExecuteMsg, SanctionExecuteMsg};
+
+use super::suite::{SuiteBuilder, ADMIN, DEVELOPER, VERIFIER};
+use crate::contract::{execute, instantiate};
+use crate::error::ContractError;
+use crate::msg::{CarbonCreditExecuteMsg, ExecuteMsg, IssuanceRecipient};
+
+const MALLORY: &str = "mallory";
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockSanctionQuerier>;
+
+fn setup() -> Deps {
+    let mut deps = mock_dependencies_with_blacklist(&[MALLORY]);
+    let mut msg = SuiteBuilder::new()
+        .with_credits(DEVELOPER, 1_000)
+        .with_credits(MALLORY, 100)
+        .instantiate_msg();
+    msg.sanction_screening = None;
+    instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+    deps
+}
+
+fn execute_as(
+    deps: &mut Deps,
+    sender: &str,
+    msg: impl Into<ExecuteMsg>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        msg.into(),
+    )
+}
+
+fn transfer(recipient: &str, amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Cw20(Cw20ExecuteMsg::Transfer {
+        recipient: recipient.to_string(),
+        amount: Uint128::new(amount),
+    })
+}
+
+fn sanctioned() -> ContractError {
+    ContractError::Common(CommonError::Sanctioned {
+        address: MALLORY.to_string(),
+    })
+}
+
+#[test]
+fn transfers_to_and_from_sanctioned_accounts_fail() {
+    let mut deps = setup();
+
+    let err = execute_as(&mut deps, DEVELOPER, transfer(MALLORY, 10)).unwrap_err();
+    assert_eq!(err, sanctioned());
+    let err = execute_as(&mut deps, MALLORY, transfer(DEVELOPER, 10)).unwrap_err();
+    assert_eq!(err, sanctioned());
+
+    execute_as(&mut deps, DEVELOPER, transfer("buyer", 10)).unwrap();
+}
+
+#[test]
+fn allowances_and_issuance_cannot_reach_sanctioned_accounts() {
+    let mut deps = setup();
+
+    // Granting an allowance moves nothing, spending it does
+    execute_as(
+        &mut deps,
+        MALLORY,
+        ExecuteMsg::Cw20(Cw20ExecuteMsg::IncreaseAllowance {
+            spender: DEVELOPER.to_string(),
+            amount: Uint128::new(50),
+            expires: None,
+        }),
+    )
+    .unwrap();
+    let err = execute_as(
+        &mut deps,
+        DEVELOPER,
+        ExecuteMsg::Cw20(Cw20ExecuteMsg::TransferFrom {
+            owner: MALLORY.to_string(),
+            recipient: DEVELOPER.to_string(),
+            amount: Uint128::new(50),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, sanctioned());
+
+    let err = execute_as(
+        &mut deps,
+        VERIFIER,
+        CarbonCreditExecuteMsg::VerifyCredits {
+            verification_id: None,
+            credits_to_verify: Uint128::new(50),
+            verification_report_url: "https://registry.example/report".to_string(),
+            vintage_year: None,
+            methodology_version: None,
+            registry_serials: None,
+            recipients: Some(vec![
+                IssuanceRecipient {
+                    address: DEVELOPER.to_string(),
+                    credits: Uint128::new(20),
+                },
+                IssuanceRecipient {
+                    address: MALLORY.to_string(),
+                    credits: Uint128::new(30),
+                },
+            ]),
+            forward_series: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, sanctioned());
+}
+
+#[test]
+fn sanctioned_accounts_cannot_retire() {
+    let mut deps = setup();
+
+    let err = execute_as(
+        &mut deps,
+        MALLORY,
+        CarbonCreditExecuteMsg::RetireCredits {
+            retirement_id: None,
+            credits_to_retire: Uint128::new(10),
+            retirement_purpose: "offset".to_string(),
+            retirement_certificate_url: "https://registry.example/cert".to_string(),
+            batch_id: None,
+            registry_serials: None,
+            beneficiary: None,
+            beneficiary_name: None,
+            retirement_use: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, sanctioned());
+}
+
+#[test]
+fn credits_cannot_be_retired_for_sanctioned_accounts() {
+    let mut deps = setup();
+
+    let err = execute_as(
+        &mut deps,
+        DEVELOPER,
+        CarbonCreditExecuteMsg::RetireCredits {
+            retirement_id: None,
+            credits_to_retire: Uint128::new(10),
+            retirement_purpose: "offset".to_string(),
+            retirement_certificate_url: "https://registry.example/cert".to_string(),
+            batch_id: None,
+            registry_serials: None,
+            beneficiary: Some(MALLORY.to_string()),
+            beneficiary_name: None,
+            retirement_use: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, sanctioned());
+
+    let err = execute_as(
+        &mut deps,
+        DEVELOPER,
+        CarbonCreditExecuteMsg::RetireCreditsFrom {
+            owner: MALLORY.to_string(),
+            retirement_id: None,
+            credits_to_retire: Uint128::new(10),
+            retirement_purpose: "offset".to_string(),
+            retirement_certificate_url: "https://registry.example/cert".to_string(),
+            batch_id: None,
+            registry_serials: None,
+            beneficiary: None,
+            beneficiary_name: None,
+            retirement_use: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, sanctioned());
+}
+
+#[test]
+fn delisted_or_unscreened_accounts_can_transfer() {
+    let mut deps = setup();
+
+    deps.querier.set_blacklist(&[]);
+    execute_as(&mut deps, MALLORY, transfer(DEVELOPER, 10)).unwrap();
+
+    // With screening switched off the blacklist is not consulted
+    deps.querier.set_blacklist(&[MALLORY]);
+    let disable = SanctionExecuteMsg::SetSanctionScreening { enabled: false };
+    let err = execute_as(&mut deps, DEVELOPER, disable.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "configure sanction screening".to_string()
+        })
+    );
+    execute_as(&mut deps, ADMIN, disable).unwrap();
+    execute_as(&mut deps, MALLORY, transfer(DEVELOPER, 10)).unwrap();
+}
@@ -0,0 +1,237 @@
+//! Serial numbers: registry blocks that cannot be tokenized twice, retirements
+//! of named and of the lowest serials, and the state of a single serial.
+
+use cosmwasm_std::{Addr, Uint128};
+
+use super::suite::{verra_serials, Suite, SuiteBuilder, DEVELOPER, VERIFIER};
+use crate::error::ContractError;
+use crate::msg::{CarbonCreditExecuteMsg, SerialState};
+use crate::state::{SerialRange, VerificationStatus};
+
+/// 100 credits from instantiation numbered 1 to 100, then Verra serials 5001
+/// to 5300 verified into batch 1 as serials 101 to 400, all held by the
+/// developer.
+fn setup() -> Suite {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 100).build();
+    suite
+        .verify_serials(VERIFIER, "v-1", (5001, 5300), VerificationStatus::Verified)
+        .unwrap();
+    suite
+}
+
+fn range(first: u128, last: u128) -> SerialRange {
+    SerialRange {
+        first: first.into(),
+        last: last.into(),
+    }
+}
+
+#[test]
+fn registry_blocks_cannot_be_tokenized_twice() {
+    let mut suite = setup();
+    assert_eq!(
+        suite.verification_record("v-1").registry_serials,
+        Some(verra_serials(5001, 5300))
+    );
+
+    for overlapping in [(5300, 5400), (4900, 5001), (5100, 5200), (4000, 6000)] {
+        let err = suite
+            .verify_serials(VERIFIER, "v-2", overlapping, VerificationStatus::Verified)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::SerialBlockTokenized {
+                registry: "Verra".to_string(),
+                serial_start: overlapping.0.into(),
+                serial_end: overlapping.1.into(),
+                verification_id: "v-1".to_string(),
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    // The block has to number exactly the credits verified
+    let err = suite
+        .execute(
+            VERIFIER,
+            CarbonCreditExecuteMsg::VerifyCredits {
+                verification_id: None,
+                credits_to_verify: Uint128::new(10),
+                verification_report_url: "https://registry.example/report".to_string(),
+                recipients: None,
+                vintage_year: None,
+                methodology_version: None,
+                registry_serials: Some(verra_serials(6001, 6005)),
+                forward_series: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::SerialBlockMismatch {
+            serial_start: Uint128::new(6001),
+            serial_end: Uint128::new(6005),
+            credits: Uint128::new(10),
+        },
+        err.downcast().unwrap()
+    );
+
+    // Adjacent blocks are fine
+    suite
+        .verify_serials(VERIFIER, "v-2", (5301, 5400), VerificationStatus::Verified)
+        .unwrap();
+    suite
+        .verify_serials(VERIFIER, "v-3", (4901, 5000), VerificationStatus::Verified)
+        .unwrap();
+}
+
+#[test]
+fn a_rejected_verification_releases_its_block() {
+    let mut suite = setup();
+
+    // A pending verification already holds its block
+    suite
+        .verify_serials(VERIFIER, "v-2", (7001, 7100), VerificationStatus::Pending)
+        .unwrap();
+    suite
+        .verify_serials(VERIFIER, "v-3", (7050, 7060), VerificationStatus::Verified)
+        .unwrap_err();
+
+    suite
+        .update_verification_status(VERIFIER, "v-2", VerificationStatus::Rejected)
+        .unwrap();
+    suite
+        .verify_serials(VERIFIER, "v-3", (7050, 7060), VerificationStatus::Verified)
+        .unwrap();
+
+    // An expired one keeps it
+    suite
+        .update_verification_status(VERIFIER, "v-1", VerificationStatus::Expired)
+        .unwrap();
+    suite
+        .verify_serials(VERIFIER, "v-4", (5001, 5300), VerificationStatus::Verified)
+        .unwrap_err();
+}
+
+#[test]
+fn retirements_consume_the_named_or_the_lowest_serials() {
+    let mut suite = setup();
+
+    suite
+        .retire_serials(DEVELOPER, "r-1", (5151, 5200))
+        .unwrap();
+    let record = suite.retirement_record("r-1");
+    assert_eq!(record.serials, [range(251, 300)]);
+    assert_eq!(record.batches[0].batch_id, 1);
+    assert_eq!(suite.batch_balances(DEVELOPER), [(0, 100), (1, 250)]);
+
+    let err = suite
+        .retire_serials(DEVELOPER, "r-2", (5141, 5160))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::SerialsAlreadyRetired {
+            serial: Uint128::new(251),
+            retirement_id: "r-1".to_string(),
+        },
+        err.downcast().unwrap()
+    );
+
+    // Without serials the lowest left in each batch drawn are retired
+    suite.retire_credits(DEVELOPER, "r-2", 150).unwrap();
+    assert_eq!(
+        suite.retirement_record("r-2").serials,
+        [range(1, 100), range(101, 150)]
+    );
+    suite.retire_credits(DEVELOPER, "r-3", 120).unwrap();
+    assert_eq!(
+        suite.retirement_record("r-3").serials,
+        [range(151, 250), range(301, 320)]
+    );
+    assert_eq!(suite.retired_credits(), Uint128::new(320));
+}
+
+#[test]
+fn only_serials_of_a_verified_block_can_be_named() {
+    let mut suite = setup();
+    suite
+        .verify_serials(VERIFIER, "v-2", (7001, 7100), VerificationStatus::Pending)
+        .unwrap();
+
+    for serials in [(7001, 7010), (5250, 5350), (1, 10)] {
+        let err = suite.retire_serials(DEVELOPER, "r-1", serials).unwrap_err();
+        assert_eq!(
+            ContractError::SerialsNotRetirable {
+                registry: "Verra".to_string(),
+                serial_start: serials.0.into(),
+                serial_end: serials.1.into(),
+            },
+            err.downcast().unwrap()
+        );
+    }
+}
+
+#[test]
+fn serial_status_reports_pending_held_retired_and_expired_serials() {
+    let mut suite = setup();
+    suite
+        .verify_serials(VERIFIER, "v-2", (7001, 7100), VerificationStatus::Pending)
+        .unwrap();
+    suite.transfer(DEVELOPER, "buyer", 50).unwrap();
+    suite
+        .retire_serials(DEVELOPER, "r-1", (5001, 5010))
+        .unwrap();
+    suite.retire_credits("buyer", "r-2", 20).unwrap();
+
+    let status = suite.serial_status(7050, Some("Verra"));
+    assert_eq!(status.batch_id, None);
+    assert_eq!(
+        status.state,
+        SerialState::Pending {
+            verification_id: "v-2".to_string()
+        }
+    );
+
+    let status = suite.serial_status(5005, Some("Verra"));
+    assert_eq!(status.batch_id, Some(1));
+    assert_eq!(
+        status.state,
+        SerialState::Retired {
+            retirement_id: "r-1".to_string(),
+            retirement_entity: Addr::unchecked(DEVELOPER),
+        }
+    );
+    // The same credit in the contract's own numbering
+    assert_eq!(suite.serial_status(105, None), status);
+
+    let status = suite.serial_status(20, None);
+    assert_eq!(status.batch_id, Some(0));
+    assert_eq!(
+        status.state,
+        SerialState::Retired {
+            retirement_id: "r-2".to_string(),
+            retirement_entity: Addr::unchecked("buyer"),
+        }
+    );
+
+    assert_eq!(suite.serial_status(21, None).state, SerialState::Held);
+    assert_eq!(
+        suite.serial_status(5011, Some("Verra")).state,
+        SerialState::Held
+    );
+
+    suite
+        .update_verification_status(VERIFIER, "v-1", VerificationStatus::Expired)
+        .unwrap();
+    assert_eq!(
+        suite.serial_status(5011, Some("Verra")).state,
+        SerialState::Expired {
+            verification_id: "v-1".to_string()
+        }
+    );
+    // Retired serials stay retired
+    assert_eq!(
+        suite.serial_status(5005, Some("Verra")).state,
+        SerialState::Retired {
+            retirement_id: "r-1".to_string(),
+            retirement_entity: Addr::unchecked(DEVELOPER),
+        }
+    );
+}
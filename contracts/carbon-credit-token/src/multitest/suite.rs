@@ -0,0 +1,1398 @@
+//! Test fixture for the carbon credit contract: a `SuiteBuilder` that
+//! instantiates the contract in a multi-test `App`, and a `Suite` with one
+//! helper per message so tests read as a sequence of actions.
+
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{to_json_binary, Addr, Decimal, Empty, StdResult, Timestamp, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, MinterResponse, TokenInfoResponse};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw721::{NftInfoResponse, OwnerOfResponse};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use retirement_certificate::msg::{CertificateMetadata, QueryMsg as CertificateQueryMsg};
+use rwa_common::pause::PauseStatusResponse;
+use rwa_common::roles::{PendingRoleTransfersResponse, RoleAdminResponse, RoleMembersResponse};
+use rwa_common::{
+    Cw20ExecuteMsg, Cw20QueryMsg, PauseCategory, PauseExecuteMsg, PauseQueryMsg, RoleExecuteMsg,
+    RoleQueryMsg,
+};
+
+use crate::msg::{
+    BufferPoolResponse, CarbonCreditExecuteMsg, CarbonCreditQueryMsg, ExecuteMsg, InstantiateMsg,
+    IssuanceRecipient, MigrateMsg, MonitoringPeriod, ProjectAmount, ProjectTag, QueryMsg,
+    ReceiveMsg, ReconcileSupplyResponse, RetirementSummaryResponse, SerialStatusResponse,
+};
+use crate::state::{
+    AuthorizationStatus, Batch, BatchAmount, BatchAuthorization, CarbonCreditInfo,
+    CreditAllocation, CreditUse, ForwardPosition, ForwardSeries, ProjectTagRecord, ProjectTags,
+    ProjectType, RegistrySerials, RetirementRecord, ReversalRecord, VerificationRecord,
+    VerificationStatus, VintagePolicy,
+};
+
+pub const ADMIN: &str = "admin";
+pub const DEVELOPER: &str = "developer";
+pub const VERIFIER: &str = "verifier";
+
+fn contract_carbon_credit() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_migrate(crate::contract::migrate)
+    .with_reply(crate::contract::reply);
+    Box::new(contract)
+}
+
+fn contract_retirement_certificate() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        retirement_certificate::execute,
+        retirement_certificate::instantiate,
+        retirement_certificate::query,
+    );
+    Box::new(contract)
+}
+
+#[derive(Default)]
+pub struct SuiteBuilder {
+    initial_balances: Vec<Cw20Coin>,
+    credits_available: Uint128,
+    without_certificates: bool,
+}
+
+impl SuiteBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Issues `amount` credits to `address` at instantiation and counts them
+    /// as issued and available.
+    pub fn with_credits(mut self, address: &str, amount: u128) -> Self {
+        self.initial_balances.push(Cw20Coin {
+            address: address.to_string(),
+            amount: amount.into(),
+        });
+        self.credits_available += Uint128::new(amount);
+        self
+    }
+
+    /// Gives `address` tokens at instantiation without counting them as
+    /// credits, like balances minted through CW20 before issuance went
+    /// through verification.
+    pub fn with_unbacked_tokens(mut self, address: &str, amount: u128) -> Self {
+        self.initial_balances.push(Cw20Coin {
+            address: address.to_string(),
+            amount: amount.into(),
+        });
+        self
+    }
+
+    /// Instantiates the contract without a retirement certificate collection.
+    pub fn without_certificates(mut self) -> Self {
+        self.without_certificates = true;
+        self
+    }
+
+    /// The message `build` instantiates the contract with, less the
+    /// certificate collection.
+    pub fn instantiate_msg(&self) -> InstantiateMsg {
+        InstantiateMsg {
+            cw20_base: Cw20InstantiateMsg {
+                name: "Rimba Raya Carbon Credit".to_string(),
+                symbol: "RRCC".to_string(),
+                decimals: 0,
+                initial_balances: self.initial_balances.clone(),
+                mint: Some(MinterResponse {
+                    minter: DEVELOPER.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
+            },
+            carbon_credit_info: CarbonCreditInfo {
+                project_id: "VCS-674".to_string(),
+                project_name: "Rimba Raya Biodiversity Reserve".to_string(),
+                project_type: ProjectType::ForestConservation,
+                verification_standard: "VCS".to_string(),
+                vintage_year: 2023,
+                country: "Indonesia".to_string(),
+                total_credits_issued: self.credits_available,
+                credits_retired: Uint128::zero(),
+                credits_available: self.credits_available,
+                co2_equivalent_per_credit: Decimal::one(),
+                credits_buffered: Uint128::zero(),
+                verification_body: Addr::unchecked(VERIFIER),
+                project_developer: Addr::unchecked(DEVELOPER),
+            },
+            admin: None,
+            // Multi-test does not serve the sanction module's stargate query;
+            // screening is covered by the mock querier tests in sanctions.rs
+            sanction_screening: Some(false),
+            certificate_code_id: None,
+        }
+    }
+
+    pub fn build(self) -> Suite {
+        let mut app = App::default();
+        let code_id = app.store_code(contract_carbon_credit());
+        let certificate_code_id = app.store_code(contract_retirement_certificate());
+
+        let mut msg = self.instantiate_msg();
+        if !self.without_certificates {
+            msg.certificate_code_id = Some(certificate_code_id);
+        }
+        let contract = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked(ADMIN),
+                &msg,
+                &[],
+                "carbon-credit-token",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+
+        Suite {
+            app,
+            code_id,
+            contract,
+        }
+    }
+}
+
+pub struct Suite {
+    pub app: App,
+    pub code_id: u64,
+    pub contract: Addr,
+}
+
+impl Suite {
+    pub fn execute(&mut self, sender: &str, msg: impl Into<ExecuteMsg>) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &msg.into(),
+            &[],
+        )
+    }
+
+    pub fn verify_credits(
+        &mut self,
+        sender: &str,
+        verification_id: &str,
+        credits: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::VerifyCredits {
+                verification_id: Some(verification_id.to_string()),
+                credits_to_verify: credits.into(),
+                verification_report_url: format!("https://registry.example/{verification_id}"),
+                recipients: None,
+                vintage_year: None,
+                methodology_version: None,
+                registry_serials: None,
+                forward_series: None,
+            },
+        )
+    }
+
+    /// Verifies `credits` delivered against the forward series `series_id`.
+    pub fn verify_forward(
+        &mut self,
+        sender: &str,
+        verification_id: &str,
+        credits: u128,
+        series_id: u64,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::VerifyCredits {
+                verification_id: Some(verification_id.to_string()),
+                credits_to_verify: credits.into(),
+                verification_report_url: format!("https://registry.example/{verification_id}"),
+                recipients: None,
+                vintage_year: None,
+                methodology_version: None,
+                registry_serials: None,
+                forward_series: Some(series_id),
+            },
+        )
+    }
+
+    pub fn submit_verification(
+        &mut self,
+        sender: &str,
+        verification_id: &str,
+        credits: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::SubmitVerification {
+                verification_id: Some(verification_id.to_string()),
+                credits_to_verify: credits.into(),
+                verification_report_url: format!("https://registry.example/{verification_id}"),
+                recipients: None,
+                vintage_year: None,
+                methodology_version: None,
+                registry_serials: None,
+            },
+        )
+    }
+
+    pub fn verify_credits_to(
+        &mut self,
+        sender: &str,
+        verification_id: &str,
+        recipients: &[(&str, u128)],
+    ) -> AnyResult<AppResponse> {
+        let credits: u128 = recipients.iter().map(|(_, credits)| credits).sum();
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::VerifyCredits {
+                verification_id: Some(verification_id.to_string()),
+                credits_to_verify: credits.into(),
+                verification_report_url: format!("https://registry.example/{verification_id}"),
+                vintage_year: None,
+                methodology_version: None,
+                recipients: Some(
+                    recipients
+                        .iter()
+                        .map(|(address, credits)| IssuanceRecipient {
+                            address: address.to_string(),
+                            credits: (*credits).into(),
+                        })
+                        .collect(),
+                ),
+                registry_serials: None,
+                forward_series: None,
+            },
+        )
+    }
+
+    /// Verifies `credits` of the given vintage, minted to the developer.
+    pub fn verify_vintage(
+        &mut self,
+        sender: &str,
+        verification_id: &str,
+        credits: u128,
+        vintage_year: u32,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::VerifyCredits {
+                verification_id: Some(verification_id.to_string()),
+                credits_to_verify: credits.into(),
+                verification_report_url: format!("https://registry.example/{verification_id}"),
+                recipients: None,
+                vintage_year: Some(vintage_year),
+                methodology_version: Some("VM0007 v1.6".to_string()),
+                registry_serials: None,
+                forward_series: None,
+            },
+        )
+    }
+
+    /// Verifies, or with `Pending` submits, the credits numbered `serials` in
+    /// the Verra registry, minted to the developer.
+    pub fn verify_serials(
+        &mut self,
+        sender: &str,
+        verification_id: &str,
+        serials: (u128, u128),
+        status: VerificationStatus,
+    ) -> AnyResult<AppResponse> {
+        let (serial_start, serial_end) = serials;
+        let credits_to_verify = (serial_end + 1 - serial_start).into();
+        let verification_id = Some(verification_id.to_string());
+        let verification_report_url = "https://registry.example/report".to_string();
+        let registry_serials = Some(verra_serials(serial_start, serial_end));
+        let msg = match status {
+            VerificationStatus::Pending => CarbonCreditExecuteMsg::SubmitVerification {
+                verification_id,
+                credits_to_verify,
+                verification_report_url,
+                recipients: None,
+                vintage_year: None,
+                methodology_version: None,
+                registry_serials,
+            },
+            _ => CarbonCreditExecuteMsg::VerifyCredits {
+                verification_id,
+                credits_to_verify,
+                verification_report_url,
+                recipients: None,
+                vintage_year: None,
+                methodology_version: None,
+                registry_serials,
+                forward_series: None,
+            },
+        };
+        self.execute(sender, msg)
+    }
+
+    pub fn retire_credits(
+        &mut self,
+        sender: &str,
+        retirement_id: &str,
+        credits: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::RetireCredits {
+                retirement_id: Some(retirement_id.to_string()),
+                credits_to_retire: credits.into(),
+                retirement_purpose: "2024 scope 1 offset".to_string(),
+                retirement_certificate_url: format!("https://registry.example/{retirement_id}"),
+                batch_id: None,
+                registry_serials: None,
+                beneficiary: None,
+                beneficiary_name: None,
+                retirement_use: None,
+            },
+        )
+    }
+
+    /// Retires `credits` of the sender for `purpose`, for `beneficiary` if
+    /// given.
+    pub fn retire_for_purpose(
+        &mut self,
+        sender: &str,
+        retirement_id: &str,
+        credits: u128,
+        purpose: &str,
+        beneficiary: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::RetireCredits {
+                retirement_id: Some(retirement_id.to_string()),
+                credits_to_retire: credits.into(),
+                retirement_purpose: purpose.to_string(),
+                retirement_certificate_url: format!("https://registry.example/{retirement_id}"),
+                batch_id: None,
+                registry_serials: None,
+                beneficiary: beneficiary.map(str::to_string),
+                beneficiary_name: None,
+                retirement_use: None,
+            },
+        )
+    }
+
+    /// Retires `credits` of the sender for `retirement_use`, from `batch_id`
+    /// or the oldest batches held.
+    pub fn retire_for_use(
+        &mut self,
+        sender: &str,
+        retirement_id: &str,
+        credits: u128,
+        batch_id: Option<u64>,
+        retirement_use: CreditUse,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::RetireCredits {
+                retirement_id: Some(retirement_id.to_string()),
+                credits_to_retire: credits.into(),
+                retirement_purpose: "2024 scope 1 offset".to_string(),
+                retirement_certificate_url: format!("https://registry.example/{retirement_id}"),
+                batch_id,
+                registry_serials: None,
+                beneficiary: None,
+                beneficiary_name: None,
+                retirement_use: Some(retirement_use),
+            },
+        )
+    }
+
+    pub fn retire_batch(
+        &mut self,
+        sender: &str,
+        retirement_id: &str,
+        batch_id: u64,
+        credits: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::RetireCredits {
+                retirement_id: Some(retirement_id.to_string()),
+                credits_to_retire: credits.into(),
+                retirement_purpose: "2024 scope 1 offset".to_string(),
+                retirement_certificate_url: format!("https://registry.example/{retirement_id}"),
+                batch_id: Some(batch_id),
+                registry_serials: None,
+                beneficiary: None,
+                beneficiary_name: None,
+                retirement_use: None,
+            },
+        )
+    }
+
+    /// Retires the credits numbered `serials` in the Verra registry.
+    pub fn retire_serials(
+        &mut self,
+        sender: &str,
+        retirement_id: &str,
+        serials: (u128, u128),
+    ) -> AnyResult<AppResponse> {
+        let (serial_start, serial_end) = serials;
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::RetireCredits {
+                retirement_id: Some(retirement_id.to_string()),
+                credits_to_retire: (serial_end + 1 - serial_start).into(),
+                retirement_purpose: "2024 scope 1 offset".to_string(),
+                retirement_certificate_url: format!("https://registry.example/{retirement_id}"),
+                batch_id: None,
+                registry_serials: Some(verra_serials(serial_start, serial_end)),
+                beneficiary: None,
+                beneficiary_name: None,
+                retirement_use: None,
+            },
+        )
+    }
+
+    /// Retires `credits` of the sender for `beneficiary`, named `name` on the
+    /// certificate if given.
+    pub fn retire_for(
+        &mut self,
+        sender: &str,
+        retirement_id: &str,
+        credits: u128,
+        beneficiary: &str,
+        name: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::RetireCredits {
+                retirement_id: Some(retirement_id.to_string()),
+                credits_to_retire: credits.into(),
+                retirement_purpose: "2024 scope 1 offset".to_string(),
+                retirement_certificate_url: format!("https://registry.example/{retirement_id}"),
+                batch_id: None,
+                registry_serials: None,
+                beneficiary: Some(beneficiary.to_string()),
+                beneficiary_name: name.map(str::to_string),
+                retirement_use: None,
+            },
+        )
+    }
+
+    /// Retires `credits` of `owner` through its allowance to the sender.
+    pub fn retire_credits_from(
+        &mut self,
+        sender: &str,
+        owner: &str,
+        retirement_id: &str,
+        credits: u128,
+        beneficiary: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::RetireCreditsFrom {
+                owner: owner.to_string(),
+                retirement_id: Some(retirement_id.to_string()),
+                credits_to_retire: credits.into(),
+                retirement_purpose: "2024 scope 1 offset".to_string(),
+                retirement_certificate_url: format!("https://registry.example/{retirement_id}"),
+                batch_id: None,
+                registry_serials: None,
+                beneficiary: beneficiary.map(str::to_string),
+                beneficiary_name: None,
+                retirement_use: None,
+            },
+        )
+    }
+
+    pub fn transfer_batch(
+        &mut self,
+        sender: &str,
+        batch_id: u64,
+        recipient: &str,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::TransferBatch {
+                batch_id,
+                recipient: recipient.to_string(),
+                amount: amount.into(),
+            },
+        )
+    }
+
+    pub fn update_verification_status(
+        &mut self,
+        sender: &str,
+        verification_id: &str,
+        status: VerificationStatus,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::UpdateVerificationStatus {
+                verification_id: verification_id.to_string(),
+                status,
+                reason: None,
+            },
+        )
+    }
+
+    pub fn transfer(
+        &mut self,
+        sender: &str,
+        recipient: &str,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            ExecuteMsg::Cw20(Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: amount.into(),
+            }),
+        )
+    }
+
+    /// Sends `amount` of the sender's credits to `contract` with `msg`.
+    pub fn send(
+        &mut self,
+        sender: &str,
+        contract: &Addr,
+        amount: u128,
+        msg: &ReceiveMsg,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            ExecuteMsg::Cw20(Cw20ExecuteMsg::Send {
+                contract: contract.to_string(),
+                amount: amount.into(),
+                msg: to_json_binary(msg).unwrap(),
+            }),
+        )
+    }
+
+    pub fn increase_allowance(
+        &mut self,
+        sender: &str,
+        spender: &str,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            ExecuteMsg::Cw20(Cw20ExecuteMsg::IncreaseAllowance {
+                spender: spender.to_string(),
+                amount: amount.into(),
+                expires: None,
+            }),
+        )
+    }
+
+    pub fn approve_forward_token(&mut self, sender: &str, token: &Addr) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::ApproveForwardToken {
+                token: token.to_string(),
+            },
+        )
+    }
+
+    pub fn revoke_forward_token(&mut self, sender: &str, token: &Addr) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::RevokeForwardToken {
+                token: token.to_string(),
+            },
+        )
+    }
+
+    pub fn set_buffer_rate(
+        &mut self,
+        sender: &str,
+        project_type: ProjectType,
+        rate: Decimal,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::SetBufferRate { project_type, rate },
+        )
+    }
+
+    pub fn report_reversal(
+        &mut self,
+        sender: &str,
+        reversal_id: &str,
+        credits: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::ReportReversal {
+                reversal_id: Some(reversal_id.to_string()),
+                credits: credits.into(),
+                reason: "wildfire".to_string(),
+            },
+        )
+    }
+
+    pub fn continue_reversal(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::ContinueReversal { limit: None },
+        )
+    }
+
+    pub fn register_project(
+        &mut self,
+        sender: &str,
+        project: CarbonCreditInfo,
+    ) -> AnyResult<AppResponse> {
+        self.execute(sender, CarbonCreditExecuteMsg::RegisterProject { project })
+    }
+
+    /// Verifies `credits` of a registered project, issued to its developer.
+    pub fn verify_project_credits(
+        &mut self,
+        sender: &str,
+        project_id: &str,
+        verification_id: &str,
+        credits: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::VerifyProjectCredits {
+                project_id: project_id.to_string(),
+                verification_id: Some(verification_id.to_string()),
+                credits_to_verify: credits.into(),
+                verification_report_url: format!("https://registry.example/{verification_id}"),
+                recipients: None,
+            },
+        )
+    }
+
+    /// Moves `(project_id, amount)` pairs of registered project credits.
+    pub fn transfer_project_credits(
+        &mut self,
+        sender: &str,
+        from: Option<&str>,
+        recipient: &str,
+        amounts: &[(&str, u128)],
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::TransferProjectCredits {
+                from: from.map(str::to_string),
+                recipient: recipient.to_string(),
+                amounts: amounts
+                    .iter()
+                    .map(|(project_id, amount)| ProjectAmount {
+                        project_id: project_id.to_string(),
+                        amount: (*amount).into(),
+                    })
+                    .collect(),
+            },
+        )
+    }
+
+    pub fn retire_project_credits(
+        &mut self,
+        sender: &str,
+        project_id: &str,
+        retirement_id: &str,
+        credits: u128,
+    ) -> AnyResult<AppResponse> {
+        self.retire_project_credits_for_use(
+            sender,
+            project_id,
+            retirement_id,
+            credits,
+            CreditUse::Voluntary,
+        )
+    }
+
+    pub fn retire_project_credits_for_use(
+        &mut self,
+        sender: &str,
+        project_id: &str,
+        retirement_id: &str,
+        credits: u128,
+        retirement_use: CreditUse,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::RetireProjectCredits {
+                project_id: project_id.to_string(),
+                retirement_id: Some(retirement_id.to_string()),
+                credits_to_retire: credits.into(),
+                retirement_purpose: "Carbon offset".to_string(),
+                retirement_certificate_url: format!("https://registry.example/{retirement_id}"),
+                beneficiary: None,
+                beneficiary_name: None,
+                retirement_use: Some(retirement_use),
+            },
+        )
+    }
+
+    pub fn approve_all(&mut self, sender: &str, operator: &str) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::ApproveAll {
+                operator: operator.to_string(),
+            },
+        )
+    }
+
+    pub fn revoke_all(&mut self, sender: &str, operator: &str) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::RevokeAll {
+                operator: operator.to_string(),
+            },
+        )
+    }
+
+    /// Issues forward units against `(period_end, expected_credits)` periods,
+    /// split between `recipients` if given.
+    pub fn issue_forward(
+        &mut self,
+        sender: &str,
+        schedule: &[(Timestamp, u128)],
+        recipients: Option<&[(&str, u128)]>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::IssueForward {
+                schedule: schedule
+                    .iter()
+                    .map(|(period_end, expected_credits)| MonitoringPeriod {
+                        period_end: *period_end,
+                        expected_credits: (*expected_credits).into(),
+                    })
+                    .collect(),
+                recipients: recipients.map(|recipients| {
+                    recipients
+                        .iter()
+                        .map(|(address, credits)| IssuanceRecipient {
+                            address: address.to_string(),
+                            credits: (*credits).into(),
+                        })
+                        .collect()
+                }),
+            },
+        )
+    }
+
+    pub fn transfer_forward(
+        &mut self,
+        sender: &str,
+        series_id: u64,
+        recipient: &str,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::TransferForward {
+                series_id,
+                recipient: recipient.to_string(),
+                amount: amount.into(),
+            },
+        )
+    }
+
+    /// Records the authorization of `batch_id` for `authorized_uses`, with a
+    /// corresponding adjustment.
+    pub fn set_batch_authorization(
+        &mut self,
+        sender: &str,
+        batch_id: u64,
+        status: AuthorizationStatus,
+        authorized_uses: &[CreditUse],
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::SetBatchAuthorization {
+                batch_id,
+                status,
+                letter_of_authorization: Some(format!("LoA-{batch_id}")),
+                authorized_uses: authorized_uses.to_vec(),
+                corresponding_adjustment: true,
+            },
+        )
+    }
+
+    pub fn set_project_authorization(
+        &mut self,
+        sender: &str,
+        project_id: &str,
+        status: AuthorizationStatus,
+        authorized_uses: &[CreditUse],
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::SetProjectAuthorization {
+                project_id: project_id.to_string(),
+                status,
+                letter_of_authorization: Some(format!("LoA-{project_id}")),
+                authorized_uses: authorized_uses.to_vec(),
+                corresponding_adjustment: true,
+            },
+        )
+    }
+
+    pub fn set_project_tags(
+        &mut self,
+        sender: &str,
+        project_id: Option<&str>,
+        tags: ProjectTags,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::SetProjectTags {
+                project_id: project_id.map(str::to_string),
+                tags,
+            },
+        )
+    }
+
+    pub fn attest_project_tags(
+        &mut self,
+        sender: &str,
+        project_id: Option<&str>,
+        revision: u32,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::AttestProjectTags {
+                project_id: project_id.map(str::to_string),
+                revision,
+                report_url: format!("https://registry.example/tags/{revision}"),
+            },
+        )
+    }
+
+    pub fn set_vintage_policy(
+        &mut self,
+        sender: &str,
+        validity_years: u32,
+        block_transfers: bool,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::SetVintagePolicy {
+                policy: Some(VintagePolicy {
+                    validity_years,
+                    block_transfers,
+                }),
+            },
+        )
+    }
+
+    pub fn sweep_expired(
+        &mut self,
+        sender: &str,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            CarbonCreditExecuteMsg::SweepExpired { start_after, limit },
+        )
+    }
+
+    pub fn mint(&mut self, sender: &str, recipient: &str, amount: u128) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            ExecuteMsg::Cw20(Cw20ExecuteMsg::Mint {
+                recipient: recipient.to_string(),
+                amount: amount.into(),
+            }),
+        )
+    }
+
+    pub fn burn(&mut self, sender: &str, amount: u128) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            ExecuteMsg::Cw20(Cw20ExecuteMsg::Burn {
+                amount: amount.into(),
+            }),
+        )
+    }
+
+    pub fn grant_role(
+        &mut self,
+        sender: &str,
+        role: &str,
+        address: &str,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            RoleExecuteMsg::GrantRole {
+                role: role.to_string(),
+                address: address.to_string(),
+            },
+        )
+    }
+
+    pub fn revoke_role(
+        &mut self,
+        sender: &str,
+        role: &str,
+        address: &str,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            RoleExecuteMsg::RevokeRole {
+                role: role.to_string(),
+                address: address.to_string(),
+            },
+        )
+    }
+
+    pub fn propose_role_transfer(
+        &mut self,
+        sender: &str,
+        role: &str,
+        from: &str,
+        to: &str,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            RoleExecuteMsg::ProposeRoleTransfer {
+                role: role.to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+            },
+        )
+    }
+
+    pub fn accept_role_transfer(
+        &mut self,
+        sender: &str,
+        role: &str,
+        from: &str,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            RoleExecuteMsg::AcceptRoleTransfer {
+                role: role.to_string(),
+                from: from.to_string(),
+            },
+        )
+    }
+
+    pub fn cancel_role_transfer(
+        &mut self,
+        sender: &str,
+        role: &str,
+        from: &str,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            RoleExecuteMsg::CancelRoleTransfer {
+                role: role.to_string(),
+                from: from.to_string(),
+            },
+        )
+    }
+
+    pub fn propose_role_admin(&mut self, sender: &str, address: &str) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            RoleExecuteMsg::ProposeRoleAdmin {
+                address: address.to_string(),
+            },
+        )
+    }
+
+    pub fn accept_role_admin(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.execute(sender, RoleExecuteMsg::AcceptRoleAdmin {})
+    }
+
+    pub fn pause(
+        &mut self,
+        sender: &str,
+        categories: Option<Vec<PauseCategory>>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(sender, PauseExecuteMsg::Pause { categories })
+    }
+
+    pub fn unpause(
+        &mut self,
+        sender: &str,
+        categories: Option<Vec<PauseCategory>>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(sender, PauseExecuteMsg::Unpause { categories })
+    }
+
+    pub fn migrate(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.app.migrate_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &MigrateMsg {},
+            self.code_id,
+        )
+    }
+
+    /// Moves the chain to `time`.
+    pub fn set_block_time(&mut self, time: Timestamp) {
+        self.app.update_block(|block| block.time = time);
+    }
+
+    fn query<T: serde::de::DeserializeOwned>(&self, msg: impl Into<QueryMsg>) -> T {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &msg.into())
+            .unwrap()
+    }
+
+    pub fn carbon_credit_info(&self) -> CarbonCreditInfo {
+        self.query(CarbonCreditQueryMsg::CarbonCreditInfo {})
+    }
+
+    pub fn verification_record(&self, verification_id: &str) -> VerificationRecord {
+        self.query(CarbonCreditQueryMsg::VerificationRecord {
+            verification_id: verification_id.to_string(),
+        })
+    }
+
+    pub fn all_verification_records(
+        &self,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<(String, VerificationRecord)> {
+        self.query(CarbonCreditQueryMsg::AllVerificationRecords {
+            start_after: start_after.map(str::to_string),
+            limit,
+        })
+    }
+
+    pub fn retirement_record(&self, retirement_id: &str) -> RetirementRecord {
+        self.query(CarbonCreditQueryMsg::RetirementRecord {
+            retirement_id: retirement_id.to_string(),
+        })
+    }
+
+    pub fn all_retirement_records(
+        &self,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<(String, RetirementRecord)> {
+        self.query(CarbonCreditQueryMsg::AllRetirementRecords {
+            start_after: start_after.map(str::to_string),
+            limit,
+        })
+    }
+
+    pub fn available_credits(&self) -> Uint128 {
+        self.query(CarbonCreditQueryMsg::AvailableCredits {})
+    }
+
+    pub fn retired_credits(&self) -> Uint128 {
+        self.query(CarbonCreditQueryMsg::RetiredCredits {})
+    }
+
+    pub fn reconcile_supply(&self) -> ReconcileSupplyResponse {
+        self.query(CarbonCreditQueryMsg::ReconcileSupply {})
+    }
+
+    pub fn batch(&self, batch_id: u64) -> Batch {
+        self.query(CarbonCreditQueryMsg::Batch { batch_id })
+    }
+
+    pub fn all_batches(&self) -> Vec<Batch> {
+        self.query(CarbonCreditQueryMsg::AllBatches {
+            start_after: None,
+            limit: None,
+        })
+    }
+
+    /// `(batch_id, credits)` for every batch `address` holds, across all pages.
+    pub fn batch_balances(&self, address: &str) -> Vec<(u64, u128)> {
+        let mut balances = vec![];
+        let mut start_after = None;
+        loop {
+            let page: Vec<BatchAmount> = self.query(CarbonCreditQueryMsg::BatchBalances {
+                address: address.to_string(),
+                start_after,
+                limit: None,
+            });
+            let Some(last) = page.last() else {
+                return balances;
+            };
+            start_after = Some(last.batch_id);
+            balances.extend(
+                page.into_iter()
+                    .map(|balance| (balance.batch_id, balance.credits.u128())),
+            );
+        }
+    }
+
+    /// The state of a Verra serial, or of one of the contract's own serials
+    /// without a registry.
+    pub fn serial_status(&self, serial: u128, registry: Option<&str>) -> SerialStatusResponse {
+        self.query(CarbonCreditQueryMsg::SerialStatus {
+            serial: serial.into(),
+            registry: registry.map(str::to_string),
+        })
+    }
+
+    pub fn certificate_collection(&self) -> Option<Addr> {
+        self.query(CarbonCreditQueryMsg::CertificateCollection {})
+    }
+
+    pub fn certificate_retirement(&self, token_id: &str) -> StdResult<RetirementRecord> {
+        self.app.wrap().query_wasm_smart(
+            &self.contract,
+            &QueryMsg::from(CarbonCreditQueryMsg::CertificateRetirement {
+                token_id: token_id.to_string(),
+            }),
+        )
+    }
+
+    /// The certificate `token_id` of the collection, with its owner.
+    pub fn certificate(&self, token_id: &str) -> (String, NftInfoResponse<CertificateMetadata>) {
+        let collection = self.certificate_collection().unwrap();
+        let owner: OwnerOfResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &collection,
+                &CertificateQueryMsg::OwnerOf {
+                    token_id: token_id.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        let info = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &collection,
+                &CertificateQueryMsg::NftInfo {
+                    token_id: token_id.to_string(),
+                },
+            )
+            .unwrap();
+        (owner.owner, info)
+    }
+
+    pub fn forward_tokens(&self) -> Vec<Addr> {
+        self.query(CarbonCreditQueryMsg::ForwardTokens {
+            start_after: None,
+            limit: None,
+        })
+    }
+
+    pub fn buffer_pool(&self) -> BufferPoolResponse {
+        self.query(CarbonCreditQueryMsg::BufferPool {})
+    }
+
+    pub fn reversal_record(&self, reversal_id: &str) -> ReversalRecord {
+        self.query(CarbonCreditQueryMsg::ReversalRecord {
+            reversal_id: reversal_id.to_string(),
+        })
+    }
+
+    pub fn reversal_cancellations(&self, reversal_id: &str) -> Vec<CreditAllocation> {
+        self.query(CarbonCreditQueryMsg::ReversalCancellations {
+            reversal_id: reversal_id.to_string(),
+            start_after: None,
+            limit: None,
+        })
+    }
+
+    pub fn project(&self, project_id: &str) -> CarbonCreditInfo {
+        self.query(CarbonCreditQueryMsg::Project {
+            project_id: project_id.to_string(),
+        })
+    }
+
+    pub fn all_projects(&self) -> Vec<(String, CarbonCreditInfo)> {
+        self.query(CarbonCreditQueryMsg::AllProjects {
+            start_after: None,
+            limit: None,
+        })
+    }
+
+    pub fn project_balance(&self, owner: &str, project_id: &str) -> Uint128 {
+        self.query(CarbonCreditQueryMsg::ProjectBalance {
+            owner: owner.to_string(),
+            project_id: project_id.to_string(),
+        })
+    }
+
+    /// `owner`'s registered project balances as `(project_id, amount)` pairs.
+    pub fn project_balances(&self, owner: &str) -> Vec<(String, u128)> {
+        let balances: Vec<ProjectAmount> = self.query(CarbonCreditQueryMsg::ProjectBalances {
+            owner: owner.to_string(),
+            start_after: None,
+            limit: None,
+        });
+        balances
+            .into_iter()
+            .map(|balance| (balance.project_id, balance.amount.u128()))
+            .collect()
+    }
+
+    pub fn is_approved_for_all(&self, owner: &str, operator: &str) -> bool {
+        self.query(CarbonCreditQueryMsg::IsApprovedForAll {
+            owner: owner.to_string(),
+            operator: operator.to_string(),
+        })
+    }
+
+    pub fn batch_authorization(&self, batch_id: u64) -> Option<BatchAuthorization> {
+        self.query(CarbonCreditQueryMsg::BatchAuthorization { batch_id })
+    }
+
+    pub fn project_authorization(&self, project_id: &str) -> Option<BatchAuthorization> {
+        self.query(CarbonCreditQueryMsg::ProjectAuthorization {
+            project_id: project_id.to_string(),
+        })
+    }
+
+    pub fn forward_series(&self, series_id: u64) -> ForwardSeries {
+        self.query(CarbonCreditQueryMsg::ForwardSeries { series_id })
+    }
+
+    /// `holder`'s (forward, delivered, shortfall) position in a series.
+    pub fn forward_position(&self, series_id: u64, holder: &str) -> (u128, u128, u128) {
+        let position: ForwardPosition = self.query(CarbonCreditQueryMsg::ForwardPosition {
+            series_id,
+            holder: holder.to_string(),
+        });
+        (
+            position.forward.u128(),
+            position.delivered.u128(),
+            position.shortfall.u128(),
+        )
+    }
+
+    pub fn forward_positions(&self, series_id: u64) -> Vec<(Addr, ForwardPosition)> {
+        self.query(CarbonCreditQueryMsg::ForwardPositions {
+            series_id,
+            start_after: None,
+            limit: None,
+        })
+    }
+
+    pub fn retirement_summary(
+        &self,
+        entity: &str,
+        from: Timestamp,
+        to: Timestamp,
+    ) -> RetirementSummaryResponse {
+        self.query(CarbonCreditQueryMsg::RetirementSummary {
+            entity: entity.to_string(),
+            from,
+            to,
+        })
+    }
+
+    pub fn project_tags(&self, project_id: Option<&str>) -> Option<ProjectTagRecord> {
+        self.query(CarbonCreditQueryMsg::ProjectTags {
+            project_id: project_id.map(str::to_string),
+        })
+    }
+
+    pub fn tag_history(&self, project_id: Option<&str>) -> Vec<ProjectTagRecord> {
+        self.query(CarbonCreditQueryMsg::TagHistory {
+            project_id: project_id.map(str::to_string),
+            start_after: None,
+            limit: None,
+        })
+    }
+
+    /// The ids of the projects carrying `tag`.
+    pub fn projects_by_tag(&self, tag: ProjectTag, attested_only: bool) -> Vec<String> {
+        let projects: Vec<(String, ProjectTagRecord)> =
+            self.query(CarbonCreditQueryMsg::ProjectsByTag {
+                tag,
+                attested_only: Some(attested_only),
+                start_after: None,
+                limit: None,
+            });
+        projects.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// The ids of the verification records of projects carrying `tag`.
+    pub fn verifications_by_tag(&self, tag: ProjectTag, attested_only: bool) -> Vec<String> {
+        let records: Vec<(String, VerificationRecord)> =
+            self.query(CarbonCreditQueryMsg::VerificationRecordsByTag {
+                tag,
+                attested_only: Some(attested_only),
+                start_after: None,
+                limit: None,
+            });
+        records.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// The ids of the retirement records of projects carrying `tag`.
+    pub fn retirements_by_tag(&self, tag: ProjectTag, attested_only: bool) -> Vec<String> {
+        let records: Vec<(String, RetirementRecord)> =
+            self.query(CarbonCreditQueryMsg::RetirementRecordsByTag {
+                tag,
+                attested_only: Some(attested_only),
+                start_after: None,
+                limit: None,
+            });
+        records.into_iter().map(|(id, _)| id).collect()
+    }
+
+    pub fn vintage_policy(&self) -> Option<VintagePolicy> {
+        self.query(CarbonCreditQueryMsg::VintagePolicy {})
+    }
+
+    pub fn role_admin(&self) -> RoleAdminResponse {
+        self.query(RoleQueryMsg::RoleAdmin {})
+    }
+
+    pub fn role_members(
+        &self,
+        role: &str,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<Addr> {
+        let res: RoleMembersResponse = self.query(RoleQueryMsg::RoleMembers {
+            role: role.to_string(),
+            start_after: start_after.map(str::to_string),
+            limit,
+        });
+        res.members
+    }
+
+    pub fn has_role(&self, role: &str, address: &str) -> bool {
+        self.query(RoleQueryMsg::HasRole {
+            role: role.to_string(),
+            address: address.to_string(),
+        })
+    }
+
+    pub fn pending_role_transfers(&self, role: &str) -> PendingRoleTransfersResponse {
+        self.query(RoleQueryMsg::PendingRoleTransfers {
+            role: role.to_string(),
+            start_after: None,
+            limit: None,
+        })
+    }
+
+    pub fn pause_status(&self) -> Vec<PauseCategory> {
+        let res: PauseStatusResponse = self.query(PauseQueryMsg::PauseStatus {});
+        res.paused
+    }
+
+    pub fn balance(&self, address: &str) -> Uint128 {
+        let res: BalanceResponse = self.query(QueryMsg::Cw20(Cw20QueryMsg::Balance {
+            address: address.to_string(),
+        }));
+        res.balance
+    }
+
+    pub fn total_supply(&self) -> Uint128 {
+        let res: TokenInfoResponse = self.query(QueryMsg::Cw20(Cw20QueryMsg::TokenInfo {}));
+        res.total_supply
+    }
+}
+
+pub fn verra_serials(serial_start: u128, serial_end: u128) -> RegistrySerials {
+    RegistrySerials {
+        registry: "Verra".to_string(),
+        serial_start: serial_start.into(),
+        serial_end: serial_end.into(),
+    }
+}
@@ -0,0 +1,243 @@
+//! Project tags: structured project type, methodology, SDG and co-benefit tags
+//! attested by the verification body, and records listed by them.
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+
+use super::suite::{Suite, SuiteBuilder, ADMIN, DEVELOPER, VERIFIER};
+use crate::error::ContractError;
+use crate::msg::ProjectTag;
+use crate::state::{CarbonCreditInfo, CoBenefit, Methodology, ProjectTags, ProjectType, Sdg};
+
+const OWN_PROJECT: &str = "VCS-674";
+const KATINGAN: &str = "VCS-1477";
+
+fn solar_tags() -> ProjectTags {
+    ProjectTags {
+        methodology: Methodology::Acm0002,
+        sdgs: vec![Sdg::AffordableEnergy, Sdg::ClimateAction],
+        co_benefits: vec![],
+    }
+}
+
+fn redd_tags() -> ProjectTags {
+    ProjectTags {
+        methodology: Methodology::Vm0007,
+        sdgs: vec![Sdg::ClimateAction, Sdg::LifeOnLand],
+        co_benefits: vec![CoBenefit::Biodiversity, CoBenefit::Community],
+    }
+}
+
+/// The contract's own project with verification v-1, and Katingan, verified
+/// by katingan-vvb for katingan-dev, with verification v-2.
+fn setup() -> Suite {
+    let mut suite = SuiteBuilder::new().build();
+    suite
+        .register_project(
+            ADMIN,
+            CarbonCreditInfo {
+                project_id: KATINGAN.to_string(),
+                project_name: "Katingan Mentaya".to_string(),
+                project_type: ProjectType::ForestConservation,
+                verification_standard: "VCS".to_string(),
+                vintage_year: 2021,
+                country: "Indonesia".to_string(),
+                total_credits_issued: Uint128::zero(),
+                credits_retired: Uint128::zero(),
+                credits_available: Uint128::zero(),
+                co2_equivalent_per_credit: Decimal::one(),
+                credits_buffered: Uint128::zero(),
+                verification_body: Addr::unchecked("katingan-vvb"),
+                project_developer: Addr::unchecked("katingan-dev"),
+            },
+        )
+        .unwrap();
+    suite.verify_credits(VERIFIER, "v-1", 500).unwrap();
+    suite
+        .verify_project_credits("katingan-vvb", KATINGAN, "v-2", 300)
+        .unwrap();
+    suite
+}
+
+#[test]
+fn tags_are_set_by_the_developer_and_attested_by_the_verification_body() {
+    let mut suite = setup();
+
+    let err = suite
+        .set_project_tags(VERIFIER, None, solar_tags())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotProjectDeveloper {
+            action: "tag the project".to_string()
+        },
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .set_project_tags(DEVELOPER, Some(KATINGAN), redd_tags())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotProjectDeveloper {
+            action: format!("tag project {KATINGAN}")
+        },
+        err.downcast().unwrap()
+    );
+    let mut tags = solar_tags();
+    tags.sdgs.push(Sdg::ClimateAction);
+    let err = suite.set_project_tags(DEVELOPER, None, tags).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidProjectTags {
+            reason: "SDG ClimateAction is listed twice".to_string()
+        },
+        err.downcast().unwrap()
+    );
+    let err = suite.attest_project_tags(VERIFIER, None, 1).unwrap_err();
+    assert_eq!(
+        ContractError::ProjectNotTagged {
+            project_id: OWN_PROJECT.to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .set_project_tags(DEVELOPER, None, solar_tags())
+        .unwrap();
+    let record = suite.project_tags(None).unwrap();
+    assert_eq!(record.project_type, ProjectType::ForestConservation);
+    assert_eq!(record.tags, solar_tags());
+    assert_eq!(record.revision, 1);
+    assert_eq!(record.attestation, None);
+
+    let err = suite.attest_project_tags(DEVELOPER, None, 1).unwrap_err();
+    assert_eq!(
+        ContractError::NotVerificationBody {
+            action: "attest the project tags".to_string()
+        },
+        err.downcast().unwrap()
+    );
+    let err = suite.attest_project_tags(VERIFIER, None, 2).unwrap_err();
+    assert_eq!(
+        ContractError::StaleTagRevision {
+            project_id: OWN_PROJECT.to_string(),
+            revision: 2,
+            current: 1,
+        },
+        err.downcast().unwrap()
+    );
+    suite.attest_project_tags(VERIFIER, None, 1).unwrap();
+    let record = suite.project_tags(None).unwrap();
+    assert_eq!(record.revision, 2);
+    let attestation = record.attestation.unwrap();
+    assert_eq!(attestation.attested_by, Addr::unchecked(VERIFIER));
+    assert_eq!(attestation.report_url, "https://registry.example/tags/1");
+
+    // Changing the tags sets the attestation aside, and every revision is kept
+    let mut tags = solar_tags();
+    tags.co_benefits.push(CoBenefit::Health);
+    suite
+        .set_project_tags(DEVELOPER, None, tags.clone())
+        .unwrap();
+    let record = suite.project_tags(None).unwrap();
+    assert_eq!(record.revision, 3);
+    assert_eq!(record.attestation, None);
+
+    let history = suite.tag_history(None);
+    assert_eq!(
+        history
+            .iter()
+            .map(|record| record.revision)
+            .collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    assert_eq!(history[0].tags, solar_tags());
+    assert_eq!(history[0].attestation, None);
+    assert_eq!(history[1].updated_by, Addr::unchecked(VERIFIER));
+    assert!(history[1].attestation.is_some());
+    assert_eq!(history[2].tags, tags);
+
+    // A registered project's tags are its own parties' to set and attest
+    suite
+        .set_project_tags("katingan-dev", Some(KATINGAN), redd_tags())
+        .unwrap();
+    let err = suite
+        .attest_project_tags(VERIFIER, Some(KATINGAN), 1)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotVerificationBody {
+            action: format!("attest the tags of project {KATINGAN}")
+        },
+        err.downcast().unwrap()
+    );
+    suite
+        .attest_project_tags("katingan-vvb", Some(KATINGAN), 1)
+        .unwrap();
+    assert_eq!(suite.tag_history(Some(KATINGAN)).len(), 2);
+    assert_eq!(suite.project_tags(None).unwrap().revision, 3);
+}
+
+#[test]
+fn records_are_listed_by_the_tags_of_their_project() {
+    let mut suite = setup();
+    suite
+        .set_project_tags(DEVELOPER, None, solar_tags())
+        .unwrap();
+    suite
+        .set_project_tags("katingan-dev", Some(KATINGAN), redd_tags())
+        .unwrap();
+    suite
+        .attest_project_tags("katingan-vvb", Some(KATINGAN), 1)
+        .unwrap();
+    suite.retire_credits(DEVELOPER, "r-1", 10).unwrap();
+    suite
+        .retire_project_credits("katingan-dev", KATINGAN, "r-2", 20)
+        .unwrap();
+
+    let climate_action = ProjectTag::Sdg(Sdg::ClimateAction);
+    assert_eq!(
+        suite.projects_by_tag(climate_action.clone(), false),
+        [KATINGAN, OWN_PROJECT]
+    );
+    assert_eq!(
+        suite.projects_by_tag(climate_action.clone(), true),
+        [KATINGAN]
+    );
+    assert_eq!(
+        suite.verifications_by_tag(climate_action.clone(), false),
+        ["v-1", "v-2"]
+    );
+    assert_eq!(suite.verifications_by_tag(climate_action, true), ["v-2"]);
+    assert_eq!(
+        suite.verifications_by_tag(ProjectTag::CoBenefit(CoBenefit::Biodiversity), false),
+        ["v-2"]
+    );
+
+    // Both projects are forest conservation projects by their info
+    assert_eq!(
+        suite.retirements_by_tag(
+            ProjectTag::ProjectType(ProjectType::ForestConservation),
+            false
+        ),
+        ["r-1", "r-2"]
+    );
+    assert!(suite
+        .retirements_by_tag(ProjectTag::ProjectType(ProjectType::RenewableEnergy), false)
+        .is_empty());
+    assert_eq!(
+        suite.retirements_by_tag(ProjectTag::Methodology(Methodology::Vm0007), true),
+        ["r-2"]
+    );
+    assert!(suite
+        .retirements_by_tag(ProjectTag::CoBenefit(CoBenefit::Water), false)
+        .is_empty());
+
+    // Projects are listed by their current tags only
+    let mut tags = solar_tags();
+    tags.sdgs = vec![Sdg::AffordableEnergy];
+    suite.set_project_tags(DEVELOPER, None, tags).unwrap();
+    assert_eq!(
+        suite.projects_by_tag(ProjectTag::Sdg(Sdg::ClimateAction), false),
+        [KATINGAN]
+    );
+    assert_eq!(
+        suite.retirements_by_tag(ProjectTag::Sdg(Sdg::AffordableEnergy), false),
+        ["r-1"]
+    );
+}
@@ -0,0 +1,830 @@
+use cosmwasm_std::{from_json, Addr, Event, Uint128};
+use rwa_common::pause::GUARDIAN;
+use rwa_common::{CommonError, PauseCategory};
+
+use super::suite::{SuiteBuilder, ADMIN, DEVELOPER, VERIFIER};
+use crate::error::ContractError;
+use crate::msg::{CarbonCreditExecuteMsg, IssuanceRecipient};
+use crate::state::{CreditAllocation, VerificationStatus, PROJECT_DEVELOPER, VERIFICATION_BODY};
+
+#[test]
+fn verify_credits_mints_to_the_project_developer() {
+    let mut suite = SuiteBuilder::new().build();
+
+    suite.verify_credits(VERIFIER, "v-1", 500).unwrap();
+
+    let record = suite.verification_record("v-1");
+    assert_eq!(record.credits_verified, Uint128::new(500));
+    assert_eq!(record.verification_body, Addr::unchecked(VERIFIER));
+    assert_eq!(record.status, VerificationStatus::Verified);
+
+    let info = suite.carbon_credit_info();
+    assert_eq!(info.total_credits_issued, Uint128::new(500));
+    assert_eq!(info.credits_available, Uint128::new(500));
+    assert_eq!(suite.available_credits(), Uint128::new(500));
+
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(500));
+    assert_eq!(suite.total_supply(), Uint128::new(500));
+    assert_eq!(
+        record.issued_to,
+        vec![CreditAllocation {
+            recipient: Addr::unchecked(DEVELOPER),
+            credits: Uint128::new(500),
+        }]
+    );
+    assert!(suite.reconcile_supply().reconciled);
+}
+
+#[test]
+fn verify_credits_splits_issuance_between_recipients() {
+    let mut suite = SuiteBuilder::new().build();
+
+    suite
+        .verify_credits_to(VERIFIER, "v-1", &[("buyer", 300), (DEVELOPER, 200)])
+        .unwrap();
+
+    assert_eq!(suite.balance("buyer"), Uint128::new(300));
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(200));
+    assert_eq!(suite.available_credits(), Uint128::new(500));
+    assert_eq!(suite.verification_record("v-1").issued_to.len(), 2);
+    assert!(suite.reconcile_supply().reconciled);
+}
+
+#[test]
+fn verify_credits_recipients_must_cover_the_verified_credits() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite
+        .execute(
+            VERIFIER,
+            CarbonCreditExecuteMsg::VerifyCredits {
+                verification_id: Some("v-1".to_string()),
+                credits_to_verify: Uint128::new(500),
+                verification_report_url: "https://registry.example/v-1".to_string(),
+                vintage_year: None,
+                methodology_version: None,
+                registry_serials: None,
+                recipients: Some(vec![IssuanceRecipient {
+                    address: "buyer".to_string(),
+                    credits: Uint128::new(400),
+                }]),
+                forward_series: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::RecipientsMismatch {
+            allocated: Uint128::new(400),
+            verified: Uint128::new(500),
+        },
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.total_supply(), Uint128::zero());
+    assert_eq!(suite.available_credits(), Uint128::zero());
+}
+
+#[test]
+fn cw20_mint_is_disabled() {
+    let mut suite = SuiteBuilder::new().build();
+
+    // Even the configured minter cannot issue credits without a verification
+    let err = suite.mint(DEVELOPER, DEVELOPER, 100).unwrap_err();
+    assert_eq!(ContractError::MintDisabled {}, err.downcast().unwrap());
+    assert_eq!(suite.total_supply(), Uint128::zero());
+}
+
+#[test]
+fn cw20_burn_is_disabled() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+
+    // Credits only leave supply with a retirement record
+    let err = suite.burn(DEVELOPER, 100).unwrap_err();
+    assert_eq!(ContractError::BurnDisabled {}, err.downcast().unwrap());
+    assert_eq!(suite.total_supply(), Uint128::new(1_000));
+    assert_eq!(suite.available_credits(), Uint128::new(1_000));
+}
+
+#[test]
+fn verify_credits_requires_verification_body() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite.verify_credits(DEVELOPER, "v-1", 500).unwrap_err();
+    assert_eq!(
+        ContractError::NotVerificationBody {
+            action: "verify credits".to_string()
+        },
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.available_credits(), Uint128::zero());
+}
+
+#[test]
+fn retire_credits_burns_and_updates_counters() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+
+    suite.retire_credits(DEVELOPER, "r-1", 300).unwrap();
+
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(700));
+    assert_eq!(suite.total_supply(), Uint128::new(700));
+    assert_eq!(suite.available_credits(), Uint128::new(700));
+    assert_eq!(suite.retired_credits(), Uint128::new(300));
+
+    let record = suite.retirement_record("r-1");
+    assert_eq!(record.credits_retired, Uint128::new(300));
+    assert_eq!(record.retirement_entity, Addr::unchecked(DEVELOPER));
+}
+
+#[test]
+fn retire_credits_requires_balance() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+
+    let err = suite.retire_credits("buyer", "r-1", 10).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::InsufficientBalance {
+            needed: Uint128::new(10),
+            available: Uint128::zero(),
+        }),
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.total_supply(), Uint128::new(1_000));
+}
+
+#[test]
+fn retire_credits_cannot_exceed_available_credits() {
+    // Tokens minted through CW20 before issuance went through verification are
+    // in circulation without being counted as available credits
+    let mut suite = SuiteBuilder::new()
+        .with_unbacked_tokens(DEVELOPER, 100)
+        .build();
+
+    let err = suite.retire_credits(DEVELOPER, "r-1", 50).unwrap_err();
+    assert_eq!(
+        ContractError::ExceedsAvailableCredits {
+            requested: Uint128::new(50),
+            available: Uint128::zero(),
+        },
+        err.downcast().unwrap()
+    );
+
+    // The burn is rolled back with the rest of the message
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(100));
+    assert_eq!(suite.retired_credits(), Uint128::zero());
+}
+
+#[test]
+fn retire_credits_records_the_beneficiary() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+
+    suite
+        .retire_for(DEVELOPER, "r-1", 300, "acme", Some("Acme Corp"))
+        .unwrap();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(700));
+
+    let record = suite.retirement_record("r-1");
+    assert_eq!(record.retirement_entity, Addr::unchecked(DEVELOPER));
+    assert_eq!(record.beneficiary, Some(Addr::unchecked("acme")));
+    assert_eq!(record.beneficiary_name.as_deref(), Some("Acme Corp"));
+    assert_eq!(record.operator, None);
+}
+
+#[test]
+fn retire_credits_from_spends_an_allowance() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+
+    let err = suite
+        .retire_credits_from("broker", DEVELOPER, "r-1", 100, None)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Cw20Base(cw20_base::ContractError::NoAllowance {}),
+        err.downcast().unwrap()
+    );
+
+    suite.increase_allowance(DEVELOPER, "broker", 300).unwrap();
+    suite
+        .retire_credits_from("broker", DEVELOPER, "r-1", 200, Some("acme"))
+        .unwrap();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(800));
+    assert_eq!(suite.balance("broker"), Uint128::zero());
+    assert_eq!(suite.retired_credits(), Uint128::new(200));
+
+    let record = suite.retirement_record("r-1");
+    assert_eq!(record.retirement_entity, Addr::unchecked(DEVELOPER));
+    assert_eq!(record.beneficiary, Some(Addr::unchecked("acme")));
+    assert_eq!(record.operator, Some(Addr::unchecked("broker")));
+
+    // Only what is left of the allowance can be retired
+    suite
+        .retire_credits_from("broker", DEVELOPER, "r-2", 150, None)
+        .unwrap_err();
+    suite
+        .retire_credits_from("broker", DEVELOPER, "r-2", 100, None)
+        .unwrap();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(700));
+}
+
+#[test]
+fn update_verification_status() {
+    let mut suite = SuiteBuilder::new().build();
+    suite.verify_credits(VERIFIER, "v-1", 500).unwrap();
+
+    let err = suite
+        .update_verification_status(DEVELOPER, "v-1", VerificationStatus::Rejected)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotVerificationBody {
+            action: "update verification status".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .update_verification_status(VERIFIER, "v-2", VerificationStatus::Rejected)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::VerificationRecordNotFound {
+            verification_id: "v-2".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .update_verification_status(VERIFIER, "v-1", VerificationStatus::Expired)
+        .unwrap();
+    assert_eq!(
+        suite.verification_record("v-1").status,
+        VerificationStatus::Expired
+    );
+}
+
+#[test]
+fn pending_verification_mints_once_verified() {
+    let mut suite = SuiteBuilder::new().build();
+
+    suite.submit_verification(VERIFIER, "v-1", 500).unwrap();
+    assert_eq!(
+        suite.verification_record("v-1").status,
+        VerificationStatus::Pending
+    );
+    assert_eq!(suite.total_supply(), Uint128::zero());
+    assert_eq!(suite.available_credits(), Uint128::zero());
+
+    suite
+        .execute(
+            VERIFIER,
+            CarbonCreditExecuteMsg::UpdateVerificationStatus {
+                verification_id: "v-1".to_string(),
+                status: VerificationStatus::Verified,
+                reason: Some("site visit completed".to_string()),
+            },
+        )
+        .unwrap();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(500));
+    assert_eq!(suite.available_credits(), Uint128::new(500));
+    assert!(suite.reconcile_supply().reconciled);
+
+    // Each transition is logged with who made it and why
+    let history = suite.verification_record("v-1").history;
+    let statuses: Vec<_> = history.iter().map(|change| change.status.clone()).collect();
+    assert_eq!(
+        statuses,
+        [VerificationStatus::Pending, VerificationStatus::Verified]
+    );
+    assert_eq!(history[1].changed_by, Addr::unchecked(VERIFIER));
+    assert_eq!(history[1].reason.as_deref(), Some("site visit completed"));
+}
+
+#[test]
+fn only_allowed_status_transitions_are_accepted() {
+    let mut suite = SuiteBuilder::new().build();
+    suite.submit_verification(VERIFIER, "v-1", 500).unwrap();
+
+    let err = suite
+        .update_verification_status(VERIFIER, "v-1", VerificationStatus::Expired)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidStatusTransition {
+            from: VerificationStatus::Pending,
+            to: VerificationStatus::Expired,
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .update_verification_status(VERIFIER, "v-1", VerificationStatus::Rejected)
+        .unwrap();
+    assert_eq!(suite.total_supply(), Uint128::zero());
+
+    // A rejection is final
+    let err = suite
+        .update_verification_status(VERIFIER, "v-1", VerificationStatus::Verified)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidStatusTransition {
+            from: VerificationStatus::Rejected,
+            to: VerificationStatus::Verified,
+        },
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.total_supply(), Uint128::zero());
+    assert_eq!(suite.available_credits(), Uint128::zero());
+}
+
+#[test]
+fn expiry_burns_the_batch_wherever_it_is_held() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    suite.verify_credits(VERIFIER, "v-1", 500).unwrap();
+    suite.transfer(DEVELOPER, "buyer", 1_400).unwrap();
+    suite.transfer_batch("buyer", 1, "broker", 150).unwrap();
+    suite.retire_credits(DEVELOPER, "r-1", 50).unwrap();
+
+    // Of the 500 credits the record issued, 50 were retired and the rest are
+    // spread over three holders
+    suite
+        .update_verification_status(VERIFIER, "v-1", VerificationStatus::Expired)
+        .unwrap();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::zero());
+    assert_eq!(suite.balance("broker"), Uint128::zero());
+    assert_eq!(suite.balance("buyer"), Uint128::new(1_000));
+    assert_eq!(suite.batch_balances("buyer"), [(0, 1_000)]);
+    assert_eq!(suite.total_supply(), Uint128::new(1_000));
+    let info = suite.carbon_credit_info();
+    assert_eq!(info.total_credits_issued, Uint128::new(1_050));
+    assert_eq!(info.credits_available, Uint128::new(1_000));
+    assert_eq!(info.credits_retired, Uint128::new(50));
+    assert!(suite.reconcile_supply().reconciled);
+
+    // An expired record stays expired
+    let err = suite
+        .update_verification_status(VERIFIER, "v-1", VerificationStatus::Verified)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidStatusTransition {
+            from: VerificationStatus::Expired,
+            to: VerificationStatus::Verified,
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn record_queries_paginate() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    for id in ["v-1", "v-2", "v-3"] {
+        suite.verify_credits(VERIFIER, id, 10).unwrap();
+    }
+    for id in ["r-1", "r-2"] {
+        suite.retire_credits(DEVELOPER, id, 10).unwrap();
+    }
+
+    let page = suite.all_verification_records(None, Some(2));
+    let ids: Vec<_> = page.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["v-1", "v-2"]);
+
+    let page = suite.all_verification_records(Some("v-2"), None);
+    let ids: Vec<_> = page.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["v-3"]);
+
+    let page = suite.all_retirement_records(Some("r-1"), None);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].1.retirement_id, "r-2");
+}
+
+#[test]
+fn record_ids_cannot_be_reused() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    suite.verify_credits(VERIFIER, "v-1", 500).unwrap();
+    suite.retire_credits(DEVELOPER, "r-1", 100).unwrap();
+
+    let err = suite.verify_credits(VERIFIER, "v-1", 900).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::DuplicateRecordId {
+            id: "v-1".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+    assert_eq!(
+        suite.verification_record("v-1").credits_verified,
+        Uint128::new(500)
+    );
+
+    let err = suite.retire_credits(DEVELOPER, "r-1", 200).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::DuplicateRecordId {
+            id: "r-1".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.retired_credits(), Uint128::new(100));
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(1_400));
+}
+
+#[test]
+fn record_ids_are_generated_when_omitted() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    let verify = CarbonCreditExecuteMsg::VerifyCredits {
+        verification_id: None,
+        credits_to_verify: Uint128::new(10),
+        verification_report_url: "https://registry.example/report".to_string(),
+        recipients: None,
+        vintage_year: None,
+        methodology_version: None,
+        registry_serials: None,
+        forward_series: None,
+    };
+
+    let res = suite.execute(VERIFIER, verify.clone()).unwrap();
+    let id: String = from_json(res.data.unwrap()).unwrap();
+    assert_eq!(id, "00000000000000000001");
+    assert_eq!(
+        suite.verification_record(&id).credits_verified,
+        Uint128::new(10)
+    );
+
+    // Generated ids skip over ones a caller already chose
+    suite
+        .verify_credits(VERIFIER, "00000000000000000002", 10)
+        .unwrap();
+    let res = suite.execute(VERIFIER, verify).unwrap();
+    let id: String = from_json(res.data.unwrap()).unwrap();
+    assert_eq!(id, "00000000000000000003");
+
+    // Each collection counts on its own, and explicit ids are echoed back
+    let res = suite
+        .execute(
+            DEVELOPER,
+            CarbonCreditExecuteMsg::RetireCredits {
+                retirement_id: None,
+                credits_to_retire: Uint128::new(5),
+                retirement_purpose: "offset".to_string(),
+                retirement_certificate_url: "https://registry.example/cert".to_string(),
+                batch_id: None,
+                registry_serials: None,
+                beneficiary: None,
+                beneficiary_name: None,
+                retirement_use: None,
+            },
+        )
+        .unwrap();
+    let id: String = from_json(res.data.unwrap()).unwrap();
+    assert_eq!(id, "00000000000000000001");
+
+    let res = suite.retire_credits(DEVELOPER, "r-1", 5).unwrap();
+    let id: String = from_json(res.data.unwrap()).unwrap();
+    assert_eq!(id, "r-1");
+}
+
+#[test]
+fn roles_are_seeded_from_instantiate() {
+    let suite = SuiteBuilder::new().build();
+
+    let admin = suite.role_admin();
+    assert_eq!(admin.admin, Addr::unchecked(ADMIN));
+    assert_eq!(admin.pending_admin, None);
+    assert_eq!(
+        suite.role_members(VERIFICATION_BODY, None, None),
+        vec![Addr::unchecked(VERIFIER)]
+    );
+    assert_eq!(
+        suite.role_members(PROJECT_DEVELOPER, None, None),
+        vec![Addr::unchecked(DEVELOPER)]
+    );
+}
+
+#[test]
+fn granted_roles_can_be_used_and_revoked() {
+    let mut suite = SuiteBuilder::new().build();
+
+    // A role can have several holders
+    suite
+        .grant_role(ADMIN, VERIFICATION_BODY, "second-verifier")
+        .unwrap();
+    assert!(suite.has_role(VERIFICATION_BODY, VERIFIER));
+    assert!(suite.has_role(VERIFICATION_BODY, "second-verifier"));
+    suite.verify_credits(VERIFIER, "v-1", 100).unwrap();
+    suite.verify_credits("second-verifier", "v-2", 200).unwrap();
+    assert_eq!(suite.available_credits(), Uint128::new(300));
+
+    suite
+        .revoke_role(ADMIN, VERIFICATION_BODY, VERIFIER)
+        .unwrap();
+    assert!(!suite.has_role(VERIFICATION_BODY, VERIFIER));
+    let err = suite.verify_credits(VERIFIER, "v-3", 100).unwrap_err();
+    assert_eq!(
+        ContractError::NotVerificationBody {
+            action: "verify credits".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    // Revoking a role that is not held is an error
+    let err = suite
+        .revoke_role(ADMIN, VERIFICATION_BODY, VERIFIER)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::RoleNotHeld {
+            role: VERIFICATION_BODY.to_string(),
+            address: VERIFIER.to_string(),
+        }),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn only_the_role_admin_grants_and_revokes() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite
+        .grant_role(VERIFIER, VERIFICATION_BODY, "second-verifier")
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "grant roles".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .revoke_role(DEVELOPER, VERIFICATION_BODY, VERIFIER)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "revoke roles".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+
+    let err = suite.grant_role(ADMIN, "auditor", "someone").unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::UnknownRole {
+            role: "auditor".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn role_transfer_takes_two_steps() {
+    let mut suite = SuiteBuilder::new().build();
+
+    // The holder proposes handing over their seat
+    suite
+        .propose_role_transfer(VERIFIER, VERIFICATION_BODY, VERIFIER, "new-verifier")
+        .unwrap();
+    let pending = suite.pending_role_transfers(VERIFICATION_BODY).transfers;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].from, Addr::unchecked(VERIFIER));
+    assert_eq!(pending[0].to, Addr::unchecked("new-verifier"));
+
+    // Until it is accepted nothing changes
+    assert!(suite.has_role(VERIFICATION_BODY, VERIFIER));
+    assert!(!suite.has_role(VERIFICATION_BODY, "new-verifier"));
+
+    // Only the proposed holder can accept
+    let err = suite
+        .accept_role_transfer("intruder", VERIFICATION_BODY, VERIFIER)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotProposedHolder {
+            role: VERIFICATION_BODY.to_string()
+        }),
+        err.downcast().unwrap()
+    );
+
+    suite
+        .accept_role_transfer("new-verifier", VERIFICATION_BODY, VERIFIER)
+        .unwrap();
+    assert_eq!(
+        suite.role_members(VERIFICATION_BODY, None, None),
+        vec![Addr::unchecked("new-verifier")]
+    );
+    assert!(suite
+        .pending_role_transfers(VERIFICATION_BODY)
+        .transfers
+        .is_empty());
+    suite.verify_credits("new-verifier", "v-1", 100).unwrap();
+    suite.verify_credits(VERIFIER, "v-2", 100).unwrap_err();
+}
+
+#[test]
+fn role_transfer_can_be_cancelled() {
+    let mut suite = SuiteBuilder::new().build();
+
+    // Neither the holder nor the admin, so it cannot propose
+    let err = suite
+        .propose_role_transfer(DEVELOPER, VERIFICATION_BODY, VERIFIER, DEVELOPER)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdminOrHolder {
+            action: "propose role transfers".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+
+    // The admin proposes on the holder's behalf and then changes its mind
+    suite
+        .propose_role_transfer(ADMIN, VERIFICATION_BODY, VERIFIER, "new-verifier")
+        .unwrap();
+    suite
+        .cancel_role_transfer(ADMIN, VERIFICATION_BODY, VERIFIER)
+        .unwrap();
+
+    let err = suite
+        .accept_role_transfer("new-verifier", VERIFICATION_BODY, VERIFIER)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::RoleTransferNotFound {
+            role: VERIFICATION_BODY.to_string(),
+            from: VERIFIER.to_string(),
+        }),
+        err.downcast().unwrap()
+    );
+    assert!(suite.has_role(VERIFICATION_BODY, VERIFIER));
+}
+
+#[test]
+fn role_admin_transfer_takes_two_steps() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite.propose_role_admin(VERIFIER, VERIFIER).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "propose a new admin".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+
+    suite.propose_role_admin(ADMIN, "new-admin").unwrap();
+    assert_eq!(
+        suite.role_admin().pending_admin,
+        Some(Addr::unchecked("new-admin"))
+    );
+
+    let err = suite.accept_role_admin(VERIFIER).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotPendingRoleAdmin {}),
+        err.downcast().unwrap()
+    );
+
+    suite.accept_role_admin("new-admin").unwrap();
+    let admin = suite.role_admin();
+    assert_eq!(admin.admin, Addr::unchecked("new-admin"));
+    assert_eq!(admin.pending_admin, None);
+
+    // The old admin has lost its powers
+    suite
+        .grant_role(ADMIN, VERIFICATION_BODY, "second-verifier")
+        .unwrap_err();
+    suite
+        .grant_role("new-admin", VERIFICATION_BODY, "second-verifier")
+        .unwrap();
+}
+
+#[test]
+fn role_members_paginate() {
+    let mut suite = SuiteBuilder::new().build();
+    for verifier in ["verifier-a", "verifier-b", "verifier-c"] {
+        suite
+            .grant_role(ADMIN, VERIFICATION_BODY, verifier)
+            .unwrap();
+    }
+
+    let page = suite.role_members(VERIFICATION_BODY, None, Some(2));
+    assert_eq!(
+        page,
+        vec![Addr::unchecked("verifier"), Addr::unchecked("verifier-a")]
+    );
+    let page = suite.role_members(VERIFICATION_BODY, Some("verifier-a"), Some(2));
+    assert_eq!(
+        page,
+        vec![Addr::unchecked("verifier-b"), Addr::unchecked("verifier-c")]
+    );
+    assert!(suite
+        .role_members(VERIFICATION_BODY, Some("verifier-c"), None)
+        .is_empty());
+}
+
+#[test]
+fn guardian_pauses_selected_categories() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    suite.grant_role(ADMIN, GUARDIAN, "guardian").unwrap();
+
+    let err = suite
+        .pause(DEVELOPER, Some(vec![PauseCategory::Transfers]))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotGuardian {
+            action: "pause".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+
+    let res = suite
+        .pause("guardian", Some(vec![PauseCategory::Transfers]))
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm-paused").add_attribute("category", "transfers")));
+    assert_eq!(suite.pause_status(), vec![PauseCategory::Transfers]);
+
+    let err = suite.transfer(DEVELOPER, "buyer", 100).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::Paused {
+            category: PauseCategory::Transfers
+        }),
+        err.downcast().unwrap()
+    );
+
+    // Everything outside the paused category keeps working
+    suite.verify_credits(VERIFIER, "v-1", 500).unwrap();
+    suite.retire_credits(DEVELOPER, "r-1", 100).unwrap();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(1_400));
+}
+
+#[test]
+fn pause_everything_and_unpause_by_admin() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    suite.grant_role(ADMIN, GUARDIAN, "guardian").unwrap();
+    suite.verify_credits(VERIFIER, "v-1", 500).unwrap();
+
+    suite.pause("guardian", None).unwrap();
+    assert_eq!(suite.pause_status(), PauseCategory::ALL.to_vec());
+
+    let err = suite.verify_credits(VERIFIER, "v-2", 500).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::Paused {
+            category: PauseCategory::Issuance
+        }),
+        err.downcast().unwrap()
+    );
+    let err = suite.retire_credits(DEVELOPER, "r-1", 100).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::Paused {
+            category: PauseCategory::Retirement
+        }),
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .update_verification_status(VERIFIER, "v-1", VerificationStatus::Rejected)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::Paused {
+            category: PauseCategory::RecordKeeping
+        }),
+        err.downcast().unwrap()
+    );
+    suite.transfer(DEVELOPER, "buyer", 100).unwrap_err();
+
+    // The guardian cannot lift the pause, only the admin can
+    let err = suite.unpause("guardian", None).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "unpause".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+
+    let res = suite
+        .unpause(ADMIN, Some(vec![PauseCategory::Issuance]))
+        .unwrap();
+    assert!(res.has_event(&Event::new("wasm-unpaused").add_attribute("category", "issuance")));
+    suite.verify_credits(VERIFIER, "v-2", 500).unwrap();
+    suite.transfer(DEVELOPER, "buyer", 100).unwrap_err();
+
+    suite.unpause(ADMIN, None).unwrap();
+    assert!(suite.pause_status().is_empty());
+    suite.transfer(DEVELOPER, "buyer", 100).unwrap();
+}
+
+#[test]
+fn cw20_messages_pass_through() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+
+    suite.transfer(DEVELOPER, "buyer", 250).unwrap();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(750));
+    assert_eq!(suite.balance("buyer"), Uint128::new(250));
+
+    // The buyer can retire what they hold
+    suite.retire_credits("buyer", "r-1", 250).unwrap();
+    assert_eq!(suite.balance("buyer"), Uint128::zero());
+    assert_eq!(suite.retired_credits(), Uint128::new(250));
+}
+
+#[test]
+fn migrate_keeps_state() {
+    let mut suite = SuiteBuilder::new().with_credits(DEVELOPER, 1_000).build();
+    let info = suite.carbon_credit_info();
+
+    suite
+        .grant_role(ADMIN, VERIFICATION_BODY, "second-verifier")
+        .unwrap();
+
+    suite.migrate(ADMIN).unwrap();
+    assert_eq!(suite.carbon_credit_info(), info);
+
+    // An already seeded role registry is left alone
+    assert_eq!(
+        suite.role_members(VERIFICATION_BODY, None, None),
+        vec![
+            Addr::unchecked("second-verifier"),
+            Addr::unchecked(VERIFIER)
+        ]
+    );
+}
@@ -0,0 +1,139 @@
+//! Registry mode.
+//!
+//! Besides the project behind its CW20 token, the contract can hold any number
+//! of projects registered by the role admin, each with its own verification
+//! body, developer and counters. Their credits are not CW20 tokens: balances
+//! are kept per owner and project, like the token ids of a CW1155 contract,
+//! and an owner can approve operators to move all of its project credits.
+
+use cosmwasm_std::{Addr, Empty, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::Bound;
+use rwa_common::{DEFAULT_LIMIT, MAX_LIMIT};
+
+use crate::error::ContractError;
+use crate::msg::ProjectAmount;
+use crate::state::{
+    CarbonCreditInfo, CARBON_CREDIT_INFO, PROJECTS, PROJECT_BALANCES, PROJECT_OPERATORS,
+};
+
+/// Registers `project` with its counters at zero. Its id must differ from
+/// every registered project's and from the contract's own project's.
+pub fn register_project(
+    storage: &mut dyn Storage,
+    mut project: CarbonCreditInfo,
+) -> Result<CarbonCreditInfo, ContractError> {
+    let own_project = CARBON_CREDIT_INFO.load(storage)?;
+    if project.project_id == own_project.project_id || PROJECTS.has(storage, &project.project_id) {
+        return Err(ContractError::ProjectExists {
+            project_id: project.project_id,
+        });
+    }
+
+    // Credits only come into a project through verification
+    project.total_credits_issued = Uint128::zero();
+    project.credits_retired = Uint128::zero();
+    project.credits_available = Uint128::zero();
+    project.credits_buffered = Uint128::zero();
+    PROJECTS.save(storage, &project.project_id, &project)?;
+    Ok(project)
+}
+
+pub fn load_project(
+    storage: &dyn Storage,
+    project_id: &str,
+) -> Result<CarbonCreditInfo, ContractError> {
+    PROJECTS
+        .may_load(storage, project_id)?
+        .ok_or_else(|| ContractError::ProjectNotFound {
+            project_id: project_id.to_string(),
+        })
+}
+
+/// The credits of `project_id` held by `owner`.
+pub fn project_balance(
+    storage: &dyn Storage,
+    owner: &Addr,
+    project_id: &str,
+) -> StdResult<Uint128> {
+    Ok(PROJECT_BALANCES
+        .may_load(storage, (owner, project_id))?
+        .unwrap_or_default())
+}
+
+/// Adds `amount` of `project_id` to `owner`.
+pub fn credit_project(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    project_id: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    PROJECT_BALANCES.update(storage, (owner, project_id), |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default().checked_add(amount)?)
+    })?;
+    Ok(())
+}
+
+/// Takes `amount` of `project_id` out of `owner`.
+pub fn debit_project(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    project_id: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let available = project_balance(storage, owner, project_id)?;
+    if available < amount {
+        return Err(ContractError::InsufficientProjectBalance {
+            project_id: project_id.to_string(),
+            requested: amount,
+            available,
+        });
+    }
+    let key = (owner, project_id);
+    if available == amount {
+        PROJECT_BALANCES.remove(storage, key);
+    } else {
+        PROJECT_BALANCES.save(storage, key, &(available - amount))?;
+    }
+    Ok(())
+}
+
+/// Up to `limit` of `owner`'s non-empty project balances in project id order,
+/// starting after `start_after`.
+pub fn project_balances(
+    storage: &dyn Storage,
+    owner: &Addr,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ProjectAmount>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    PROJECT_BALANCES
+        .prefix(owner)
+        .range(
+            storage,
+            start_after.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(project_id, amount)| ProjectAmount { project_id, amount }))
+        .collect()
+}
+
+/// Lets `operator` move all of `owner`'s project credits, or stops it.
+pub fn set_operator(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    operator: &Addr,
+    approved: bool,
+) -> StdResult<()> {
+    if approved {
+        PROJECT_OPERATORS.save(storage, (owner, operator), &Empty {})
+    } else {
+        PROJECT_OPERATORS.remove(storage, (owner, operator));
+        Ok(())
+    }
+}
+
+pub fn is_operator(storage: &dyn Storage, owner: &Addr, operator: &Addr) -> bool {
+    owner == operator || PROJECT_OPERATORS.has(storage, (owner, operator))
+}
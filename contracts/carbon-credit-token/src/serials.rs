@@ -0,0 +1,300 @@
+//! Serial numbers.
+//!
+//! Every credit has a serial number in the contract's own numbering, assigned
+//! batch by batch. A verification can also name the block of serials its
+//! credits carry in an off-chain registry; the blocks registered for each
+//! registry never overlap, so the same registry credits cannot be tokenized
+//! twice. Retirements consume serial ranges, either the registry serials the
+//! caller names or the lowest serials left in each batch drawn from.
+
+use cosmwasm_std::{Order, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::Bound;
+
+use crate::batches::load_batch;
+use crate::error::ContractError;
+use crate::msg::{SerialState, SerialStatusResponse};
+use crate::state::{
+    Batch, RegistryBlock, RegistrySerials, RetiredSerials, SerialRange, VerificationStatus,
+    BATCHES, REGISTRY_BLOCKS, RETIRED_SERIALS, RETIREMENTS, VERIFICATION_RECORDS,
+};
+
+/// Reserves a verification's registry block, rejecting one that overlaps a
+/// block already tokenized.
+pub fn register_block(
+    storage: &mut dyn Storage,
+    serials: &RegistrySerials,
+    credits: Uint128,
+    verification_id: &str,
+) -> Result<(), ContractError> {
+    let block_size = serials
+        .serial_end
+        .checked_sub(serials.serial_start)
+        .ok()
+        .and_then(|span| span.checked_add(Uint128::one()).ok());
+    if serials.registry.is_empty() || block_size != Some(credits) {
+        return Err(ContractError::SerialBlockMismatch {
+            serial_start: serials.serial_start,
+            serial_end: serials.serial_end,
+            credits,
+        });
+    }
+
+    if let Some((_, block)) = find_block(storage, &serials.registry, serials.serial_end)? {
+        if block.serial_end >= serials.serial_start {
+            return Err(ContractError::SerialBlockTokenized {
+                registry: serials.registry.clone(),
+                serial_start: serials.serial_start,
+                serial_end: serials.serial_end,
+                verification_id: block.verification_id,
+            });
+        }
+    }
+
+    REGISTRY_BLOCKS.save(
+        storage,
+        (&serials.registry, serials.serial_start.u128()),
+        &RegistryBlock {
+            serial_end: serials.serial_end,
+            verification_id: verification_id.to_string(),
+        },
+    )?;
+    Ok(())
+}
+
+/// Frees a rejected verification's registry block for tokenization.
+pub fn release_block(storage: &mut dyn Storage, serials: &RegistrySerials) {
+    REGISTRY_BLOCKS.remove(storage, (&serials.registry, serials.serial_start.u128()));
+}
+
+/// The block of `registry` starting closest below or at `serial`, with its
+/// first serial.
+fn find_block(
+    storage: &dyn Storage,
+    registry: &str,
+    serial: Uint128,
+) -> StdResult<Option<(Uint128, RegistryBlock)>> {
+    let block = REGISTRY_BLOCKS
+        .prefix(registry)
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(serial.u128())),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?;
+    Ok(block.map(|(serial_start, block)| (Uint128::new(serial_start), block)))
+}
+
+/// The batch that numbered `serial` in the contract's own numbering.
+fn find_batch(storage: &dyn Storage, serial: Uint128) -> StdResult<Option<Batch>> {
+    // Batches number their credits in id order, so the ids can be bisected
+    let Some(mut high) = BATCHES
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+    else {
+        return Ok(None);
+    };
+    let mut low = 0;
+    while low <= high {
+        let mid = low + (high - low) / 2;
+        let Some(batch) = BATCHES.may_load(storage, mid)? else {
+            // Only the base batch may be missing
+            low = mid + 1;
+            continue;
+        };
+        if serial < batch.first_serial {
+            if mid == 0 {
+                break;
+            }
+            high = mid - 1;
+        } else if serial > batch.last_serial {
+            low = mid + 1;
+        } else {
+            return Ok(Some(batch));
+        }
+    }
+    Ok(None)
+}
+
+/// Turns registry serials into the contract's own numbering. They must lie
+/// within the block of a single verified record.
+pub fn resolve_registry_serials(
+    storage: &dyn Storage,
+    serials: &RegistrySerials,
+) -> Result<(u64, SerialRange), ContractError> {
+    let not_retirable = || ContractError::SerialsNotRetirable {
+        registry: serials.registry.clone(),
+        serial_start: serials.serial_start,
+        serial_end: serials.serial_end,
+    };
+
+    let (block_start, block) =
+        find_block(storage, &serials.registry, serials.serial_start)?.ok_or_else(not_retirable)?;
+    if serials.serial_end < serials.serial_start || serials.serial_end > block.serial_end {
+        return Err(not_retirable());
+    }
+    let record = VERIFICATION_RECORDS.load(storage, &block.verification_id)?;
+    let batch_id = match (record.status, record.batch_id) {
+        (VerificationStatus::Verified, Some(batch_id)) => batch_id,
+        _ => return Err(not_retirable()),
+    };
+
+    let batch = load_batch(storage, batch_id)?;
+    let offset = serials.serial_start - block_start;
+    let first = batch.first_serial + offset;
+    let last = first + (serials.serial_end - serials.serial_start);
+    Ok((batch_id, SerialRange { first, last }))
+}
+
+/// Marks `range` retired, rejecting serials that already are.
+pub fn retire_range(
+    storage: &mut dyn Storage,
+    range: &SerialRange,
+    retirement_id: &str,
+) -> Result<(), ContractError> {
+    if let Some((first, retired)) = find_retired(storage, range.last)? {
+        if retired.last_serial >= range.first {
+            return Err(ContractError::SerialsAlreadyRetired {
+                serial: range.first.max(first),
+                retirement_id: retired.retirement_id,
+            });
+        }
+    }
+    RETIRED_SERIALS.save(
+        storage,
+        range.first.u128(),
+        &RetiredSerials {
+            last_serial: range.last,
+            retirement_id: retirement_id.to_string(),
+        },
+    )?;
+    Ok(())
+}
+
+/// Retires the lowest `credits` serials of `batch_id` not retired yet.
+pub fn retire_lowest(
+    storage: &mut dyn Storage,
+    batch_id: u64,
+    credits: Uint128,
+    retirement_id: &str,
+) -> Result<Vec<SerialRange>, ContractError> {
+    let batch = load_batch(storage, batch_id)?;
+
+    // Collect the gaps between the ranges already retired
+    let mut ranges = vec![];
+    let mut remaining = credits;
+    let mut cursor = batch.first_serial;
+    let retired = RETIRED_SERIALS
+        .range(
+            storage,
+            Some(Bound::inclusive(batch.first_serial.u128())),
+            Some(Bound::inclusive(batch.last_serial.u128())),
+            Order::Ascending,
+        )
+        .map(|item| item.map(|(first, retired)| (Uint128::new(first), retired.last_serial)))
+        .chain(std::iter::once(Ok((
+            batch.last_serial + Uint128::one(),
+            batch.last_serial,
+        ))));
+    for item in retired {
+        if remaining.is_zero() {
+            break;
+        }
+        let (first, last) = item?;
+        if first > cursor {
+            let take = (first - cursor).min(remaining);
+            ranges.push(SerialRange {
+                first: cursor,
+                last: cursor + take - Uint128::one(),
+            });
+            remaining -= take;
+        }
+        cursor = cursor.max(last + Uint128::one());
+    }
+    if !remaining.is_zero() {
+        return Err(StdError::generic_err(format!("batch {batch_id} has no serials left")).into());
+    }
+
+    for range in &ranges {
+        retire_range(storage, range, retirement_id)?;
+    }
+    Ok(ranges)
+}
+
+/// The retired range starting closest below or at `serial`, with its first
+/// serial.
+fn find_retired(
+    storage: &dyn Storage,
+    serial: Uint128,
+) -> StdResult<Option<(Uint128, RetiredSerials)>> {
+    let retired = RETIRED_SERIALS
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(serial.u128())),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?;
+    Ok(retired.map(|(first, retired)| (Uint128::new(first), retired)))
+}
+
+/// Where `serial` stands, in a registry's numbering or the contract's own.
+pub fn serial_status(
+    storage: &dyn Storage,
+    registry: Option<String>,
+    serial: Uint128,
+) -> StdResult<SerialStatusResponse> {
+    let not_found = || StdError::not_found(format!("serial {serial}"));
+
+    // Registry serials are found through the verification of their block
+    let (batch, serial) = match registry {
+        Some(registry) => {
+            let (block_start, block) = find_block(storage, &registry, serial)?
+                .filter(|(_, block)| serial <= block.serial_end)
+                .ok_or_else(not_found)?;
+            let record = VERIFICATION_RECORDS.load(storage, &block.verification_id)?;
+            let Some(batch_id) = record.batch_id else {
+                return Ok(SerialStatusResponse {
+                    batch_id: None,
+                    state: SerialState::Pending {
+                        verification_id: record.verification_id,
+                    },
+                    vintage_expired_at: None,
+                });
+            };
+            let batch = BATCHES.load(storage, batch_id)?;
+            let serial = batch.first_serial + (serial - block_start);
+            (batch, serial)
+        }
+        None => (find_batch(storage, serial)?.ok_or_else(not_found)?, serial),
+    };
+
+    let record = batch
+        .verification_id
+        .as_deref()
+        .map(|verification_id| VERIFICATION_RECORDS.load(storage, verification_id))
+        .transpose()?;
+    let state = match find_retired(storage, serial)? {
+        Some((_, retired)) if serial <= retired.last_serial => {
+            let record = RETIREMENTS.load(storage, &retired.retirement_id)?;
+            SerialState::Retired {
+                retirement_id: record.retirement_id,
+                retirement_entity: record.retirement_entity,
+            }
+        }
+        _ => match &record {
+            Some(record) if record.status == VerificationStatus::Expired => SerialState::Expired {
+                verification_id: record.verification_id.clone(),
+            },
+            _ => SerialState::Held,
+        },
+    };
+    Ok(SerialStatusResponse {
+        batch_id: Some(batch.batch_id),
+        state,
+        vintage_expired_at: record.and_then(|record| record.vintage_expired_at),
+    })
+}
@@ -0,0 +1,569 @@
+use std::fmt;
+
+use cosmwasm_std::{Addr, Decimal, Empty, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use rwa_common::pause::GUARDIAN;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// Custom state for carbon credit specific data
+pub const CARBON_CREDIT_INFO: Item<CarbonCreditInfo> = Item::new("carbon_credit_info");
+pub const VERIFICATION_RECORDS: Map<&str, VerificationRecord> = Map::new("verification_records");
+// Retirements by id, indexed by entity, beneficiary and date
+pub const RETIREMENTS_NAMESPACE: &str = "retirement_records";
+pub const RETIREMENTS: IndexedMap<&str, RetirementRecord, RetirementIndexes> = IndexedMap::new(
+    RETIREMENTS_NAMESPACE,
+    RetirementIndexes {
+        entity: MultiIndex::new(
+            entity_index,
+            RETIREMENTS_NAMESPACE,
+            "retirement_records__entity",
+        ),
+        beneficiary: MultiIndex::new(
+            beneficiary_index,
+            RETIREMENTS_NAMESPACE,
+            "retirement_records__beneficiary",
+        ),
+        date: MultiIndex::new(
+            date_index,
+            RETIREMENTS_NAMESPACE,
+            "retirement_records__date",
+        ),
+    },
+);
+// Issuance batches by id, the batched part of every holder's balance, and the
+// same balances by batch, to find a batch's holders
+pub const BATCHES: Map<u64, Batch> = Map::new("batches");
+pub const BATCH_BALANCES: Map<(&Addr, u64), Uint128> = Map::new("batch_balances");
+pub const BATCH_HOLDERS: Map<(u64, &Addr), Uint128> = Map::new("batch_holders");
+// Registry serial blocks already tokenized, by registry and first serial, and
+// the retired ranges of the contract's own serials by first serial
+pub const REGISTRY_BLOCKS: Map<(&str, u128), RegistryBlock> = Map::new("registry_blocks");
+pub const RETIRED_SERIALS: Map<u128, RetiredSerials> = Map::new("retired_serials");
+// The soulbound CW721 collection retirement certificates are minted into
+pub const CERTIFICATE_COLLECTION: Item<Addr> = Item::new("certificate_collection");
+// The batches the last CW20 send drew on, kept for the receive hook of the
+// receiving contract; a send to this contract retires exactly these
+pub const SENT_BATCHES: Item<Vec<BatchAmount>> = Item::new("sent_batches");
+// The other carbon credit token contracts the receive hook forwards credits
+// to. Any CW20 can call the hook, so only tokens the role admin approved are
+// trusted to retire what is forwarded to them.
+pub const FORWARD_TOKENS: Map<&Addr, Empty> = Map::new("forward_tokens");
+// Share of every issuance withheld into the buffer pool, by project type, and
+// the credits the pool holds by batch
+pub const BUFFER_RATES: Map<&str, Decimal> = Map::new("buffer_rates");
+pub const BUFFER_POOL: Map<u64, Uint128> = Map::new("buffer_pool");
+pub const REVERSAL_RECORDS: Map<&str, ReversalRecord> = Map::new("reversal_records");
+// The credits each holder lost to a reversal, by reversal and holder, and the
+// reversal still working through its holders
+pub const REVERSAL_CANCELLATIONS: Map<(&str, &Addr), Uint128> = Map::new("reversal_cancellations");
+pub const PENDING_REVERSAL: Item<PendingReversal> = Item::new("pending_reversal");
+// Projects registered alongside the contract's own, by project id, with
+// their credits held per owner and project, and the operators each owner
+// lets move all of its project credits
+pub const PROJECTS: Map<&str, CarbonCreditInfo> = Map::new("projects");
+pub const PROJECT_BALANCES: Map<(&Addr, &str), Uint128> = Map::new("project_balances");
+pub const PROJECT_OPERATORS: Map<(&Addr, &Addr), Empty> = Map::new("project_operators");
+// Forward units sold ahead of verification, by series, and each holder's
+// position in a series
+pub const FORWARD_SERIES: Map<u64, ForwardSeries> = Map::new("forward_series");
+pub const FORWARD_POSITIONS: Map<(u64, &Addr), ForwardPosition> = Map::new("forward_positions");
+// Article 6 authorizations of the host country, by batch, and by registered
+// project, whose credits keep no batches
+pub const BATCH_AUTHORIZATIONS: Map<u64, BatchAuthorization> = Map::new("batch_authorizations");
+pub const PROJECT_AUTHORIZATIONS: Map<&str, BatchAuthorization> =
+    Map::new("project_authorizations");
+// The structured tags of the contract's own and registered projects, by
+// project id, and every revision they went through, which is never rewritten
+pub const PROJECT_TAGS: Map<&str, ProjectTagRecord> = Map::new("project_tags");
+pub const TAG_HISTORY: Map<(&str, u32), ProjectTagRecord> = Map::new("tag_history");
+// The tagged projects by each of their current tags, and the verification and
+// retirement record ids of every project, to list records by tag
+pub const TAGGED_PROJECTS: Map<(&str, &str), Empty> = Map::new("tagged_projects");
+pub const PROJECT_VERIFICATIONS: Map<(&str, &str), Empty> = Map::new("project_verifications");
+pub const PROJECT_RETIREMENTS: Map<(&str, &str), Empty> = Map::new("project_retirements");
+// How long vintages stay valid; without a policy they never expire
+pub const VINTAGE_POLICY: Item<VintagePolicy> = Item::new("vintage_policy");
+
+// Roles kept in the shared role registry
+pub const VERIFICATION_BODY: &str = "verification_body";
+pub const PROJECT_DEVELOPER: &str = "project_developer";
+pub const HOST_COUNTRY_AUTHORITY: &str = "host_country_authority";
+pub const ROLES: &[&str] = &[
+    VERIFICATION_BODY,
+    PROJECT_DEVELOPER,
+    HOST_COUNTRY_AUTHORITY,
+    GUARDIAN,
+];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CarbonCreditInfo {
+    pub project_id: String,
+    pub project_name: String,
+    pub project_type: ProjectType,
+    pub verification_standard: String, // e.g., "VCS", "Gold Standard", "CAR"
+    pub vintage_year: u32,
+    pub country: String,
+    pub total_credits_issued: Uint128,
+    pub credits_retired: Uint128,
+    pub credits_available: Uint128,
+    pub co2_equivalent_per_credit: Decimal, // tons of CO2 per credit
+    // Issued credits withheld into the buffer pool; they are held by the
+    // contract and never circulate, so they are not available
+    #[serde(default)]
+    pub credits_buffered: Uint128,
+    // Initial holders of the verification_body and project_developer roles;
+    // later changes are made in the role registry. The project developer also
+    // receives verified credits when no recipients are named.
+    pub verification_body: Addr,
+    pub project_developer: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VerificationRecord {
+    pub verification_id: String,
+    pub verification_date: Timestamp,
+    pub credits_verified: Uint128,
+    pub verification_body: Addr,
+    pub verification_report_url: String,
+    pub status: VerificationStatus,
+    // Who the verified credits were minted to; empty for records written before
+    // verification minted
+    #[serde(default)]
+    pub issued_to: Vec<CreditAllocation>,
+    // Every status the record has had, oldest first; empty for records written
+    // before the history was kept
+    #[serde(default)]
+    pub history: Vec<StatusChange>,
+    // The vintage and methodology version of the batch the credits are issued
+    // into, and the batch once they are
+    #[serde(default)]
+    pub vintage_year: Option<u32>,
+    #[serde(default)]
+    pub methodology_version: Option<String>,
+    #[serde(default)]
+    pub batch_id: Option<u64>,
+    // The registry serials of the verified credits, if they were named
+    #[serde(default)]
+    pub registry_serials: Option<RegistrySerials>,
+    // The credits withheld into the buffer pool when the record was issued;
+    // `issued_to` lists what the recipients received net of them
+    #[serde(default)]
+    pub buffer_credits: Uint128,
+    // The registered project the credits were issued for; none for the
+    // contract's own project
+    #[serde(default)]
+    pub project_id: Option<String>,
+    // The forward series whose units the credits were delivered against
+    #[serde(default)]
+    pub forward_series: Option<u64>,
+    // When the batch's vintage expired under the vintage policy, once a sweep
+    // found it expired. The credits stay issued and the status is untouched.
+    #[serde(default)]
+    pub vintage_expired_at: Option<Timestamp>,
+}
+
+/// A block of serial numbers assigned by an off-chain registry such as Verra,
+/// Gold Standard or CAR, both ends inclusive.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RegistrySerials {
+    pub registry: String,
+    pub serial_start: Uint128,
+    pub serial_end: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RegistryBlock {
+    pub serial_end: Uint128,
+    pub verification_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreditAllocation {
+    pub recipient: Addr,
+    pub credits: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum VerificationStatus {
+    Pending,
+    Verified,
+    Rejected,
+    Expired,
+}
+
+impl VerificationStatus {
+    /// Whether a record with this status may move to `next`. Pending records
+    /// are verified or rejected, and verified ones can only expire.
+    pub fn can_become(&self, next: &VerificationStatus) -> bool {
+        matches!(
+            (self, next),
+            (VerificationStatus::Pending, VerificationStatus::Verified)
+                | (VerificationStatus::Pending, VerificationStatus::Rejected)
+                | (VerificationStatus::Verified, VerificationStatus::Expired)
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatusChange {
+    pub status: VerificationStatus,
+    pub changed_at: Timestamp,
+    pub changed_by: Addr,
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RetirementRecord {
+    pub retirement_id: String,
+    pub retirement_date: Timestamp,
+    pub credits_retired: Uint128,
+    pub retirement_purpose: String,
+    pub retirement_entity: Addr,
+    pub retirement_certificate_url: String,
+    // The batches the retired credits were taken from
+    #[serde(default)]
+    pub batches: Vec<BatchAmount>,
+    // The serials retired, in the contract's own numbering
+    #[serde(default)]
+    pub serials: Vec<SerialRange>,
+    // The collection the retirement's certificate was minted into, under the
+    // retirement id
+    #[serde(default)]
+    pub certificate_collection: Option<Addr>,
+    // Who the retirement is claimed for when not the retirement entity, and
+    // the name shown on its certificate
+    #[serde(default)]
+    pub beneficiary: Option<Addr>,
+    #[serde(default)]
+    pub beneficiary_name: Option<String>,
+    // The account that retired the entity's credits through an allowance
+    #[serde(default)]
+    pub operator: Option<Addr>,
+    // The registered project the credits were retired from; none for the
+    // contract's own project
+    #[serde(default)]
+    pub project_id: Option<String>,
+    // What the retirement is counted towards; voluntary for records written
+    // before uses were declared
+    #[serde(default)]
+    pub retirement_use: CreditUse,
+}
+
+/// Secondary indexes of the retirement records, by retirement date in seconds
+/// and, for the first two, by account first.
+pub struct RetirementIndexes<'a> {
+    /// The account that retired the credits
+    pub entity: MultiIndex<'a, (Addr, u64), RetirementRecord, String>,
+    /// The account the retirement is claimed for: the beneficiary when one is
+    /// named, else the retirement entity, which then retired for itself
+    pub beneficiary: MultiIndex<'a, (Addr, u64), RetirementRecord, String>,
+    pub date: MultiIndex<'a, u64, RetirementRecord, String>,
+}
+
+impl<'a> IndexList<RetirementRecord> for RetirementIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<RetirementRecord>> + '_> {
+        let v: Vec<&dyn Index<RetirementRecord>> =
+            vec![&self.entity, &self.beneficiary, &self.date];
+        Box::new(v.into_iter())
+    }
+}
+
+fn entity_index(_pk: &[u8], record: &RetirementRecord) -> (Addr, u64) {
+    (
+        record.retirement_entity.clone(),
+        record.retirement_date.seconds(),
+    )
+}
+
+fn beneficiary_index(_pk: &[u8], record: &RetirementRecord) -> (Addr, u64) {
+    (
+        record.claimed_for().clone(),
+        record.retirement_date.seconds(),
+    )
+}
+
+fn date_index(_pk: &[u8], record: &RetirementRecord) -> u64 {
+    record.retirement_date.seconds()
+}
+
+impl RetirementRecord {
+    /// The account the retirement is claimed for
+    pub fn claimed_for(&self) -> &Addr {
+        self.beneficiary.as_ref().unwrap_or(&self.retirement_entity)
+    }
+}
+
+/// A range of the contract's own serials, both ends inclusive.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SerialRange {
+    pub first: Uint128,
+    pub last: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RetiredSerials {
+    pub last_serial: Uint128,
+    pub retirement_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Batch {
+    pub batch_id: u64,
+    pub vintage_year: u32,
+    pub methodology_version: Option<String>,
+    // The verification that issued the batch; none for the base batch
+    pub verification_id: Option<String>,
+    // Serial numbers of the batch's credits, both inclusive
+    pub first_serial: Uint128,
+    pub last_serial: Uint128,
+    pub credits_issued: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchAmount {
+    pub batch_id: u64,
+    pub credits: Uint128,
+}
+
+/// A reversal such as a fire or illegal logging, covered by cancelling buffer
+/// pool credits first and then holders' credits pro-rata.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReversalRecord {
+    pub reversal_id: String,
+    pub reported_at: Timestamp,
+    pub reported_by: Addr,
+    pub credits_reversed: Uint128,
+    pub reason: String,
+    // The buffer pool credits cancelled, by batch
+    pub buffer_cancelled: Vec<BatchAmount>,
+    // The credits cancelled from holders once the pool was exhausted; what
+    // each holder lost is kept apart, by holder
+    pub holder_credits: Uint128,
+    // Whether holders are still being worked through, a page at a time
+    pub in_progress: bool,
+}
+
+/// A reversal cancelling credits from holders pro-rata, through the holders of
+/// each batch in batch and address order. The shares are taken from a running
+/// total of the batch balances counted, against the supply when the reversal
+/// was reported.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingReversal {
+    pub reversal_id: String,
+    pub credits: Uint128,
+    pub supply: Uint128,
+    pub balances_counted: Uint128,
+    pub cancelled: Uint128,
+    pub last_holding: Option<(u64, Addr)>,
+}
+
+/// Forward units issued against a monitoring schedule. Each verification
+/// delivered against the series settles its next period.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ForwardSeries {
+    pub series_id: u64,
+    pub issued_at: Timestamp,
+    pub issued_by: Addr,
+    pub periods: Vec<ForwardPeriod>,
+    pub units_issued: Uint128,
+    /// Units not yet converted or written off by a shortfall
+    pub units_outstanding: Uint128,
+    pub credits_delivered: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ForwardPeriod {
+    pub period_end: Timestamp,
+    pub expected_credits: Uint128,
+    /// The verification that settled the period, once one has
+    pub verification_id: Option<String>,
+    pub credits_delivered: Uint128,
+}
+
+/// A holder's units of a forward series, and what became of those settled.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ForwardPosition {
+    pub forward: Uint128,
+    pub delivered: Uint128,
+    pub shortfall: Uint128,
+}
+
+/// How long the credits of a vintage stay valid.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VintagePolicy {
+    /// A vintage stays valid through the end of `vintage_year +
+    /// validity_years`, UTC, and expires when the next year starts
+    pub validity_years: u32,
+    /// Whether credits of expired vintages are also kept from moving to
+    /// another account, rather than only from compliance retirements
+    pub block_transfers: bool,
+}
+
+/// What retired credits are counted towards. Credits can always be retired
+/// for voluntary use; NDC and CORSIA use take the host country's
+/// authorization.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CreditUse {
+    #[default]
+    Voluntary,
+    /// Towards another country's nationally determined contribution, under
+    /// Article 6.2
+    Ndc,
+    /// Towards an airline's CORSIA offsetting requirements
+    Corsia,
+}
+
+impl fmt::Display for CreditUse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CreditUse::Voluntary => write!(f, "voluntary"),
+            CreditUse::Ndc => write!(f, "NDC"),
+            CreditUse::Corsia => write!(f, "CORSIA"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthorizationStatus {
+    /// A Letter of Authorization has been requested
+    Pending,
+    Authorized,
+    /// Withdrawn; retirements already made stand
+    Revoked,
+}
+
+/// The host country's authorization of a batch under Article 6.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchAuthorization {
+    pub status: AuthorizationStatus,
+    /// Reference of the Letter of Authorization
+    pub letter_of_authorization: Option<String>,
+    /// The uses beyond voluntary the batch is authorized for
+    pub authorized_uses: Vec<CreditUse>,
+    /// Whether the host country applies a corresponding adjustment for the
+    /// batch's credits
+    pub corresponding_adjustment: bool,
+    pub updated_by: Addr,
+    pub updated_at: Timestamp,
+}
+
+/// A project's methodology, and what it contributes beyond the reductions it
+/// is credited for.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProjectTags {
+    pub methodology: Methodology,
+    /// The UN Sustainable Development Goals the project contributes to
+    pub sdgs: Vec<Sdg>,
+    pub co_benefits: Vec<CoBenefit>,
+}
+
+/// A project's tags as of one revision, with the verification body's
+/// attestation of them once given.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProjectTagRecord {
+    /// The project type of the project's carbon credit info, attested with
+    /// the tags
+    pub project_type: ProjectType,
+    pub tags: ProjectTags,
+    /// Counts every change to the tags or their attestation, from 1
+    pub revision: u32,
+    pub updated_by: Addr,
+    pub updated_at: Timestamp,
+    pub attestation: Option<TagAttestation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TagAttestation {
+    pub attested_by: Addr,
+    pub attested_at: Timestamp,
+    pub report_url: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectType {
+    RenewableEnergy,
+    ForestConservation,
+    Reforestation,
+    BlueCarbon,
+    Agriculture,
+    Cookstoves,
+    MethaneCapture,
+    CarbonCapture,
+    EnergyEfficiency,
+}
+
+impl ProjectType {
+    /// The name the type is serialized under, which keys its buffer rate.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectType::RenewableEnergy => "renewable_energy",
+            ProjectType::ForestConservation => "forest_conservation",
+            ProjectType::Reforestation => "reforestation",
+            ProjectType::BlueCarbon => "blue_carbon",
+            ProjectType::Agriculture => "agriculture",
+            ProjectType::Cookstoves => "cookstoves",
+            ProjectType::MethaneCapture => "methane_capture",
+            ProjectType::CarbonCapture => "carbon_capture",
+            ProjectType::EnergyEfficiency => "energy_efficiency",
+        }
+    }
+}
+
+/// Crediting methodologies of the Verra and CDM catalogues.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Methodology {
+    /// REDD+ methodology framework
+    Vm0007,
+    /// Avoided unplanned deforestation
+    Vm0015,
+    /// Tidal wetland and seagrass restoration
+    Vm0033,
+    /// Improved agricultural land management
+    Vm0042,
+    /// Afforestation, reforestation and revegetation
+    Vm0047,
+    /// Flaring or use of landfill gas
+    Acm0001,
+    /// Grid-connected renewable electricity
+    Acm0002,
+    /// Energy efficiency in thermal applications of non-renewable biomass
+    AmsIiG,
+}
+
+/// The seventeen UN Sustainable Development Goals, in order.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Sdg {
+    NoPoverty,
+    ZeroHunger,
+    GoodHealth,
+    QualityEducation,
+    GenderEquality,
+    CleanWater,
+    AffordableEnergy,
+    DecentWork,
+    IndustryInnovation,
+    ReducedInequalities,
+    SustainableCities,
+    ResponsibleConsumption,
+    ClimateAction,
+    LifeBelowWater,
+    LifeOnLand,
+    PeaceAndJustice,
+    Partnerships,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CoBenefit {
+    Biodiversity,
+    /// Jobs, income or services for local communities
+    Community,
+    Water,
+    Health,
+}
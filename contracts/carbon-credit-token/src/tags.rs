@@ -0,0 +1,292 @@
+//! Project tags.
+//!
+//! Besides its `project_type` and the free-form `verification_standard` of its
+//! carbon credit info, every project, the contract's own or a registered one,
+//! can carry structured tags: its methodology, the UN SDGs it contributes to
+//! and its co-benefits. The developer sets them and the project's verification
+//! body attests them, together with the project type; any change to the tags
+//! sets the attestation aside. Each change is kept as a new revision in a
+//! history that is never rewritten. Projects are indexed by each of their
+//! current tags, and verification and retirement records by their project, so
+//! that records can be listed by a tag of the project they belong to.
+
+use cosmwasm_std::{to_json_string, Addr, Empty, Order, StdResult, Storage, Timestamp};
+use cw_storage_plus::{Bound, Map};
+use rwa_common::{DEFAULT_LIMIT, MAX_LIMIT};
+
+use crate::error::ContractError;
+use crate::msg::ProjectTag;
+use crate::state::{
+    ProjectTagRecord, ProjectTags, ProjectType, TagAttestation, CARBON_CREDIT_INFO,
+    PROJECT_RETIREMENTS, PROJECT_TAGS, PROJECT_VERIFICATIONS, RETIREMENTS, TAGGED_PROJECTS,
+    TAG_HISTORY, VERIFICATION_RECORDS,
+};
+
+/// Replaces the tags of `project_id`, a project of `project_type`, without an
+/// attestation.
+pub fn set_project_tags(
+    storage: &mut dyn Storage,
+    project_id: &str,
+    project_type: ProjectType,
+    tags: ProjectTags,
+    updated_by: Addr,
+    updated_at: Timestamp,
+) -> Result<ProjectTagRecord, ContractError> {
+    ensure_unique("SDG", &tags.sdgs)?;
+    ensure_unique("co-benefit", &tags.co_benefits)?;
+    let revision = PROJECT_TAGS
+        .may_load(storage, project_id)?
+        .map_or(1, |current| current.revision + 1);
+    let record = ProjectTagRecord {
+        project_type,
+        tags,
+        revision,
+        updated_by,
+        updated_at,
+        attestation: None,
+    };
+    save_revision(storage, project_id, &record)?;
+    Ok(record)
+}
+
+/// Attests revision `revision` of the tags of `project_id`, which must still
+/// be the current one.
+pub fn attest_project_tags(
+    storage: &mut dyn Storage,
+    project_id: &str,
+    revision: u32,
+    attestation: TagAttestation,
+) -> Result<ProjectTagRecord, ContractError> {
+    let mut record = load_tags(storage, project_id)?;
+    if record.revision != revision {
+        return Err(ContractError::StaleTagRevision {
+            project_id: project_id.to_string(),
+            revision,
+            current: record.revision,
+        });
+    }
+    record.revision += 1;
+    record.updated_by = attestation.attested_by.clone();
+    record.updated_at = attestation.attested_at;
+    record.attestation = Some(attestation);
+    save_revision(storage, project_id, &record)?;
+    Ok(record)
+}
+
+pub fn load_tags(
+    storage: &dyn Storage,
+    project_id: &str,
+) -> Result<ProjectTagRecord, ContractError> {
+    PROJECT_TAGS
+        .may_load(storage, project_id)?
+        .ok_or_else(|| ContractError::ProjectNotTagged {
+            project_id: project_id.to_string(),
+        })
+}
+
+/// Up to `limit` revisions of the tags of `project_id`, oldest first, starting
+/// after `start_after`.
+pub fn tag_history(
+    storage: &dyn Storage,
+    project_id: &str,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ProjectTagRecord>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    TAG_HISTORY
+        .prefix(project_id)
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, record)| record))
+        .collect()
+}
+
+/// Up to `limit` projects carrying `tag`, in project id order, starting after
+/// `start_after`. With `attested_only`, projects whose current tags are not
+/// attested are left out.
+pub fn projects_by_tag(
+    storage: &dyn Storage,
+    tag: &ProjectTag,
+    attested_only: bool,
+    start_after: Option<&str>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, ProjectTagRecord)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let tag = to_json_string(tag)?;
+    TAGGED_PROJECTS
+        .prefix(&tag)
+        .keys(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .map(|project_id| {
+            let project_id = project_id?;
+            let record = PROJECT_TAGS.load(storage, &project_id)?;
+            Ok((project_id, record))
+        })
+        .filter(|item| {
+            item.as_ref().map_or(true, |(_, record)| {
+                !attested_only || record.attestation.is_some()
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Up to `limit` records in id order, starting after `start_after`, that
+/// belong to a project carrying `tag`. `index` files the record ids by
+/// project, and `load` reads a record.
+pub fn records_by_tag<'a, T>(
+    storage: &dyn Storage,
+    index: &Map<'a, (&'a str, &'a str), Empty>,
+    load: impl Fn(&dyn Storage, &str) -> StdResult<T>,
+    tag: &ProjectTag,
+    attested_only: bool,
+    start_after: Option<&'a str>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, T)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let tag = to_json_string(tag)?;
+    let projects = TAGGED_PROJECTS
+        .prefix(&tag)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // The first page of each project's records holds the first page of all
+    let mut record_ids = vec![];
+    for project_id in projects {
+        if attested_only
+            && PROJECT_TAGS
+                .load(storage, &project_id)?
+                .attestation
+                .is_none()
+        {
+            continue;
+        }
+        for record_id in index
+            .prefix(&project_id)
+            .keys(
+                storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+        {
+            record_ids.push(record_id?);
+        }
+    }
+    record_ids.sort();
+    record_ids.truncate(limit);
+    record_ids
+        .into_iter()
+        .map(|record_id| {
+            let record = load(storage, &record_id)?;
+            Ok((record_id, record))
+        })
+        .collect()
+}
+
+/// Files record `record_id` in `index` under its registered project, or the
+/// contract's own project without one.
+pub fn index_record<'a>(
+    storage: &mut dyn Storage,
+    index: &Map<'a, (&'a str, &'a str), Empty>,
+    project_id: Option<&'a str>,
+    record_id: &'a str,
+) -> StdResult<()> {
+    let own_project;
+    let project_id = match project_id {
+        Some(project_id) => project_id,
+        None => {
+            own_project = CARBON_CREDIT_INFO.load(storage)?.project_id;
+            own_project.as_str()
+        }
+    };
+    index.save(storage, (project_id, record_id), &Empty {})
+}
+
+/// Files the verification and retirement records written before records were
+/// indexed by project.
+pub fn index_project_records(storage: &mut dyn Storage) -> StdResult<()> {
+    let verifications = VERIFICATION_RECORDS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(id, record)| (id, record.project_id)))
+        .collect::<StdResult<Vec<_>>>()?;
+    for (verification_id, project_id) in verifications {
+        index_record(
+            storage,
+            &PROJECT_VERIFICATIONS,
+            project_id.as_deref(),
+            &verification_id,
+        )?;
+    }
+    let retirements = RETIREMENTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(id, record)| (id, record.project_id)))
+        .collect::<StdResult<Vec<_>>>()?;
+    for (retirement_id, project_id) in retirements {
+        index_record(
+            storage,
+            &PROJECT_RETIREMENTS,
+            project_id.as_deref(),
+            &retirement_id,
+        )?;
+    }
+    Ok(())
+}
+
+/// Every tag `record` carries, as the key it is indexed under.
+fn tag_keys(record: &ProjectTagRecord) -> StdResult<Vec<String>> {
+    let tags = &record.tags;
+    let mut keys = vec![
+        ProjectTag::ProjectType(record.project_type),
+        ProjectTag::Methodology(tags.methodology),
+    ];
+    keys.extend(tags.sdgs.iter().map(|sdg| ProjectTag::Sdg(*sdg)));
+    keys.extend(
+        tags.co_benefits
+            .iter()
+            .map(|co_benefit| ProjectTag::CoBenefit(*co_benefit)),
+    );
+    keys.iter().map(to_json_string).collect()
+}
+
+fn save_revision(
+    storage: &mut dyn Storage,
+    project_id: &str,
+    record: &ProjectTagRecord,
+) -> StdResult<()> {
+    // Re-file the project under its current tags
+    if let Some(current) = PROJECT_TAGS.may_load(storage, project_id)? {
+        for tag in tag_keys(&current)? {
+            TAGGED_PROJECTS.remove(storage, (&tag, project_id));
+        }
+    }
+    for tag in tag_keys(record)? {
+        TAGGED_PROJECTS.save(storage, (&tag, project_id), &Empty {})?;
+    }
+    PROJECT_TAGS.save(storage, project_id, record)?;
+    TAG_HISTORY.save(storage, (project_id, record.revision), record)
+}
+
+fn ensure_unique<T: PartialEq + std::fmt::Debug>(
+    kind: &str,
+    values: &[T],
+) -> Result<(), ContractError> {
+    for (i, value) in values.iter().enumerate() {
+        if values[..i].contains(value) {
+            return Err(ContractError::InvalidProjectTags {
+                reason: format!("{kind} {value:?} is listed twice"),
+            });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,586 @@
+use std::collections::BTreeSet;
+
+use carbon_credit_token::msg::{
+    CarbonCreditExecuteMsg, CarbonCreditQueryMsg, ExecuteMsg as TokenExecuteMsg,
+    QueryMsg as TokenQueryMsg,
+};
+use carbon_credit_token::state::{Batch, BatchAmount, CarbonCreditInfo};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{BalanceResponse, Cw20ReceiveMsg};
+use cw20_base::contract::instantiate as cw20_instantiate;
+use cw20_base::state::TOKEN_INFO;
+use cw_storage_plus::Bound;
+use rwa_common::migration::rewrite_item;
+use rwa_common::roles::seed_roles;
+use rwa_common::sanctions::SANCTION_SCREENING;
+use rwa_common::token::{burn_tokens, mint_tokens};
+use rwa_common::{
+    action_response, assign_record_id, ensure_admin, ensure_not_paused, ensure_not_sanctioned,
+    ensure_upgrade, execute_cw20, execute_pause, execute_roles, execute_sanctions,
+    paginate_records, query_cw20, query_pause, query_roles, query_sanctions, record_response,
+    Cw20ExecuteMsg, Cw20QueryMsg, Drift, DEFAULT_LIMIT, MAX_LIMIT,
+};
+
+use crate::error::ContractError;
+use crate::holdings::{all_holdings, deposit, holdings, withdraw, withdraw_oldest};
+use crate::msg::{
+    EligibilityResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, PoolExecuteMsg, PoolQueryMsg,
+    PoolReceiveMsg, QueryMsg, ReconcileSupplyResponse,
+};
+use crate::state::{
+    Eligibility, PoolRetirement, Source, APPROVED_TOKENS, POOL_INFO, POOL_RETIREMENTS, ROLES,
+    SOURCES,
+};
+
+// Version info for migration
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    // Every pool token must be backed by a deposited credit
+    if !msg.cw20_base.initial_balances.is_empty() || msg.cw20_base.mint.is_some() {
+        return Err(ContractError::UnbackedPoolTokens {});
+    }
+
+    // Store pool specific information
+    let mut pool_info = msg.pool_info;
+    ensure_valid_fee(pool_info.redemption_fee)?;
+    pool_info.fee_recipient = deps.api.addr_validate(pool_info.fee_recipient.as_str())?;
+    POOL_INFO.save(deps.storage, &pool_info)?;
+
+    // The pool has no role holders to seed, only its admin
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(&admin)?,
+        None => info.sender.clone(),
+    };
+    seed_roles(deps.storage, &admin, &[])?;
+
+    if let Some(enabled) = msg.sanction_screening {
+        SANCTION_SCREENING.save(deps.storage, &enabled)?;
+    }
+
+    // Initialize the base CW20 contract
+    let res = cw20_instantiate(deps.branch(), env, info, msg.cw20_base)?;
+
+    // cw20-base records its own name, so ours has to be written afterwards
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        // Handle standard CW20 messages
+        ExecuteMsg::Cw20(msg) => {
+            // Pool tokens only leave supply with the credits backing them
+            if let Cw20ExecuteMsg::Burn { .. } = msg {
+                return Err(ContractError::BurnDisabled {});
+            }
+
+            // Honour the circuit breakers and screen everyone tokens move between
+            if let Some(category) = msg.pause_category() {
+                ensure_not_paused(deps.storage, category)?;
+            }
+            ensure_not_sanctioned(deps.as_ref(), &msg.token_parties(info.sender.as_str()))?;
+            Ok(execute_cw20(deps, env, info, msg)?)
+        }
+
+        // Handle role registry messages
+        ExecuteMsg::Roles(msg) => Ok(execute_roles(deps, info, ROLES, msg)?),
+
+        // Handle circuit breaker messages
+        ExecuteMsg::Pause(msg) => Ok(execute_pause(deps, info, msg)?),
+
+        // Handle sanction screening messages
+        ExecuteMsg::Sanctions(msg) => Ok(execute_sanctions(deps, info, msg)?),
+
+        // Handle pool specific messages
+        ExecuteMsg::Pool(msg) => {
+            ensure_not_paused(deps.storage, msg.pause_category())?;
+            match msg {
+                PoolExecuteMsg::Receive(wrapper) => receive_cw20(deps, info, wrapper),
+                PoolExecuteMsg::Redeem { token, amount } => redeem(deps, info, token, amount),
+                PoolExecuteMsg::Retire {
+                    retirement_id,
+                    amount,
+                    retirement_purpose,
+                    retirement_certificate_url,
+                    beneficiary,
+                    beneficiary_name,
+                } => {
+                    let retirement = NewRetirement {
+                        retirement_id,
+                        amount,
+                        retirement_purpose,
+                        retirement_certificate_url,
+                        beneficiary,
+                        beneficiary_name,
+                    };
+                    retire(deps, env, info, retirement)
+                }
+                PoolExecuteMsg::UpdateEligibility { eligibility } => {
+                    ensure_admin(deps.storage, &info.sender, "update eligibility")?;
+                    POOL_INFO.update(deps.storage, |mut pool_info| -> StdResult<_> {
+                        pool_info.eligibility = eligibility;
+                        Ok(pool_info)
+                    })?;
+                    Ok(action_response("update_eligibility"))
+                }
+                PoolExecuteMsg::ApproveToken { token } => {
+                    ensure_admin(deps.storage, &info.sender, "approve tokens")?;
+                    let token = deps.api.addr_validate(&token)?;
+                    APPROVED_TOKENS.save(deps.storage, &token, &Empty {})?;
+                    Ok(action_response("approve_token").add_attribute("token", token))
+                }
+                PoolExecuteMsg::RevokeToken { token } => {
+                    ensure_admin(deps.storage, &info.sender, "revoke tokens")?;
+                    let token = deps.api.addr_validate(&token)?;
+                    APPROVED_TOKENS.remove(deps.storage, &token);
+                    Ok(action_response("revoke_token").add_attribute("token", token))
+                }
+                PoolExecuteMsg::SetRedemptionFee {
+                    redemption_fee,
+                    fee_recipient,
+                } => set_redemption_fee(deps, info, redemption_fee, fee_recipient),
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        // Handle standard CW20 queries
+        QueryMsg::Cw20(msg) => query_cw20(deps, env, msg),
+
+        // Handle role registry queries
+        QueryMsg::Roles(msg) => query_roles(deps, msg),
+
+        // Handle circuit breaker queries
+        QueryMsg::Pause(msg) => query_pause(deps, msg),
+
+        // Handle sanction screening queries
+        QueryMsg::Sanctions(msg) => query_sanctions(deps, msg),
+
+        // Handle pool specific queries
+        QueryMsg::Pool(msg) => match msg {
+            PoolQueryMsg::PoolInfo {} => to_json_binary(&POOL_INFO.load(deps.storage)?),
+            PoolQueryMsg::CheckEligibility { token, batch_id } => {
+                query_check_eligibility(deps, token, batch_id)
+            }
+            PoolQueryMsg::ApprovedTokens { start_after, limit } => {
+                query_approved_tokens(deps, start_after, limit)
+            }
+            PoolQueryMsg::Holdings { start_after, limit } => {
+                let start_after = start_after
+                    .map(|token| deps.api.addr_validate(&token))
+                    .transpose()?;
+                to_json_binary(&holdings(deps.storage, start_after.as_ref(), limit)?)
+            }
+            PoolQueryMsg::PoolRetirement { retirement_id } => {
+                to_json_binary(&POOL_RETIREMENTS.load(deps.storage, &retirement_id)?)
+            }
+            PoolQueryMsg::AllPoolRetirements { start_after, limit } => {
+                to_json_binary(&paginate_records(
+                    deps.storage,
+                    &POOL_RETIREMENTS,
+                    start_after.as_deref(),
+                    limit,
+                )?)
+            }
+            PoolQueryMsg::ReconcileSupply {} => query_reconcile_supply(deps, env),
+        },
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let from_version = ensure_upgrade(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Rewrite the pool info so fields added since the stored version are
+    // persisted with their defaults
+    rewrite_item(deps.storage, &POOL_INFO)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(action_response("migrate")
+        .add_attribute("from_version", from_version.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+fn ensure_valid_fee(redemption_fee: Decimal) -> Result<(), ContractError> {
+    if redemption_fee >= Decimal::one() {
+        return Err(ContractError::InvalidRedemptionFee {
+            fee: redemption_fee,
+        });
+    }
+    Ok(())
+}
+
+/// Checks the project of the approved carbon credit token `token`, and its
+/// credits of the vintage `vintage_year` resolves to, or of the project's
+/// vintage when it resolves to none, against the eligibility rules, and
+/// returns the project and vintage they are pooled under. The vintage is only
+/// resolved once the token is known to be a carbon credit token.
+fn ensure_eligible(
+    deps: Deps,
+    eligibility: &Eligibility,
+    token: &Addr,
+    vintage_year: impl FnOnce() -> Result<Option<u32>, ContractError>,
+) -> Result<Source, ContractError> {
+    let ineligible = |reason: String| ContractError::IneligibleCredits {
+        token: token.to_string(),
+        reason,
+    };
+
+    if !APPROVED_TOKENS.has(deps.storage, token) {
+        return Err(ineligible(
+            "the token is not an approved carbon credit token".to_string(),
+        ));
+    }
+    let project: CarbonCreditInfo = deps.querier.query_wasm_smart(
+        token,
+        &TokenQueryMsg::from(CarbonCreditQueryMsg::CarbonCreditInfo {}),
+    )?;
+
+    let vintage_year = vintage_year()?.unwrap_or(project.vintage_year);
+    if let Some(min_vintage_year) = eligibility.min_vintage_year {
+        if vintage_year < min_vintage_year {
+            return Err(ineligible(format!(
+                "vintage {vintage_year} is before {min_vintage_year}"
+            )));
+        }
+    }
+    if let Some(max_vintage_year) = eligibility.max_vintage_year {
+        if vintage_year > max_vintage_year {
+            return Err(ineligible(format!(
+                "vintage {vintage_year} is after {max_vintage_year}"
+            )));
+        }
+    }
+    if matches!(&eligibility.project_types, Some(accepted) if !accepted.contains(&project.project_type))
+    {
+        return Err(ineligible(format!(
+            "project type {} is not accepted",
+            project.project_type.as_str()
+        )));
+    }
+    let criteria = [
+        (
+            "verification standard",
+            &eligibility.verification_standards,
+            &project.verification_standard,
+        ),
+        ("country", &eligibility.countries, &project.country),
+    ];
+    for (criterion, accepted, value) in criteria {
+        if matches!(accepted, Some(accepted) if !accepted.contains(value)) {
+            return Err(ineligible(format!("{criterion} {value} is not accepted")));
+        }
+    }
+
+    // Credits of a token are pooled under one vintage
+    if let Some(source) = SOURCES.may_load(deps.storage, token)? {
+        if source.vintage_year != vintage_year {
+            return Err(ineligible(format!(
+                "its credits are pooled under vintage {}, not {vintage_year}",
+                source.vintage_year
+            )));
+        }
+    }
+    Ok(Source {
+        project_id: project.project_id,
+        vintage_year,
+    })
+}
+
+/// The vintage of the batches of `token` the send being received drew on.
+/// Credits are pooled under the vintage of their batch, so a send drawing on
+/// batches of several vintages cannot be pooled under one.
+fn sent_vintage(deps: Deps, token: &Addr) -> Result<Option<u32>, ContractError> {
+    let sent: Vec<BatchAmount> = deps.querier.query_wasm_smart(
+        token,
+        &TokenQueryMsg::from(CarbonCreditQueryMsg::SentBatches {}),
+    )?;
+    let mut vintage_years = BTreeSet::new();
+    for sent in sent {
+        vintage_years.insert(batch_vintage(deps, token, sent.batch_id)?);
+    }
+    match Vec::from_iter(vintage_years).as_slice() {
+        [] => Ok(None),
+        [vintage_year] => Ok(Some(*vintage_year)),
+        [first, .., last] => Err(ContractError::IneligibleCredits {
+            token: token.to_string(),
+            reason: format!("the credits sent span vintages {first} to {last}"),
+        }),
+    }
+}
+
+fn batch_vintage(deps: Deps, token: &Addr, batch_id: u64) -> StdResult<u32> {
+    let batch: Batch = deps.querier.query_wasm_smart(
+        token,
+        &TokenQueryMsg::from(CarbonCreditQueryMsg::Batch { batch_id }),
+    )?;
+    Ok(batch.vintage_year)
+}
+
+// Pool specific functions
+fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let PoolReceiveMsg::Deposit {} = from_json(&wrapper.msg)?;
+
+    // The sender of the hook is the token whose credits were sent
+    let token = info.sender;
+    let pool_info = POOL_INFO.load(deps.storage)?;
+    let source = ensure_eligible(deps.as_ref(), &pool_info.eligibility, &token, || {
+        sent_vintage(deps.as_ref(), &token)
+    })?;
+
+    // The credits were screened on their way in; who the pool tokens are
+    // minted to is screened here
+    ensure_not_sanctioned(deps.as_ref(), &[wrapper.sender.as_str()])?;
+    let depositor = deps.api.addr_validate(&wrapper.sender)?;
+
+    deposit(deps.storage, &token, &source, wrapper.amount)?;
+    mint_tokens(deps.storage, &depositor, wrapper.amount)?;
+
+    Ok(action_response("deposit")
+        .add_attribute("token", &token)
+        .add_attribute("project_id", source.project_id)
+        .add_attribute("depositor", &depositor)
+        .add_attribute("credits", wrapper.amount))
+}
+
+fn redeem(
+    deps: DepsMut,
+    info: MessageInfo,
+    token: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // The credits are transferred to the sender, which must not be sanctioned
+    ensure_not_sanctioned(deps.as_ref(), &[info.sender.as_str()])?;
+    let token = deps.api.addr_validate(&token)?;
+    let pool_info = POOL_INFO.load(deps.storage)?;
+
+    // The fee changes hands in pool tokens, so its credits stay in the pool
+    let fee = amount * pool_info.redemption_fee;
+    let credits = amount - fee;
+    burn_tokens(deps.storage, &info.sender, amount)?;
+    if !fee.is_zero() {
+        mint_tokens(deps.storage, &pool_info.fee_recipient, fee)?;
+    }
+    withdraw(deps.storage, &token, credits)?;
+
+    let transfer = Cw20ExecuteMsg::Transfer {
+        recipient: info.sender.to_string(),
+        amount: credits,
+    };
+    Ok(action_response("redeem")
+        .add_attribute("token", &token)
+        .add_attribute("redeemer", &info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("fee", fee)
+        .add_attribute("credits", credits)
+        .add_message(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&transfer)?,
+            funds: vec![],
+        }))
+}
+
+// The details of a retirement of pool tokens
+struct NewRetirement {
+    retirement_id: Option<String>,
+    amount: Uint128,
+    retirement_purpose: String,
+    retirement_certificate_url: String,
+    beneficiary: Option<String>,
+    beneficiary_name: Option<String>,
+}
+
+fn retire(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    retirement: NewRetirement,
+) -> Result<Response, ContractError> {
+    let NewRetirement {
+        retirement_id,
+        amount,
+        retirement_purpose,
+        retirement_certificate_url,
+        beneficiary,
+        beneficiary_name,
+    } = retirement;
+
+    // Sanctioned accounts cannot retire, nor have credits retired for them
+    let mut parties = vec![info.sender.as_str()];
+    parties.extend(beneficiary.as_deref());
+    ensure_not_sanctioned(deps.as_ref(), &parties)?;
+    let beneficiary = beneficiary
+        .map(|beneficiary| deps.api.addr_validate(&beneficiary))
+        .transpose()?;
+
+    // Reject a reused id, or generate the next one
+    let retirement_id = assign_record_id(deps.storage, &POOL_RETIREMENTS, retirement_id)?;
+
+    burn_tokens(deps.storage, &info.sender, amount)?;
+    let retired = withdraw_oldest(deps.storage, amount)?;
+
+    // Each token retires its share for the beneficiary, or for the holder,
+    // with the pool as the retiring account
+    let claimant = beneficiary.as_ref().unwrap_or(&info.sender);
+    let messages = retired
+        .iter()
+        .map(|drawn| {
+            let retire = CarbonCreditExecuteMsg::RetireCredits {
+                retirement_id: None,
+                credits_to_retire: drawn.credits,
+                retirement_purpose: retirement_purpose.clone(),
+                retirement_certificate_url: retirement_certificate_url.clone(),
+                batch_id: None,
+                registry_serials: None,
+                beneficiary: Some(claimant.to_string()),
+                beneficiary_name: beneficiary_name.clone(),
+                retirement_use: None,
+            };
+            Ok(WasmMsg::Execute {
+                contract_addr: drawn.token.to_string(),
+                msg: to_json_binary(&TokenExecuteMsg::from(retire))?,
+                funds: vec![],
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let pool_retirement = PoolRetirement {
+        retirement_id: retirement_id.clone(),
+        retirement_date: env.block.time,
+        credits_retired: amount,
+        retirement_purpose,
+        retirement_entity: info.sender,
+        beneficiary,
+        beneficiary_name,
+        retired,
+    };
+    POOL_RETIREMENTS.save(deps.storage, &retirement_id, &pool_retirement)?;
+
+    let mut res = record_response("retire", "retirement_id", &retirement_id)?
+        .add_attribute("credits_retired", amount)
+        .add_attribute("retirement_entity", &pool_retirement.retirement_entity)
+        .add_messages(messages);
+    if let Some(beneficiary) = &pool_retirement.beneficiary {
+        res = res.add_attribute("beneficiary", beneficiary);
+    }
+    Ok(res)
+}
+
+fn set_redemption_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    redemption_fee: Decimal,
+    fee_recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.storage, &info.sender, "set the redemption fee")?;
+    ensure_valid_fee(redemption_fee)?;
+
+    let mut pool_info = POOL_INFO.load(deps.storage)?;
+    pool_info.redemption_fee = redemption_fee;
+    if let Some(fee_recipient) = fee_recipient {
+        pool_info.fee_recipient = deps.api.addr_validate(&fee_recipient)?;
+    }
+    POOL_INFO.save(deps.storage, &pool_info)?;
+
+    Ok(action_response("set_redemption_fee")
+        .add_attribute("redemption_fee", redemption_fee.to_string())
+        .add_attribute("fee_recipient", pool_info.fee_recipient))
+}
+
+// Query functions
+fn query_check_eligibility(deps: Deps, token: String, batch_id: Option<u64>) -> StdResult<Binary> {
+    let token = deps.api.addr_validate(&token)?;
+    let pool_info = POOL_INFO.load(deps.storage)?;
+    let vintage_year = || {
+        batch_id
+            .map(|batch_id| batch_vintage(deps, &token, batch_id))
+            .transpose()
+            .map_err(ContractError::from)
+    };
+    let res = match ensure_eligible(deps, &pool_info.eligibility, &token, vintage_year) {
+        Ok(_) => EligibilityResponse {
+            eligible: true,
+            reason: None,
+        },
+        Err(ContractError::IneligibleCredits { reason, .. }) => EligibilityResponse {
+            eligible: false,
+            reason: Some(reason),
+        },
+        Err(err) => return Err(StdError::generic_err(err.to_string())),
+    };
+    to_json_binary(&res)
+}
+
+fn query_approved_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|token| deps.api.addr_validate(&token))
+        .transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let tokens = APPROVED_TOKENS
+        .keys(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&tokens)
+}
+
+fn query_reconcile_supply(deps: Deps, env: Env) -> StdResult<Binary> {
+    let held = all_holdings(deps.storage)?;
+    let total_held = held.iter().try_fold(Uint128::zero(), |total, holding| {
+        total.checked_add(holding.credits)
+    })?;
+
+    let mut total_balance = Uint128::zero();
+    for holding in &held {
+        let res: BalanceResponse = deps.querier.query_wasm_smart(
+            &holding.token,
+            &Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        total_balance = total_balance.checked_add(res.balance)?;
+    }
+
+    let supply = Drift::between(total_held, TOKEN_INFO.load(deps.storage)?.total_supply);
+    let balances = Drift::between(total_held, total_balance);
+    to_json_binary(&ReconcileSupplyResponse {
+        reconciled: supply.is_zero() && balances.is_zero(),
+        supply,
+        balances,
+    })
+}
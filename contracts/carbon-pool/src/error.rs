@@ -0,0 +1,41 @@
+//! Every variant renders as `<Code>: <description>`, where `<Code>` is the
+//! variant name. Codes are part of the contract's interface and never change
+//! meaning; clients should match on the part before the first colon.
+
+use cosmwasm_std::{Decimal, OverflowError, StdError, Uint128};
+use rwa_common::CommonError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("Std: {0}")]
+    Std(#[from] StdError),
+
+    #[error("Overflow: {0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Cw20Base: {0}")]
+    Cw20Base(#[from] cw20_base::ContractError),
+
+    #[error(transparent)]
+    Common(#[from] CommonError),
+
+    #[error("UnbackedPoolTokens: pool tokens are only minted against deposited credits")]
+    UnbackedPoolTokens {},
+
+    #[error("BurnDisabled: pool tokens only leave supply through Redeem or Retire")]
+    BurnDisabled {},
+
+    #[error("IneligibleCredits: credits of {token} are not accepted: {reason}")]
+    IneligibleCredits { token: String, reason: String },
+
+    #[error("InvalidRedemptionFee: fee {fee} must be below 1")]
+    InvalidRedemptionFee { fee: Decimal },
+
+    #[error("InsufficientHoldings: requested {requested} credits of {token}, held {held}")]
+    InsufficientHoldings {
+        token: String,
+        requested: Uint128,
+        held: Uint128,
+    },
+}
@@ -0,0 +1,133 @@
+//! The pool's holdings.
+//!
+//! The pool holds the deposited credits as balances at their own carbon credit
+//! tokens. This ledger records what each token's balance should be, keyed by
+//! vintage so that retirements can draw the oldest credits first.
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::Bound;
+use rwa_common::{DEFAULT_LIMIT, MAX_LIMIT};
+
+use crate::error::ContractError;
+use crate::msg::Holding;
+use crate::state::{Source, TokenAmount, HOLDINGS, SOURCES};
+
+/// Adds `credits` of `token`, a token of `source`'s project and vintage.
+pub fn deposit(
+    storage: &mut dyn Storage,
+    token: &Addr,
+    source: &Source,
+    credits: Uint128,
+) -> StdResult<()> {
+    SOURCES.save(storage, token, source)?;
+    HOLDINGS.update(
+        storage,
+        (source.vintage_year, token),
+        |held| -> StdResult<_> { Ok(held.unwrap_or_default().checked_add(credits)?) },
+    )?;
+    Ok(())
+}
+
+/// The credits of `token` held.
+pub fn held(storage: &dyn Storage, token: &Addr) -> StdResult<Uint128> {
+    let Some(source) = SOURCES.may_load(storage, token)? else {
+        return Ok(Uint128::zero());
+    };
+    Ok(HOLDINGS
+        .may_load(storage, (source.vintage_year, token))?
+        .unwrap_or_default())
+}
+
+/// Takes `credits` of `token` out of the holdings.
+pub fn withdraw(
+    storage: &mut dyn Storage,
+    token: &Addr,
+    credits: Uint128,
+) -> Result<(), ContractError> {
+    let held = held(storage, token)?;
+    if held < credits {
+        return Err(ContractError::InsufficientHoldings {
+            token: token.to_string(),
+            requested: credits,
+            held,
+        });
+    }
+    let vintage_year = SOURCES.load(storage, token)?.vintage_year;
+    if held == credits {
+        HOLDINGS.remove(storage, (vintage_year, token));
+        SOURCES.remove(storage, token);
+    } else {
+        HOLDINGS.save(storage, (vintage_year, token), &(held - credits))?;
+    }
+    Ok(())
+}
+
+/// Takes `credits` out of the holdings, oldest vintage first, and returns what
+/// was taken of each token.
+pub fn withdraw_oldest(
+    storage: &mut dyn Storage,
+    credits: Uint128,
+) -> Result<Vec<TokenAmount>, ContractError> {
+    let mut remaining = credits;
+    let mut withdrawn = vec![];
+    while !remaining.is_zero() {
+        let Some(((_, token), held)) = HOLDINGS
+            .range(storage, None, None, Order::Ascending)
+            .next()
+            .transpose()?
+        else {
+            // Pool tokens are only minted against holdings, so a holder never
+            // has more to retire than the pool holds
+            return Err(ContractError::InsufficientHoldings {
+                token: "the pool".to_string(),
+                requested: credits,
+                held: credits - remaining,
+            });
+        };
+        let taken = held.min(remaining);
+        withdraw(storage, &token, taken)?;
+        remaining -= taken;
+        withdrawn.push(TokenAmount {
+            token,
+            credits: taken,
+        });
+    }
+    Ok(withdrawn)
+}
+
+/// Up to `limit` of the tokens held, in address order, starting after
+/// `start_after`.
+pub fn holdings(
+    storage: &dyn Storage,
+    start_after: Option<&Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Holding>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    SOURCES
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| {
+            let (token, source) = item?;
+            let credits = HOLDINGS.load(storage, (source.vintage_year, &token))?;
+            Ok(Holding {
+                token,
+                project_id: source.project_id,
+                vintage_year: source.vintage_year,
+                credits,
+            })
+        })
+        .collect()
+}
+
+/// Every token held with its credits, oldest vintage first.
+pub fn all_holdings(storage: &dyn Storage) -> StdResult<Vec<TokenAmount>> {
+    HOLDINGS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|((_, token), credits)| TokenAmount { token, credits }))
+        .collect()
+}
@@ -0,0 +1,20 @@
+//! A pool of fungible carbon credits.
+//!
+//! Carbon credit tokens that meet the pool's eligibility rules are deposited
+//! through the CW20 receive hook and mint pool tokens one for one. Pool tokens
+//! are redeemed for the credits of a chosen token, for a fee, or retired
+//! directly, which retires the pool's oldest vintages on the holder's behalf.
+
+pub mod contract;
+pub mod error;
+pub mod holdings;
+pub mod msg;
+pub mod state;
+
+#[cfg(test)]
+mod multitest;
+
+pub use crate::contract::{execute, instantiate, migrate, query};
+pub use crate::error::ContractError;
+pub use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+pub use crate::state::{Eligibility, PoolInfo, PoolRetirement};
@@ -0,0 +1,222 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use rwa_common::{
+    Cw20ExecuteMsg, Cw20QueryMsg, Drift, PauseCategory, PauseExecuteMsg, PauseQueryMsg,
+    RoleExecuteMsg, RoleQueryMsg, SanctionExecuteMsg, SanctionQueryMsg,
+};
+
+use crate::state::{Eligibility, PoolInfo, PoolRetirement};
+
+// Extended instantiate message
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The pool token. It starts with no balances and no minter: pool tokens
+    /// are only minted against deposits.
+    pub cw20_base: Cw20InstantiateMsg,
+    pub pool_info: PoolInfo,
+    /// Administrator of the role registry, defaulting to the sender
+    pub admin: Option<String>,
+    /// Whether token movements are screened against the sanction blacklist,
+    /// defaulting to true
+    pub sanction_screening: Option<bool>,
+}
+
+// Extended execute messages: the standard CW20 messages, the shared role, pause
+// and sanction messages, and the pool specific ones, sharing the same JSON
+// namespace
+#[cw_serde]
+#[serde(untagged)]
+pub enum ExecuteMsg {
+    Cw20(Cw20ExecuteMsg),
+    Roles(RoleExecuteMsg),
+    Pause(PauseExecuteMsg),
+    Sanctions(SanctionExecuteMsg),
+    Pool(PoolExecuteMsg),
+}
+
+// Pool specific messages. Messages that create a record take an optional id:
+// without one the next generated id is used. Either way the id is returned in
+// the response data.
+#[cw_serde]
+pub enum PoolExecuteMsg {
+    /// CW20 receive hook: eligible carbon credits sent with a `Deposit`
+    /// payload mint the sender as many pool tokens
+    Receive(Cw20ReceiveMsg),
+    /// Burns `amount` pool tokens for the credits of `token`, less the
+    /// redemption fee, which goes to the fee recipient in pool tokens
+    Redeem { token: String, amount: Uint128 },
+    /// Burns pool tokens and retires as many of the pool's credits, oldest
+    /// vintage first, for the sender or a named beneficiary
+    Retire {
+        retirement_id: Option<String>,
+        amount: Uint128,
+        retirement_purpose: String,
+        retirement_certificate_url: String,
+        beneficiary: Option<String>,
+        beneficiary_name: Option<String>,
+    },
+    /// Replaces the eligibility rules; credits already held stay in the pool.
+    /// Role admin only.
+    UpdateEligibility { eligibility: Eligibility },
+    /// Trusts the carbon credit token contract `token` to report its project
+    /// and batches. Role admin only.
+    ApproveToken { token: String },
+    /// Stops accepting credits of `token`; credits already held stay in the
+    /// pool. Role admin only.
+    RevokeToken { token: String },
+    /// Role admin only
+    SetRedemptionFee {
+        redemption_fee: Decimal,
+        fee_recipient: Option<String>,
+    },
+}
+
+/// Payload of the CW20 receive hook.
+#[cw_serde]
+pub enum PoolReceiveMsg {
+    Deposit {},
+}
+
+impl From<RoleExecuteMsg> for ExecuteMsg {
+    fn from(msg: RoleExecuteMsg) -> Self {
+        ExecuteMsg::Roles(msg)
+    }
+}
+
+impl From<SanctionExecuteMsg> for ExecuteMsg {
+    fn from(msg: SanctionExecuteMsg) -> Self {
+        ExecuteMsg::Sanctions(msg)
+    }
+}
+
+impl From<PauseExecuteMsg> for ExecuteMsg {
+    fn from(msg: PauseExecuteMsg) -> Self {
+        ExecuteMsg::Pause(msg)
+    }
+}
+
+impl PoolExecuteMsg {
+    /// The circuit breaker that stops this message
+    pub fn pause_category(&self) -> PauseCategory {
+        match self {
+            PoolExecuteMsg::Receive(_) => PauseCategory::Issuance,
+            PoolExecuteMsg::Redeem { .. } | PoolExecuteMsg::Retire { .. } => {
+                PauseCategory::Retirement
+            }
+            PoolExecuteMsg::UpdateEligibility { .. }
+            | PoolExecuteMsg::ApproveToken { .. }
+            | PoolExecuteMsg::RevokeToken { .. }
+            | PoolExecuteMsg::SetRedemptionFee { .. } => PauseCategory::RecordKeeping,
+        }
+    }
+}
+
+impl From<PoolExecuteMsg> for ExecuteMsg {
+    fn from(msg: PoolExecuteMsg) -> Self {
+        ExecuteMsg::Pool(msg)
+    }
+}
+
+// Extended query messages
+#[cw_serde]
+#[derive(QueryResponses)]
+#[serde(untagged)]
+#[query_responses(nested)]
+pub enum QueryMsg {
+    Cw20(Cw20QueryMsg),
+    Roles(RoleQueryMsg),
+    Pause(PauseQueryMsg),
+    Sanctions(SanctionQueryMsg),
+    Pool(PoolQueryMsg),
+}
+
+// Pool specific queries
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum PoolQueryMsg {
+    #[returns(PoolInfo)]
+    PoolInfo {},
+    /// Whether credits of `token` would be accepted, those of batch
+    /// `batch_id` or, without one, of the project's vintage, and why not
+    #[returns(EligibilityResponse)]
+    CheckEligibility {
+        token: String,
+        batch_id: Option<u64>,
+    },
+    /// The approved carbon credit token contracts, in address order
+    #[returns(Vec<Addr>)]
+    ApprovedTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Vec<Holding>)]
+    Holdings {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(PoolRetirement)]
+    PoolRetirement { retirement_id: String },
+    #[returns(Vec<(String, PoolRetirement)>)]
+    AllPoolRetirements {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(ReconcileSupplyResponse)]
+    ReconcileSupply {},
+}
+
+impl From<RoleQueryMsg> for QueryMsg {
+    fn from(msg: RoleQueryMsg) -> Self {
+        QueryMsg::Roles(msg)
+    }
+}
+
+impl From<SanctionQueryMsg> for QueryMsg {
+    fn from(msg: SanctionQueryMsg) -> Self {
+        QueryMsg::Sanctions(msg)
+    }
+}
+
+impl From<PauseQueryMsg> for QueryMsg {
+    fn from(msg: PauseQueryMsg) -> Self {
+        QueryMsg::Pause(msg)
+    }
+}
+
+impl From<PoolQueryMsg> for QueryMsg {
+    fn from(msg: PoolQueryMsg) -> Self {
+        QueryMsg::Pool(msg)
+    }
+}
+
+#[cw_serde]
+pub struct EligibilityResponse {
+    pub eligible: bool,
+    pub reason: Option<String>,
+}
+
+/// The credits of one token the pool holds.
+#[cw_serde]
+pub struct Holding {
+    pub token: Addr,
+    pub project_id: String,
+    pub vintage_year: u32,
+    pub credits: Uint128,
+}
+
+#[cw_serde]
+pub struct ReconcileSupplyResponse {
+    /// The credits held against the pool token supply. Burning pool tokens
+    /// with a plain CW20 `burn` leaves their credits in the pool.
+    pub supply: Drift<Uint128>,
+    /// The credits held against the pool's balances at the carbon credit
+    /// tokens, which fall short when a token reverses or expires credits the
+    /// pool holds
+    pub balances: Drift<Uint128>,
+    pub reconciled: bool,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,456 @@
+//! Test fixture for the carbon pool: a `SuiteBuilder` that instantiates the
+//! pool in a multi-test `App` next to the carbon credit token code, and a
+//! `Suite` with one helper per message so tests read as a sequence of actions.
+
+use anyhow::Result as AnyResult;
+use carbon_credit_token::msg::{
+    CarbonCreditExecuteMsg, CarbonCreditQueryMsg, ExecuteMsg as TokenExecuteMsg,
+    InstantiateMsg as TokenInstantiateMsg, QueryMsg as TokenQueryMsg,
+};
+use carbon_credit_token::state::{CarbonCreditInfo, ProjectType, RetirementRecord};
+use cosmwasm_std::{to_json_binary, Addr, Decimal, Empty, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, MinterResponse, TokenInfoResponse};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use rwa_common::{Cw20ExecuteMsg, Cw20QueryMsg};
+
+use crate::msg::{
+    EligibilityResponse, ExecuteMsg, Holding, InstantiateMsg, PoolExecuteMsg, PoolQueryMsg,
+    PoolReceiveMsg, QueryMsg, ReconcileSupplyResponse,
+};
+use crate::state::{Eligibility, PoolInfo, PoolRetirement};
+
+pub const ADMIN: &str = "admin";
+pub const DEVELOPER: &str = "developer";
+pub const VERIFIER: &str = "verifier";
+pub const TREASURY: &str = "treasury";
+
+fn contract_carbon_pool() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_migrate(crate::contract::migrate);
+    Box::new(contract)
+}
+
+fn contract_carbon_credit() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        carbon_credit_token::execute,
+        carbon_credit_token::instantiate,
+        carbon_credit_token::query,
+    )
+    .with_reply(carbon_credit_token::reply);
+    Box::new(contract)
+}
+
+fn contract_cw20() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// The project of a carbon credit token to deposit from.
+pub struct Project {
+    pub project_id: &'static str,
+    pub project_type: ProjectType,
+    pub vintage_year: u32,
+}
+
+pub struct SuiteBuilder {
+    redemption_fee: Decimal,
+}
+
+impl SuiteBuilder {
+    pub fn new() -> Self {
+        Self {
+            redemption_fee: Decimal::zero(),
+        }
+    }
+
+    pub fn with_redemption_fee(mut self, redemption_fee: Decimal) -> Self {
+        self.redemption_fee = redemption_fee;
+        self
+    }
+
+    /// A pool of nature-based credits of 2020 or later.
+    pub fn instantiate_msg(&self) -> InstantiateMsg {
+        InstantiateMsg {
+            cw20_base: Cw20InstantiateMsg {
+                name: "Nature Carbon Pool".to_string(),
+                symbol: "NCP".to_string(),
+                decimals: 0,
+                initial_balances: vec![],
+                mint: None,
+                marketing: None,
+            },
+            pool_info: PoolInfo {
+                pool_name: "Nature-based 2020+".to_string(),
+                eligibility: Eligibility {
+                    min_vintage_year: Some(2020),
+                    max_vintage_year: None,
+                    project_types: Some(vec![
+                        ProjectType::ForestConservation,
+                        ProjectType::BlueCarbon,
+                    ]),
+                    verification_standards: None,
+                    countries: None,
+                },
+                redemption_fee: self.redemption_fee,
+                fee_recipient: Addr::unchecked(TREASURY),
+            },
+            admin: None,
+            // Multi-test does not serve the sanction module's stargate query
+            sanction_screening: Some(false),
+        }
+    }
+
+    pub fn build(self) -> Suite {
+        let mut app = App::default();
+        let carbon_credit_code_id = app.store_code(contract_carbon_credit());
+        let code_id = app.store_code(contract_carbon_pool());
+        let cw20_code_id = app.store_code(contract_cw20());
+
+        let contract = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked(ADMIN),
+                &self.instantiate_msg(),
+                &[],
+                "carbon-pool",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+
+        Suite {
+            app,
+            code_id,
+            carbon_credit_code_id,
+            cw20_code_id,
+            contract,
+        }
+    }
+}
+
+pub struct Suite {
+    pub app: App,
+    pub code_id: u64,
+    pub carbon_credit_code_id: u64,
+    pub cw20_code_id: u64,
+    pub contract: Addr,
+}
+
+impl Suite {
+    /// Instantiates a carbon credit token for `project` with `credits` held
+    /// by the developer, and approves it.
+    pub fn carbon_credit_token(&mut self, project: Project, credits: u128) -> Addr {
+        let token = self.unapproved_carbon_credit_token(project, credits);
+        self.approve_token(ADMIN, &token).unwrap();
+        token
+    }
+
+    /// Instantiates a carbon credit token for `project` with `credits` held
+    /// by the developer, without approving it.
+    pub fn unapproved_carbon_credit_token(&mut self, project: Project, credits: u128) -> Addr {
+        let msg = TokenInstantiateMsg {
+            cw20_base: Cw20InstantiateMsg {
+                name: format!("Carbon Credit {}", project.project_id),
+                symbol: "CCT".to_string(),
+                decimals: 0,
+                initial_balances: vec![Cw20Coin {
+                    address: DEVELOPER.to_string(),
+                    amount: credits.into(),
+                }]
+                .into_iter()
+                .filter(|coin| !coin.amount.is_zero())
+                .collect(),
+                mint: Some(MinterResponse {
+                    minter: DEVELOPER.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
+            },
+            carbon_credit_info: CarbonCreditInfo {
+                project_id: project.project_id.to_string(),
+                project_name: format!("Project {}", project.project_id),
+                project_type: project.project_type,
+                verification_standard: "VCS".to_string(),
+                vintage_year: project.vintage_year,
+                country: "Indonesia".to_string(),
+                total_credits_issued: credits.into(),
+                credits_retired: Uint128::zero(),
+                credits_available: credits.into(),
+                co2_equivalent_per_credit: Decimal::one(),
+                credits_buffered: Uint128::zero(),
+                verification_body: Addr::unchecked(VERIFIER),
+                project_developer: Addr::unchecked(DEVELOPER),
+            },
+            admin: None,
+            sanction_screening: Some(false),
+            certificate_code_id: None,
+        };
+        self.app
+            .instantiate_contract(
+                self.carbon_credit_code_id,
+                Addr::unchecked(ADMIN),
+                &msg,
+                &[],
+                project.project_id,
+                None,
+            )
+            .unwrap()
+    }
+
+    /// Instantiates a plain CW20 token with `amount` held by the developer.
+    pub fn cw20_token(&mut self, amount: u128) -> Addr {
+        let msg = Cw20InstantiateMsg {
+            name: "Not A Carbon Credit".to_string(),
+            symbol: "NACC".to_string(),
+            decimals: 0,
+            initial_balances: vec![Cw20Coin {
+                address: DEVELOPER.to_string(),
+                amount: amount.into(),
+            }],
+            mint: None,
+            marketing: None,
+        };
+        self.app
+            .instantiate_contract(
+                self.cw20_code_id,
+                Addr::unchecked(ADMIN),
+                &msg,
+                &[],
+                "cw20",
+                None,
+            )
+            .unwrap()
+    }
+
+    /// Verifies `credits` of `vintage_year` at the carbon credit `token`,
+    /// issued to the developer in a new batch.
+    pub fn verify_credits(
+        &mut self,
+        token: &Addr,
+        credits: u128,
+        vintage_year: u32,
+    ) -> AnyResult<AppResponse> {
+        let verify = CarbonCreditExecuteMsg::VerifyCredits {
+            verification_id: None,
+            credits_to_verify: credits.into(),
+            verification_report_url: "https://registry.example/report".to_string(),
+            recipients: None,
+            vintage_year: Some(vintage_year),
+            methodology_version: None,
+            registry_serials: None,
+            forward_series: None,
+        };
+        self.app.execute_contract(
+            Addr::unchecked(VERIFIER),
+            token.clone(),
+            &TokenExecuteMsg::from(verify),
+            &[],
+        )
+    }
+
+    pub fn execute(&mut self, sender: &str, msg: impl Into<ExecuteMsg>) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &msg.into(),
+            &[],
+        )
+    }
+
+    /// Sends `amount` of `token` to the pool with a `Deposit` payload.
+    pub fn deposit(&mut self, sender: &str, token: &Addr, amount: u128) -> AnyResult<AppResponse> {
+        let send = Cw20ExecuteMsg::Send {
+            contract: self.contract.to_string(),
+            amount: amount.into(),
+            msg: to_json_binary(&PoolReceiveMsg::Deposit {}).unwrap(),
+        };
+        self.app
+            .execute_contract(Addr::unchecked(sender), token.clone(), &send, &[])
+    }
+
+    pub fn redeem(&mut self, sender: &str, token: &Addr, amount: u128) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            PoolExecuteMsg::Redeem {
+                token: token.to_string(),
+                amount: amount.into(),
+            },
+        )
+    }
+
+    pub fn burn(&mut self, sender: &str, amount: u128) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            ExecuteMsg::Cw20(Cw20ExecuteMsg::Burn {
+                amount: amount.into(),
+            }),
+        )
+    }
+
+    pub fn retire(
+        &mut self,
+        sender: &str,
+        retirement_id: &str,
+        amount: u128,
+        beneficiary: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            PoolExecuteMsg::Retire {
+                retirement_id: Some(retirement_id.to_string()),
+                amount: amount.into(),
+                retirement_purpose: "2024 scope 1 offset".to_string(),
+                retirement_certificate_url: format!("https://registry.example/{retirement_id}"),
+                beneficiary: beneficiary.map(str::to_string),
+                beneficiary_name: Some("Acme Corp".to_string()),
+            },
+        )
+    }
+
+    pub fn update_eligibility(
+        &mut self,
+        sender: &str,
+        eligibility: Eligibility,
+    ) -> AnyResult<AppResponse> {
+        self.execute(sender, PoolExecuteMsg::UpdateEligibility { eligibility })
+    }
+
+    pub fn approve_token(&mut self, sender: &str, token: &Addr) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            PoolExecuteMsg::ApproveToken {
+                token: token.to_string(),
+            },
+        )
+    }
+
+    pub fn revoke_token(&mut self, sender: &str, token: &Addr) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            PoolExecuteMsg::RevokeToken {
+                token: token.to_string(),
+            },
+        )
+    }
+
+    pub fn set_redemption_fee(
+        &mut self,
+        sender: &str,
+        redemption_fee: Decimal,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            PoolExecuteMsg::SetRedemptionFee {
+                redemption_fee,
+                fee_recipient: None,
+            },
+        )
+    }
+
+    fn query<T: serde::de::DeserializeOwned>(&self, msg: impl Into<QueryMsg>) -> T {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &msg.into())
+            .unwrap()
+    }
+
+    pub fn pool_info(&self) -> PoolInfo {
+        self.query(PoolQueryMsg::PoolInfo {})
+    }
+
+    pub fn check_eligibility(&self, token: &Addr) -> EligibilityResponse {
+        self.query(PoolQueryMsg::CheckEligibility {
+            token: token.to_string(),
+            batch_id: None,
+        })
+    }
+
+    pub fn check_batch_eligibility(&self, token: &Addr, batch_id: u64) -> EligibilityResponse {
+        self.query(PoolQueryMsg::CheckEligibility {
+            token: token.to_string(),
+            batch_id: Some(batch_id),
+        })
+    }
+
+    pub fn approved_tokens(&self) -> Vec<Addr> {
+        self.query(PoolQueryMsg::ApprovedTokens {
+            start_after: None,
+            limit: None,
+        })
+    }
+
+    /// The credits held of each token, in token address order.
+    pub fn holdings(&self) -> Vec<Holding> {
+        self.query(PoolQueryMsg::Holdings {
+            start_after: None,
+            limit: None,
+        })
+    }
+
+    /// The credits held of `token`.
+    pub fn held(&self, token: &Addr) -> Uint128 {
+        self.holdings()
+            .into_iter()
+            .find(|holding| holding.token == *token)
+            .map_or(Uint128::zero(), |holding| holding.credits)
+    }
+
+    pub fn pool_retirement(&self, retirement_id: &str) -> PoolRetirement {
+        self.query(PoolQueryMsg::PoolRetirement {
+            retirement_id: retirement_id.to_string(),
+        })
+    }
+
+    pub fn reconcile_supply(&self) -> ReconcileSupplyResponse {
+        self.query(PoolQueryMsg::ReconcileSupply {})
+    }
+
+    /// The pool token balance of `address`.
+    pub fn balance(&self, address: &str) -> Uint128 {
+        self.token_balance(&self.contract, address)
+    }
+
+    /// The balance of `address` at the CW20 `token`.
+    pub fn token_balance(&self, token: &Addr, address: &str) -> Uint128 {
+        let res: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                token,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        res.balance
+    }
+
+    pub fn total_supply(&self) -> Uint128 {
+        let res: TokenInfoResponse = self.query(QueryMsg::Cw20(Cw20QueryMsg::TokenInfo {}));
+        res.total_supply
+    }
+
+    /// The retirements recorded by the carbon credit `token`.
+    pub fn token_retirements(&self, token: &Addr) -> Vec<RetirementRecord> {
+        let records: Vec<(String, RetirementRecord)> = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                token,
+                &TokenQueryMsg::from(CarbonCreditQueryMsg::AllRetirementRecords {
+                    start_after: None,
+                    limit: None,
+                }),
+            )
+            .unwrap();
+        records.into_iter().map(|(_, record)| record).collect()
+    }
+}
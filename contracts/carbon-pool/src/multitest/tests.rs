@@ -0,0 +1,352 @@
+use carbon_credit_token::state::ProjectType;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::MinterResponse;
+use cw_multi_test::Executor;
+use rwa_common::CommonError;
+
+use super::suite::{Project, SuiteBuilder, ADMIN, DEVELOPER, TREASURY};
+use crate::error::ContractError;
+use crate::msg::{EligibilityResponse, Holding};
+use crate::state::TokenAmount;
+
+const FOREST_2021: Project = Project {
+    project_id: "VCS-1477",
+    project_type: ProjectType::ForestConservation,
+    vintage_year: 2021,
+};
+const MANGROVE_2023: Project = Project {
+    project_id: "VCS-2250",
+    project_type: ProjectType::BlueCarbon,
+    vintage_year: 2023,
+};
+
+#[test]
+fn eligible_credits_mint_pool_tokens_one_for_one() {
+    let mut suite = SuiteBuilder::new().build();
+    let pool = suite.contract.to_string();
+    let forest = suite.carbon_credit_token(FOREST_2021, 1_000);
+    let mangrove = suite.carbon_credit_token(MANGROVE_2023, 500);
+
+    suite.deposit(DEVELOPER, &forest, 600).unwrap();
+    suite.deposit(DEVELOPER, &mangrove, 500).unwrap();
+
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(1_100));
+    assert_eq!(suite.total_supply(), Uint128::new(1_100));
+    assert_eq!(suite.token_balance(&forest, DEVELOPER), Uint128::new(400));
+    assert_eq!(suite.token_balance(&forest, &pool), Uint128::new(600));
+    assert_eq!(
+        suite.holdings(),
+        [
+            Holding {
+                token: forest,
+                project_id: "VCS-1477".to_string(),
+                vintage_year: 2021,
+                credits: Uint128::new(600),
+            },
+            Holding {
+                token: mangrove,
+                project_id: "VCS-2250".to_string(),
+                vintage_year: 2023,
+                credits: Uint128::new(500),
+            },
+        ]
+    );
+    assert!(suite.reconcile_supply().reconciled);
+}
+
+#[test]
+fn ineligible_credits_are_rejected() {
+    let mut suite = SuiteBuilder::new().build();
+    let old = suite.carbon_credit_token(
+        Project {
+            project_id: "VCS-191",
+            project_type: ProjectType::ForestConservation,
+            vintage_year: 2018,
+        },
+        100,
+    );
+    let solar = suite.carbon_credit_token(
+        Project {
+            project_id: "GS-7",
+            project_type: ProjectType::RenewableEnergy,
+            vintage_year: 2022,
+        },
+        100,
+    );
+    // Any CW20 could claim to be a carbon credit token, and anyone can
+    // instantiate the carbon credit token code with a project of its choosing
+    let impostor = suite.cw20_token(100);
+    let unapproved = suite.unapproved_carbon_credit_token(FOREST_2021, 100);
+    let not_approved = "the token is not an approved carbon credit token".to_string();
+
+    let rejections = [
+        (old, "vintage 2018 is before 2020".to_string()),
+        (
+            solar,
+            "project type renewable_energy is not accepted".to_string(),
+        ),
+        (impostor, not_approved.clone()),
+        (unapproved, not_approved),
+    ];
+    for (token, reason) in rejections {
+        assert_eq!(
+            suite.check_eligibility(&token),
+            EligibilityResponse {
+                eligible: false,
+                reason: Some(reason.clone()),
+            }
+        );
+        let err = suite.deposit(DEVELOPER, &token, 100).unwrap_err();
+        assert_eq!(
+            ContractError::IneligibleCredits {
+                token: token.to_string(),
+                reason,
+            },
+            err.downcast().unwrap()
+        );
+        assert_eq!(suite.token_balance(&token, DEVELOPER), Uint128::new(100));
+    }
+    assert_eq!(suite.total_supply(), Uint128::zero());
+}
+
+#[test]
+fn approved_tokens_are_pooled_under_the_vintage_of_their_batch() {
+    let mut suite = SuiteBuilder::new().build();
+    let forest = suite.unapproved_carbon_credit_token(FOREST_2021, 0);
+    let err = suite.approve_token(DEVELOPER, &forest).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "approve tokens".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+    suite.approve_token(ADMIN, &forest).unwrap();
+    assert_eq!(suite.approved_tokens(), vec![forest.clone()]);
+
+    // The batch's vintage counts, not the project's
+    let old = suite.carbon_credit_token(FOREST_2021, 0);
+    suite.verify_credits(&old, 100, 2018).unwrap();
+    let err = suite.deposit(DEVELOPER, &old, 100).unwrap_err();
+    assert_eq!(
+        ContractError::IneligibleCredits {
+            token: old.to_string(),
+            reason: "vintage 2018 is before 2020".to_string(),
+        },
+        err.downcast().unwrap()
+    );
+    suite.verify_credits(&forest, 100, 2022).unwrap();
+    suite.deposit(DEVELOPER, &forest, 60).unwrap();
+    assert_eq!(suite.holdings()[0].vintage_year, 2022);
+
+    // A token's credits are pooled under the vintage of its first deposit,
+    // whichever batches later deposits draw on
+    suite.verify_credits(&forest, 50, 2023).unwrap();
+    assert_eq!(
+        suite.check_batch_eligibility(&forest, 2),
+        EligibilityResponse {
+            eligible: false,
+            reason: Some("its credits are pooled under vintage 2022, not 2023".to_string()),
+        }
+    );
+    suite.deposit(DEVELOPER, &forest, 40).unwrap();
+    let err = suite.deposit(DEVELOPER, &forest, 10).unwrap_err();
+    assert_eq!(
+        ContractError::IneligibleCredits {
+            token: forest.to_string(),
+            reason: "its credits are pooled under vintage 2022, not 2023".to_string(),
+        },
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.held(&forest), Uint128::new(100));
+
+    // Nor can a single deposit draw on batches of several vintages
+    let mangrove = suite.carbon_credit_token(MANGROVE_2023, 0);
+    suite.verify_credits(&mangrove, 30, 2022).unwrap();
+    suite.verify_credits(&mangrove, 30, 2023).unwrap();
+    let err = suite.deposit(DEVELOPER, &mangrove, 50).unwrap_err();
+    assert_eq!(
+        ContractError::IneligibleCredits {
+            token: mangrove.to_string(),
+            reason: "the credits sent span vintages 2022 to 2023".to_string(),
+        },
+        err.downcast().unwrap()
+    );
+    suite.deposit(DEVELOPER, &mangrove, 30).unwrap();
+
+    // Revoking a token keeps its credits in the pool
+    suite.revoke_token(ADMIN, &forest).unwrap();
+    assert!(!suite.approved_tokens().contains(&forest));
+    assert_eq!(suite.held(&forest), Uint128::new(100));
+    suite.redeem(DEVELOPER, &forest, 100).unwrap();
+}
+
+#[test]
+fn selective_redemption_pays_the_fee_in_pool_tokens() {
+    let mut suite = SuiteBuilder::new()
+        .with_redemption_fee(Decimal::percent(5))
+        .build();
+    let forest = suite.carbon_credit_token(FOREST_2021, 1_000);
+    let mangrove = suite.carbon_credit_token(MANGROVE_2023, 500);
+    suite.deposit(DEVELOPER, &forest, 1_000).unwrap();
+    suite.deposit(DEVELOPER, &mangrove, 500).unwrap();
+
+    // 5% of 201 is 10.05, rounded down
+    suite.redeem(DEVELOPER, &mangrove, 201).unwrap();
+    assert_eq!(suite.token_balance(&mangrove, DEVELOPER), Uint128::new(191));
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(1_299));
+    assert_eq!(suite.balance(TREASURY), Uint128::new(10));
+    assert_eq!(suite.held(&mangrove), Uint128::new(309));
+    assert_eq!(suite.total_supply(), Uint128::new(1_309));
+    assert!(suite.reconcile_supply().reconciled);
+
+    let err = suite.redeem(DEVELOPER, &mangrove, 1_000).unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientHoldings {
+            token: mangrove.to_string(),
+            requested: Uint128::new(950),
+            held: Uint128::new(309),
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn retiring_pool_tokens_retires_the_oldest_vintages() {
+    let mut suite = SuiteBuilder::new().build();
+    let pool = suite.contract.clone();
+    let mangrove = suite.carbon_credit_token(MANGROVE_2023, 500);
+    let forest = suite.carbon_credit_token(FOREST_2021, 300);
+    suite.deposit(DEVELOPER, &mangrove, 500).unwrap();
+    suite.deposit(DEVELOPER, &forest, 300).unwrap();
+
+    suite.retire(DEVELOPER, "r-1", 400, Some("acme")).unwrap();
+    assert_eq!(suite.balance(DEVELOPER), Uint128::new(400));
+    assert_eq!(suite.total_supply(), Uint128::new(400));
+
+    let record = suite.pool_retirement("r-1");
+    assert_eq!(record.credits_retired, Uint128::new(400));
+    assert_eq!(record.retirement_entity, Addr::unchecked(DEVELOPER));
+    assert_eq!(record.beneficiary, Some(Addr::unchecked("acme")));
+    assert_eq!(
+        record.retired,
+        [
+            TokenAmount {
+                token: forest.clone(),
+                credits: Uint128::new(300),
+            },
+            TokenAmount {
+                token: mangrove.clone(),
+                credits: Uint128::new(100),
+            },
+        ]
+    );
+    assert_eq!(suite.held(&forest), Uint128::zero());
+    assert_eq!(suite.held(&mangrove), Uint128::new(400));
+
+    // Each token records the retirement by the pool, for the beneficiary
+    for (token, credits) in [(&forest, 300), (&mangrove, 100)] {
+        let retirements = suite.token_retirements(token);
+        assert_eq!(retirements.len(), 1);
+        assert_eq!(retirements[0].credits_retired, Uint128::new(credits));
+        assert_eq!(retirements[0].retirement_entity, pool);
+        assert_eq!(retirements[0].beneficiary, Some(Addr::unchecked("acme")));
+        assert_eq!(
+            retirements[0].beneficiary_name.as_deref(),
+            Some("Acme Corp")
+        );
+    }
+    assert!(suite.reconcile_supply().reconciled);
+}
+
+#[test]
+fn pool_settings_are_changed_by_the_role_admin() {
+    let mut suite = SuiteBuilder::new().build();
+    let forest = suite.carbon_credit_token(FOREST_2021, 1_000);
+
+    let err = suite
+        .set_redemption_fee(DEVELOPER, Decimal::percent(2))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "set the redemption fee".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+    let err = suite.set_redemption_fee(ADMIN, Decimal::one()).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidRedemptionFee {
+            fee: Decimal::one()
+        },
+        err.downcast().unwrap()
+    );
+    suite
+        .set_redemption_fee(ADMIN, Decimal::percent(2))
+        .unwrap();
+    assert_eq!(suite.pool_info().redemption_fee, Decimal::percent(2));
+
+    suite.deposit(DEVELOPER, &forest, 100).unwrap();
+    let mut eligibility = suite.pool_info().eligibility;
+    eligibility.min_vintage_year = Some(2022);
+    let err = suite
+        .update_eligibility(DEVELOPER, eligibility.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::NotRoleAdmin {
+            action: "update eligibility".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+    suite.update_eligibility(ADMIN, eligibility).unwrap();
+
+    let err = suite.deposit(DEVELOPER, &forest, 100).unwrap_err();
+    assert_eq!(
+        ContractError::IneligibleCredits {
+            token: forest.to_string(),
+            reason: "vintage 2021 is before 2022".to_string(),
+        },
+        err.downcast().unwrap()
+    );
+
+    // Credits deposited before stay redeemable
+    suite.redeem(DEVELOPER, &forest, 100).unwrap();
+    assert_eq!(suite.token_balance(&forest, DEVELOPER), Uint128::new(998));
+    assert_eq!(suite.balance(TREASURY), Uint128::new(2));
+}
+
+#[test]
+fn pool_tokens_cannot_be_minted_without_deposits() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let mut msg = SuiteBuilder::new().instantiate_msg();
+    msg.cw20_base.mint = Some(MinterResponse {
+        minter: ADMIN.to_string(),
+        cap: None,
+    });
+    let err = suite
+        .app
+        .instantiate_contract(
+            suite.code_id,
+            Addr::unchecked(ADMIN),
+            &msg,
+            &[],
+            "minted-pool",
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::UnbackedPoolTokens {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn pool_tokens_cannot_be_burned_without_their_credits() {
+    let mut suite = SuiteBuilder::new().build();
+    let forest = suite.carbon_credit_token(FOREST_2021, 1_000);
+    suite.deposit(DEVELOPER, &forest, 600).unwrap();
+
+    let err = suite.burn(DEVELOPER, 100).unwrap_err();
+    assert_eq!(ContractError::BurnDisabled {}, err.downcast().unwrap());
+    assert_eq!(suite.total_supply(), Uint128::new(600));
+    assert!(suite.reconcile_supply().reconciled);
+}
@@ -0,0 +1,68 @@
+use carbon_credit_token::state::ProjectType;
+use cosmwasm_std::{Addr, Decimal, Empty, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+use rwa_common::pause::GUARDIAN;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// Custom state for pool specific data
+pub const POOL_INFO: Item<PoolInfo> = Item::new("pool_info");
+pub const POOL_RETIREMENTS: Map<&str, PoolRetirement> = Map::new("pool_retirements");
+// The carbon credit token contracts the role admin approved. Any contract,
+// even an instance of the carbon credit token code, can answer a carbon
+// credit info query, so only approved tokens are trusted to report their
+// project truthfully.
+pub const APPROVED_TOKENS: Map<&Addr, Empty> = Map::new("approved_tokens");
+// The project and vintage of every token the pool holds credits of
+pub const SOURCES: Map<&Addr, Source> = Map::new("sources");
+// Credits held per vintage and token, so retirements can draw the oldest first
+pub const HOLDINGS: Map<(u32, &Addr), Uint128> = Map::new("holdings");
+
+// Roles kept in the shared role registry. Pool settings are changed by the
+// role admin; the pool has no roles of its own.
+pub const ROLES: &[&str] = &[GUARDIAN];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolInfo {
+    pub pool_name: String,
+    pub eligibility: Eligibility,
+    pub redemption_fee: Decimal, // share of a selective redemption paid as a fee
+    pub fee_recipient: Addr,     // receives redemption fees in pool tokens
+}
+
+/// The credits a pool accepts, of approved tokens only. Criteria left unset
+/// accept any value.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Eligibility {
+    pub min_vintage_year: Option<u32>,
+    pub max_vintage_year: Option<u32>,
+    pub project_types: Option<Vec<ProjectType>>,
+    pub verification_standards: Option<Vec<String>>,
+    pub countries: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Source {
+    pub project_id: String,
+    pub vintage_year: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolRetirement {
+    pub retirement_id: String,
+    pub retirement_date: Timestamp,
+    pub credits_retired: Uint128,
+    pub retirement_purpose: String,
+    pub retirement_entity: Addr,
+    pub beneficiary: Option<Addr>,
+    pub beneficiary_name: Option<String>,
+    // The underlying credits retired, per token, oldest vintage first
+    pub retired: Vec<TokenAmount>,
+}
+
+/// An amount of one token's credits.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenAmount {
+    pub token: Addr,
+    pub credits: Uint128,
+}
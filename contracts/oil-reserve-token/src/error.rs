@@ -0,0 +1,40 @@
+//! Every variant renders as `<Code>: <description>`, where `<Code>` is the
+//! variant name. Codes are part of the contract's interface and never change
+//! meaning; clients should match on the part before the first colon.
+
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use rwa_common::CommonError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("Std: {0}")]
+    Std(#[from] StdError),
+
+    #[error("Overflow: {0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Cw20Base: {0}")]
+    Cw20Base(#[from] cw20_base::ContractError),
+
+    #[error(transparent)]
+    Common(#[from] CommonError),
+
+    #[error("NotExtractionCompany: only the extraction company can {action}")]
+    NotExtractionCompany { action: String },
+
+    #[error("NotReserveAuditor: only the reserve auditor can {action}")]
+    NotReserveAuditor { action: String },
+
+    #[error("ExceedsAvailableBarrels: requested {requested}, available {available}")]
+    ExceedsAvailableBarrels {
+        requested: Uint128,
+        available: Uint128,
+    },
+
+    #[error("ReserveAuditNotFound: no reserve audit with id {audit_id}")]
+    ReserveAuditNotFound { audit_id: String },
+
+    #[error("TradingRecordNotFound: no trading record with id {trade_id}")]
+    TradingRecordNotFound { trade_id: String },
+}
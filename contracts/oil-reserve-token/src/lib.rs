@@ -1,7 +1,12 @@
 pub mod contract;
+pub mod error;
 pub mod msg;
 pub mod state;
 
-pub use contract::{instantiate, execute, query};
-pub use msg::{InstantiateMsg, ExecuteMsg, QueryMsg};
-pub use state::{OilReserveInfo, ExtractionRecord, ReserveAudit, TradingRecord};
+#[cfg(test)]
+mod multitest;
+
+pub use crate::contract::{execute, instantiate, migrate, query};
+pub use crate::error::ContractError;
+pub use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+pub use crate::state::{ExtractionRecord, OilReserveInfo, ReserveAudit, TradingRecord};
@@ -0,0 +1,210 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Timestamp, Uint128};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use rwa_common::{
+    Cw20ExecuteMsg, Cw20QueryMsg, Drift, PauseCategory, PauseExecuteMsg, PauseQueryMsg,
+    RoleExecuteMsg, RoleQueryMsg, SanctionExecuteMsg, SanctionQueryMsg,
+};
+
+use crate::state::{
+    AuditStatus, ExtractionMethod, ExtractionRecord, OilReserveInfo, OilType, ReserveAudit,
+    TradeStatus, TradeType, TradingRecord,
+};
+
+// Extended instantiate message
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub cw20_base: Cw20InstantiateMsg,
+    pub oil_reserve_info: OilReserveInfo,
+    /// Administrator of the role registry, defaulting to the sender
+    pub admin: Option<String>,
+    /// Whether token movements are screened against the sanction blacklist,
+    /// defaulting to true
+    pub sanction_screening: Option<bool>,
+}
+
+// Extended execute messages: the standard CW20 messages, the shared role, pause
+// and sanction messages, and the oil reserve specific ones, sharing the same JSON
+// namespace
+#[cw_serde]
+#[serde(untagged)]
+pub enum ExecuteMsg {
+    Cw20(Cw20ExecuteMsg),
+    Roles(RoleExecuteMsg),
+    Pause(PauseExecuteMsg),
+    Sanctions(SanctionExecuteMsg),
+    OilReserve(OilReserveExecuteMsg),
+}
+
+// Oil reserve specific messages. Messages that create a record take an
+// optional id: without one the next generated id is used. Either way the id is
+// returned in the response data.
+#[cw_serde]
+pub enum OilReserveExecuteMsg {
+    RecordExtraction {
+        extraction_id: Option<String>,
+        barrels_extracted: Uint128,
+        extraction_method: ExtractionMethod,
+        environmental_impact_score: Decimal,
+        carbon_footprint_per_barrel: Decimal,
+        extraction_cost_per_barrel: Decimal,
+        quality_certificate_url: String,
+    },
+    ConductReserveAudit {
+        audit_id: Option<String>,
+        audited_reserves: Uint128,
+        audit_report_url: String,
+        reserve_quality_grade: String,
+        extraction_feasibility_score: Decimal,
+    },
+    UpdateAuditStatus {
+        audit_id: String,
+        status: AuditStatus,
+    },
+    RecordTrade {
+        trade_id: Option<String>,
+        seller: String,
+        buyer: String,
+        tokens_traded: Uint128,
+        price_per_token: Decimal,
+        trade_type: TradeType,
+        settlement_date: Timestamp,
+    },
+    UpdateTradeStatus {
+        trade_id: String,
+        status: TradeStatus,
+    },
+}
+
+impl From<RoleExecuteMsg> for ExecuteMsg {
+    fn from(msg: RoleExecuteMsg) -> Self {
+        ExecuteMsg::Roles(msg)
+    }
+}
+
+impl From<SanctionExecuteMsg> for ExecuteMsg {
+    fn from(msg: SanctionExecuteMsg) -> Self {
+        ExecuteMsg::Sanctions(msg)
+    }
+}
+
+impl From<PauseExecuteMsg> for ExecuteMsg {
+    fn from(msg: PauseExecuteMsg) -> Self {
+        ExecuteMsg::Pause(msg)
+    }
+}
+
+impl OilReserveExecuteMsg {
+    /// The circuit breaker that stops this message
+    pub fn pause_category(&self) -> PauseCategory {
+        match self {
+            OilReserveExecuteMsg::RecordExtraction { .. } => PauseCategory::Issuance,
+            OilReserveExecuteMsg::ConductReserveAudit { .. } => PauseCategory::RecordKeeping,
+            OilReserveExecuteMsg::UpdateAuditStatus { .. } => PauseCategory::RecordKeeping,
+            OilReserveExecuteMsg::RecordTrade { .. } => PauseCategory::RecordKeeping,
+            OilReserveExecuteMsg::UpdateTradeStatus { .. } => PauseCategory::RecordKeeping,
+        }
+    }
+}
+
+impl From<OilReserveExecuteMsg> for ExecuteMsg {
+    fn from(msg: OilReserveExecuteMsg) -> Self {
+        ExecuteMsg::OilReserve(msg)
+    }
+}
+
+// Extended query messages
+#[cw_serde]
+#[derive(QueryResponses)]
+#[serde(untagged)]
+#[query_responses(nested)]
+pub enum QueryMsg {
+    Cw20(Cw20QueryMsg),
+    Roles(RoleQueryMsg),
+    Pause(PauseQueryMsg),
+    Sanctions(SanctionQueryMsg),
+    OilReserve(OilReserveQueryMsg),
+}
+
+// Oil reserve specific queries
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum OilReserveQueryMsg {
+    #[returns(OilReserveInfo)]
+    OilReserveInfo {},
+    #[returns(ExtractionRecord)]
+    ExtractionRecord { extraction_id: String },
+    #[returns(Vec<(String, ExtractionRecord)>)]
+    AllExtractionRecords {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(ReserveAudit)]
+    ReserveAudit { audit_id: String },
+    #[returns(Vec<(String, ReserveAudit)>)]
+    AllReserveAudits {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(TradingRecord)]
+    TradingRecord { trade_id: String },
+    #[returns(Vec<(String, TradingRecord)>)]
+    AllTradingRecords {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(Uint128)]
+    AvailableBarrels {},
+    #[returns(Uint128)]
+    ExtractedBarrels {},
+    #[returns(QualityMetricsResponse)]
+    ReserveQualityMetrics {},
+    #[returns(ReconcileSupplyResponse)]
+    ReconcileSupply {},
+}
+
+impl From<RoleQueryMsg> for QueryMsg {
+    fn from(msg: RoleQueryMsg) -> Self {
+        QueryMsg::Roles(msg)
+    }
+}
+
+impl From<SanctionQueryMsg> for QueryMsg {
+    fn from(msg: SanctionQueryMsg) -> Self {
+        QueryMsg::Sanctions(msg)
+    }
+}
+
+impl From<PauseQueryMsg> for QueryMsg {
+    fn from(msg: PauseQueryMsg) -> Self {
+        QueryMsg::Pause(msg)
+    }
+}
+
+impl From<OilReserveQueryMsg> for QueryMsg {
+    fn from(msg: OilReserveQueryMsg) -> Self {
+        QueryMsg::OilReserve(msg)
+    }
+}
+
+#[cw_serde]
+pub struct QualityMetricsResponse {
+    pub api_gravity: Decimal,
+    pub sulfur_content: Decimal,
+    pub oil_type: OilType,
+    pub extraction_feasibility_score: Decimal,
+}
+
+#[cw_serde]
+pub struct ReconcileSupplyResponse {
+    /// `total_reserves_barrels` against `extracted_barrels + available_barrels`
+    pub reserves: Drift<Uint128>,
+    /// The tokens backed by `extracted_barrels` against the CW20 total supply.
+    /// Each extraction rounds its mint down, so a shortfall of up to one token
+    /// per extraction is expected when `barrels_per_token` is fractional.
+    pub supply: Drift<Uint128>,
+    pub reconciled: bool,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,159 @@
+//! Property tests: random sequences of operations run against the contract
+//! and against a plain model of its counters, which must agree after every
+//! step, including on the drift reported by `ReconcileSupply`.
+
+use cosmwasm_std::{Decimal, Uint128};
+use proptest::prelude::*;
+use rwa_common::Drift;
+
+use super::suite::{Suite, SuiteBuilder, OPERATOR};
+
+const HOLDERS: [&str; 3] = [OPERATOR, "refinery", "trader"];
+const TOTAL_RESERVES: u128 = 50_000;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Extract {
+        barrels: u128,
+    },
+    Transfer {
+        from: usize,
+        to: usize,
+        amount: u128,
+    },
+    Burn {
+        holder: usize,
+        amount: u128,
+    },
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let holder = 0..HOLDERS.len();
+    prop_oneof![
+        (1..8_000u128).prop_map(|barrels| Op::Extract { barrels }),
+        (holder.clone(), holder.clone(), 1..4_000u128)
+            .prop_map(|(from, to, amount)| Op::Transfer { from, to, amount }),
+        (holder, 1..1_000u128).prop_map(|(holder, amount)| Op::Burn { holder, amount }),
+    ]
+}
+
+/// What the contract should hold after the operations it accepted.
+#[derive(Debug)]
+struct Model {
+    barrels_per_token_percent: u128,
+    balances: [u128; 3],
+    supply: u128,
+    extracted: u128,
+    available: u128,
+}
+
+impl Model {
+    fn new(barrels_per_token_percent: u128) -> Self {
+        Model {
+            barrels_per_token_percent,
+            balances: [0; 3],
+            supply: 0,
+            extracted: 0,
+            available: TOTAL_RESERVES,
+        }
+    }
+
+    fn tokens_for(&self, barrels: u128) -> u128 {
+        barrels * self.barrels_per_token_percent / 100
+    }
+
+    /// Applies `op` and returns whether the contract is expected to accept it.
+    fn apply(&mut self, op: &Op) -> bool {
+        match *op {
+            Op::Extract { barrels } => {
+                if self.available < barrels {
+                    return false;
+                }
+                let tokens = self.tokens_for(barrels);
+                self.extracted += barrels;
+                self.available -= barrels;
+                self.balances[0] += tokens;
+                self.supply += tokens;
+                true
+            }
+            Op::Transfer { from, to, amount } => {
+                if self.balances[from] < amount {
+                    return false;
+                }
+                self.balances[from] -= amount;
+                self.balances[to] += amount;
+                true
+            }
+            Op::Burn { holder, amount } => {
+                if self.balances[holder] < amount {
+                    return false;
+                }
+                self.balances[holder] -= amount;
+                self.supply -= amount;
+                true
+            }
+        }
+    }
+}
+
+fn execute(suite: &mut Suite, step: usize, op: &Op) -> bool {
+    let res = match *op {
+        Op::Extract { barrels } => suite.record_extraction(OPERATOR, &format!("e-{step}"), barrels),
+        Op::Transfer { from, to, amount } => suite.transfer(HOLDERS[from], HOLDERS[to], amount),
+        Op::Burn { holder, amount } => suite.burn(HOLDERS[holder], amount),
+    };
+    res.is_ok()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn counters_follow_the_model(
+        barrels_per_token_percent in 1..400u128,
+        ops in prop::collection::vec(op(), 1..40),
+    ) {
+        let mut suite = SuiteBuilder::new()
+            .with_reserves(TOTAL_RESERVES)
+            .with_barrels_per_token(Decimal::percent(barrels_per_token_percent as u64))
+            .build();
+        let mut model = Model::new(barrels_per_token_percent);
+        let mut extractions = 0u128;
+        let mut burned = 0u128;
+
+        for (step, op) in ops.iter().enumerate() {
+            let expected = model.apply(op);
+            prop_assert_eq!(execute(&mut suite, step, op), expected, "{:?}", op);
+            if expected {
+                match op {
+                    Op::Extract { .. } => extractions += 1,
+                    Op::Burn { amount, .. } => burned += amount,
+                    Op::Transfer { .. } => {}
+                }
+            }
+
+            let info = suite.oil_reserve_info();
+            prop_assert_eq!(info.extracted_barrels.u128(), model.extracted);
+            prop_assert_eq!(info.available_barrels.u128(), model.available);
+            prop_assert_eq!(suite.total_supply().u128(), model.supply);
+            for (holder, balance) in HOLDERS.iter().zip(model.balances) {
+                prop_assert_eq!(suite.balance(holder).u128(), balance);
+            }
+
+            // Reserves are conserved; the supply drifts from the extracted
+            // barrels by exactly what the model predicts
+            prop_assert_eq!(model.extracted + model.available, TOTAL_RESERVES);
+            let report = suite.reconcile_supply();
+            prop_assert!(report.reserves.is_zero());
+            let backed = model.tokens_for(model.extracted);
+            prop_assert_eq!(
+                &report.supply,
+                &Drift::between(Uint128::new(backed), Uint128::new(model.supply))
+            );
+
+            // Rounding each mint down loses less than one token per extraction
+            prop_assert!(model.supply + burned <= backed);
+            prop_assert!(backed - (model.supply + burned) < extractions.max(1));
+        }
+    }
+}
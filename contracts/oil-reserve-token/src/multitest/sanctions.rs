@@ -0,0 +1,105 @@
+//! Sanction screening. Multi-test does not serve the sanction module's
+//! stargate query, so these tests call the entry points directly and let
+//! `MockSanctionQuerier` answer the blacklist.
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{Decimal, OwnedDeps, Response, Uint128};
+use rwa_common::sanctions::testing::{mock_dependencies_with_blacklist, MockSanctionQuerier};
+use rwa_common::{CommonError, Cw20ExecuteMsg, RoleExecuteMsg};
+
+use super::suite::{SuiteBuilder, ADMIN, OPERATOR};
+use crate::contract::{execute, instantiate};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, OilReserveExecuteMsg};
+use crate::state::{ExtractionMethod, EXTRACTION_COMPANY};
+
+const MALLORY: &str = "mallory";
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockSanctionQuerier>;
+
+fn setup() -> Deps {
+    let mut deps = mock_dependencies_with_blacklist(&[MALLORY]);
+    let mut msg = SuiteBuilder::new()
+        .with_balance(OPERATOR, 1_000)
+        .instantiate_msg(mock_env().block.time);
+    msg.sanction_screening = None;
+    instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+    deps
+}
+
+fn execute_as(
+    deps: &mut Deps,
+    sender: &str,
+    msg: impl Into<ExecuteMsg>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        msg.into(),
+    )
+}
+
+fn sanctioned() -> ContractError {
+    ContractError::Common(CommonError::Sanctioned {
+        address: MALLORY.to_string(),
+    })
+}
+
+#[test]
+fn sanctioned_extraction_company_cannot_mint() {
+    let mut deps = setup();
+    execute_as(
+        &mut deps,
+        ADMIN,
+        RoleExecuteMsg::GrantRole {
+            role: EXTRACTION_COMPANY.to_string(),
+            address: MALLORY.to_string(),
+        },
+    )
+    .unwrap();
+
+    let err = execute_as(
+        &mut deps,
+        MALLORY,
+        OilReserveExecuteMsg::RecordExtraction {
+            extraction_id: None,
+            barrels_extracted: Uint128::new(100),
+            extraction_method: ExtractionMethod::ConventionalDrilling,
+            environmental_impact_score: Decimal::percent(7_500),
+            carbon_footprint_per_barrel: Decimal::percent(5),
+            extraction_cost_per_barrel: Decimal::percent(4_500),
+            quality_certificate_url: "https://quality.example/e".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, sanctioned());
+}
+
+#[test]
+fn tokens_cannot_be_sent_to_sanctioned_accounts() {
+    let mut deps = setup();
+
+    let err = execute_as(
+        &mut deps,
+        OPERATOR,
+        ExecuteMsg::Cw20(Cw20ExecuteMsg::Transfer {
+            recipient: MALLORY.to_string(),
+            amount: Uint128::new(10),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, sanctioned());
+
+    let err = execute_as(
+        &mut deps,
+        OPERATOR,
+        ExecuteMsg::Cw20(Cw20ExecuteMsg::Send {
+            contract: MALLORY.to_string(),
+            amount: Uint128::new(10),
+            msg: Default::default(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, sanctioned());
+}
@@ -0,0 +1,438 @@
+//! Test fixture for the oil reserve contract: a `SuiteBuilder` that
+//! instantiates the contract in a multi-test `App`, and a `Suite` with one
+//! helper per message so tests read as a sequence of actions.
+
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{Addr, Decimal, Empty, Timestamp, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, MinterResponse, TokenInfoResponse};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use rwa_common::pause::PauseStatusResponse;
+use rwa_common::roles::RoleMembersResponse;
+use rwa_common::{
+    Cw20ExecuteMsg, Cw20QueryMsg, PauseCategory, PauseExecuteMsg, PauseQueryMsg, RoleExecuteMsg,
+    RoleQueryMsg,
+};
+
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, OilReserveExecuteMsg, OilReserveQueryMsg,
+    QualityMetricsResponse, QueryMsg, ReconcileSupplyResponse,
+};
+use crate::state::{
+    AuditStatus, ExtractionMethod, ExtractionRecord, OilReserveInfo, OilType, ReserveAudit,
+    TradeStatus, TradeType, TradingRecord,
+};
+
+pub const ADMIN: &str = "admin";
+pub const OPERATOR: &str = "operator";
+pub const AUDITOR: &str = "auditor";
+pub const REGULATOR: &str = "regulator";
+
+fn contract_oil_reserve() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_migrate(crate::contract::migrate);
+    Box::new(contract)
+}
+
+pub struct SuiteBuilder {
+    initial_balances: Vec<Cw20Coin>,
+    total_reserves_barrels: Uint128,
+    barrels_per_token: Decimal,
+    mint_cap: Option<Uint128>,
+}
+
+impl SuiteBuilder {
+    pub fn new() -> Self {
+        Self {
+            initial_balances: vec![],
+            total_reserves_barrels: Uint128::new(1_000_000),
+            barrels_per_token: Decimal::one(),
+            mint_cap: None,
+        }
+    }
+
+    pub fn with_balance(mut self, address: &str, amount: u128) -> Self {
+        self.initial_balances.push(Cw20Coin {
+            address: address.to_string(),
+            amount: amount.into(),
+        });
+        self
+    }
+
+    pub fn with_reserves(mut self, barrels: u128) -> Self {
+        self.total_reserves_barrels = barrels.into();
+        self
+    }
+
+    pub fn with_barrels_per_token(mut self, barrels_per_token: Decimal) -> Self {
+        self.barrels_per_token = barrels_per_token;
+        self
+    }
+
+    pub fn with_mint_cap(mut self, cap: u128) -> Self {
+        self.mint_cap = Some(cap.into());
+        self
+    }
+
+    /// The message `build` instantiates the contract with.
+    pub fn instantiate_msg(&self, start: Timestamp) -> InstantiateMsg {
+        InstantiateMsg {
+            cw20_base: Cw20InstantiateMsg {
+                name: "Permian Basin Reserve".to_string(),
+                symbol: "PBRT".to_string(),
+                decimals: 0,
+                initial_balances: self.initial_balances.clone(),
+                mint: Some(MinterResponse {
+                    minter: OPERATOR.to_string(),
+                    cap: self.mint_cap,
+                }),
+                marketing: None,
+            },
+            oil_reserve_info: OilReserveInfo {
+                reserve_id: "PB-0042".to_string(),
+                reserve_name: "Permian Basin Block 42".to_string(),
+                location: "Texas, USA".to_string(),
+                field_name: "Wolfcamp".to_string(),
+                oil_type: OilType::LightSweet,
+                api_gravity: Decimal::percent(3_900),
+                sulfur_content: Decimal::percent(20),
+                total_reserves_barrels: self.total_reserves_barrels,
+                extracted_barrels: Uint128::zero(),
+                available_barrels: self.total_reserves_barrels,
+                barrels_per_token: self.barrels_per_token,
+                extraction_company: Addr::unchecked(OPERATOR),
+                reserve_auditor: Addr::unchecked(AUDITOR),
+                government_authority: Addr::unchecked(REGULATOR),
+                extraction_start_date: start,
+                estimated_extraction_end_date: start.plus_seconds(20 * 365 * 24 * 60 * 60),
+            },
+            admin: None,
+            // Multi-test does not serve the sanction module's stargate query;
+            // screening is covered by the mock querier tests in sanctions.rs
+            sanction_screening: Some(false),
+        }
+    }
+
+    pub fn build(self) -> Suite {
+        let mut app = App::default();
+        let code_id = app.store_code(contract_oil_reserve());
+
+        let msg = self.instantiate_msg(app.block_info().time);
+        let contract = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked(ADMIN),
+                &msg,
+                &[],
+                "oil-reserve-token",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+
+        Suite {
+            app,
+            code_id,
+            contract,
+        }
+    }
+}
+
+pub struct Suite {
+    pub app: App,
+    pub code_id: u64,
+    pub contract: Addr,
+}
+
+impl Suite {
+    pub fn execute(&mut self, sender: &str, msg: impl Into<ExecuteMsg>) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &msg.into(),
+            &[],
+        )
+    }
+
+    pub fn record_extraction(
+        &mut self,
+        sender: &str,
+        extraction_id: &str,
+        barrels: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            OilReserveExecuteMsg::RecordExtraction {
+                extraction_id: Some(extraction_id.to_string()),
+                barrels_extracted: barrels.into(),
+                extraction_method: ExtractionMethod::HorizontalDrilling,
+                environmental_impact_score: Decimal::percent(4_200),
+                carbon_footprint_per_barrel: Decimal::percent(43),
+                extraction_cost_per_barrel: Decimal::percent(3_500),
+                quality_certificate_url: format!("https://assay.example/{extraction_id}"),
+            },
+        )
+    }
+
+    pub fn conduct_reserve_audit(
+        &mut self,
+        sender: &str,
+        audit_id: &str,
+        audited_reserves: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            OilReserveExecuteMsg::ConductReserveAudit {
+                audit_id: Some(audit_id.to_string()),
+                audited_reserves: audited_reserves.into(),
+                audit_report_url: format!("https://audit.example/{audit_id}"),
+                reserve_quality_grade: "A".to_string(),
+                extraction_feasibility_score: Decimal::percent(8_500),
+            },
+        )
+    }
+
+    pub fn update_audit_status(
+        &mut self,
+        sender: &str,
+        audit_id: &str,
+        status: AuditStatus,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            OilReserveExecuteMsg::UpdateAuditStatus {
+                audit_id: audit_id.to_string(),
+                status,
+            },
+        )
+    }
+
+    pub fn record_trade(
+        &mut self,
+        sender: &str,
+        trade_id: &str,
+        seller: &str,
+        buyer: &str,
+        tokens: u128,
+        price_per_token: Decimal,
+    ) -> AnyResult<AppResponse> {
+        let settlement_date = self.app.block_info().time.plus_seconds(2 * 24 * 60 * 60);
+        self.execute(
+            sender,
+            OilReserveExecuteMsg::RecordTrade {
+                trade_id: Some(trade_id.to_string()),
+                seller: seller.to_string(),
+                buyer: buyer.to_string(),
+                tokens_traded: tokens.into(),
+                price_per_token,
+                trade_type: TradeType::Spot,
+                settlement_date,
+            },
+        )
+    }
+
+    pub fn update_trade_status(
+        &mut self,
+        sender: &str,
+        trade_id: &str,
+        status: TradeStatus,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            OilReserveExecuteMsg::UpdateTradeStatus {
+                trade_id: trade_id.to_string(),
+                status,
+            },
+        )
+    }
+
+    pub fn transfer(
+        &mut self,
+        sender: &str,
+        recipient: &str,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            ExecuteMsg::Cw20(Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: amount.into(),
+            }),
+        )
+    }
+
+    pub fn burn(&mut self, sender: &str, amount: u128) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            ExecuteMsg::Cw20(Cw20ExecuteMsg::Burn {
+                amount: amount.into(),
+            }),
+        )
+    }
+
+    pub fn grant_role(
+        &mut self,
+        sender: &str,
+        role: &str,
+        address: &str,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            RoleExecuteMsg::GrantRole {
+                role: role.to_string(),
+                address: address.to_string(),
+            },
+        )
+    }
+
+    pub fn revoke_role(
+        &mut self,
+        sender: &str,
+        role: &str,
+        address: &str,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            RoleExecuteMsg::RevokeRole {
+                role: role.to_string(),
+                address: address.to_string(),
+            },
+        )
+    }
+
+    pub fn pause(
+        &mut self,
+        sender: &str,
+        categories: Option<Vec<PauseCategory>>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(sender, PauseExecuteMsg::Pause { categories })
+    }
+
+    pub fn unpause(
+        &mut self,
+        sender: &str,
+        categories: Option<Vec<PauseCategory>>,
+    ) -> AnyResult<AppResponse> {
+        self.execute(sender, PauseExecuteMsg::Unpause { categories })
+    }
+
+    pub fn migrate(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.app.migrate_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &MigrateMsg {},
+            self.code_id,
+        )
+    }
+
+    pub fn block_time(&self) -> Timestamp {
+        self.app.block_info().time
+    }
+
+    fn query<T: serde::de::DeserializeOwned>(&self, msg: impl Into<QueryMsg>) -> T {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &msg.into())
+            .unwrap()
+    }
+
+    pub fn oil_reserve_info(&self) -> OilReserveInfo {
+        self.query(OilReserveQueryMsg::OilReserveInfo {})
+    }
+
+    pub fn extraction_record(&self, extraction_id: &str) -> ExtractionRecord {
+        self.query(OilReserveQueryMsg::ExtractionRecord {
+            extraction_id: extraction_id.to_string(),
+        })
+    }
+
+    pub fn all_extraction_records(
+        &self,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<(String, ExtractionRecord)> {
+        self.query(OilReserveQueryMsg::AllExtractionRecords {
+            start_after: start_after.map(str::to_string),
+            limit,
+        })
+    }
+
+    pub fn reserve_audit(&self, audit_id: &str) -> ReserveAudit {
+        self.query(OilReserveQueryMsg::ReserveAudit {
+            audit_id: audit_id.to_string(),
+        })
+    }
+
+    pub fn all_reserve_audits(
+        &self,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<(String, ReserveAudit)> {
+        self.query(OilReserveQueryMsg::AllReserveAudits {
+            start_after: start_after.map(str::to_string),
+            limit,
+        })
+    }
+
+    pub fn trading_record(&self, trade_id: &str) -> TradingRecord {
+        self.query(OilReserveQueryMsg::TradingRecord {
+            trade_id: trade_id.to_string(),
+        })
+    }
+
+    pub fn all_trading_records(
+        &self,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> Vec<(String, TradingRecord)> {
+        self.query(OilReserveQueryMsg::AllTradingRecords {
+            start_after: start_after.map(str::to_string),
+            limit,
+        })
+    }
+
+    pub fn available_barrels(&self) -> Uint128 {
+        self.query(OilReserveQueryMsg::AvailableBarrels {})
+    }
+
+    pub fn extracted_barrels(&self) -> Uint128 {
+        self.query(OilReserveQueryMsg::ExtractedBarrels {})
+    }
+
+    pub fn reserve_quality_metrics(&self) -> QualityMetricsResponse {
+        self.query(OilReserveQueryMsg::ReserveQualityMetrics {})
+    }
+
+    pub fn reconcile_supply(&self) -> ReconcileSupplyResponse {
+        self.query(OilReserveQueryMsg::ReconcileSupply {})
+    }
+
+    pub fn role_members(&self, role: &str) -> Vec<Addr> {
+        let res: RoleMembersResponse = self.query(RoleQueryMsg::RoleMembers {
+            role: role.to_string(),
+            start_after: None,
+            limit: None,
+        });
+        res.members
+    }
+
+    pub fn pause_status(&self) -> Vec<PauseCategory> {
+        let res: PauseStatusResponse = self.query(PauseQueryMsg::PauseStatus {});
+        res.paused
+    }
+
+    pub fn balance(&self, address: &str) -> Uint128 {
+        let res: BalanceResponse = self.query(QueryMsg::Cw20(Cw20QueryMsg::Balance {
+            address: address.to_string(),
+        }));
+        res.balance
+    }
+
+    pub fn total_supply(&self) -> Uint128 {
+        let res: TokenInfoResponse = self.query(QueryMsg::Cw20(Cw20QueryMsg::TokenInfo {}));
+        res.total_supply
+    }
+}
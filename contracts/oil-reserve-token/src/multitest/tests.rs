@@ -0,0 +1,377 @@
+use cosmwasm_std::{from_json, Addr, Decimal, Uint128};
+use rwa_common::pause::GUARDIAN;
+use rwa_common::{CommonError, PauseCategory};
+
+use super::suite::{SuiteBuilder, ADMIN, AUDITOR, OPERATOR, REGULATOR};
+use crate::error::ContractError;
+use crate::msg::OilReserveExecuteMsg;
+use crate::state::{AuditStatus, OilType, TradeStatus, RESERVE_AUDITOR};
+
+#[test]
+fn record_extraction_mints_and_draws_down_reserves() {
+    let mut suite = SuiteBuilder::new().with_reserves(10_000).build();
+
+    suite.record_extraction(OPERATOR, "e-1", 2_500).unwrap();
+
+    let info = suite.oil_reserve_info();
+    assert_eq!(info.extracted_barrels, Uint128::new(2_500));
+    assert_eq!(info.available_barrels, Uint128::new(7_500));
+    assert_eq!(suite.extracted_barrels(), Uint128::new(2_500));
+    assert_eq!(suite.available_barrels(), Uint128::new(7_500));
+
+    assert_eq!(suite.balance(OPERATOR), Uint128::new(2_500));
+    assert_eq!(suite.total_supply(), Uint128::new(2_500));
+
+    let record = suite.extraction_record("e-1");
+    assert_eq!(record.barrels_extracted, Uint128::new(2_500));
+    assert_eq!(record.extraction_company, Addr::unchecked(OPERATOR));
+    assert_eq!(record.extraction_date, suite.block_time());
+}
+
+#[test]
+fn record_extraction_scales_by_barrels_per_token() {
+    let mut suite = SuiteBuilder::new()
+        .with_barrels_per_token(Decimal::percent(50))
+        .build();
+
+    suite.record_extraction(OPERATOR, "e-1", 1_000).unwrap();
+    assert_eq!(suite.total_supply(), Uint128::new(500));
+    assert_eq!(suite.extracted_barrels(), Uint128::new(1_000));
+}
+
+#[test]
+fn record_extraction_requires_extraction_company() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite.record_extraction(AUDITOR, "e-1", 100).unwrap_err();
+    assert_eq!(
+        ContractError::NotExtractionCompany {
+            action: "record extractions".to_string()
+        },
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.total_supply(), Uint128::zero());
+}
+
+#[test]
+fn record_extraction_cannot_exceed_available_barrels() {
+    let mut suite = SuiteBuilder::new().with_reserves(1_000).build();
+    suite.record_extraction(OPERATOR, "e-1", 800).unwrap();
+
+    let err = suite.record_extraction(OPERATOR, "e-2", 500).unwrap_err();
+    assert_eq!(
+        ContractError::ExceedsAvailableBarrels {
+            requested: Uint128::new(500),
+            available: Uint128::new(200),
+        },
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.extracted_barrels(), Uint128::new(800));
+}
+
+#[test]
+fn record_extraction_respects_mint_cap() {
+    let mut suite = SuiteBuilder::new()
+        .with_balance(OPERATOR, 900)
+        .with_mint_cap(1_000)
+        .build();
+
+    let err = suite.record_extraction(OPERATOR, "e-1", 200).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::MintCapExceeded {
+            amount: Uint128::new(200),
+            cap: Uint128::new(1_000),
+        }),
+        err.downcast().unwrap()
+    );
+
+    // The reserve counters are rolled back with the failed mint
+    assert_eq!(suite.extracted_barrels(), Uint128::zero());
+}
+
+#[test]
+fn reserve_audits() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let err = suite
+        .conduct_reserve_audit(OPERATOR, "a-1", 950_000)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotReserveAuditor {
+            action: "conduct audits".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .conduct_reserve_audit(AUDITOR, "a-1", 950_000)
+        .unwrap();
+    let audit = suite.reserve_audit("a-1");
+    assert_eq!(audit.audited_reserves, Uint128::new(950_000));
+    assert_eq!(audit.auditor, Addr::unchecked(AUDITOR));
+    assert_eq!(audit.audit_status, AuditStatus::Pending);
+
+    let err = suite
+        .update_audit_status(REGULATOR, "a-1", AuditStatus::Approved)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotReserveAuditor {
+            action: "update audit status".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .update_audit_status(AUDITOR, "a-2", AuditStatus::Approved)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ReserveAuditNotFound {
+            audit_id: "a-2".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .update_audit_status(AUDITOR, "a-1", AuditStatus::Approved)
+        .unwrap();
+    assert_eq!(
+        suite.reserve_audit("a-1").audit_status,
+        AuditStatus::Approved
+    );
+}
+
+#[test]
+fn several_auditors_share_the_role() {
+    let mut suite = SuiteBuilder::new().build();
+    assert_eq!(
+        suite.role_members(RESERVE_AUDITOR),
+        vec![Addr::unchecked(AUDITOR)]
+    );
+
+    suite
+        .grant_role(ADMIN, RESERVE_AUDITOR, "second-auditor")
+        .unwrap();
+    suite
+        .conduct_reserve_audit(AUDITOR, "a-1", 950_000)
+        .unwrap();
+    suite
+        .conduct_reserve_audit("second-auditor", "a-2", 940_000)
+        .unwrap();
+
+    // Either auditor can approve the other's audit
+    suite
+        .update_audit_status("second-auditor", "a-1", AuditStatus::Approved)
+        .unwrap();
+    assert_eq!(
+        suite.reserve_audit("a-1").audit_status,
+        AuditStatus::Approved
+    );
+    assert_eq!(
+        suite.reserve_audit("a-2").auditor,
+        Addr::unchecked("second-auditor")
+    );
+
+    // Once revoked, the original auditor is locked out
+    suite.revoke_role(ADMIN, RESERVE_AUDITOR, AUDITOR).unwrap();
+    let err = suite
+        .conduct_reserve_audit(AUDITOR, "a-3", 930_000)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotReserveAuditor {
+            action: "conduct audits".to_string()
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn paused_issuance_stops_extraction() {
+    let mut suite = SuiteBuilder::new().build();
+    suite.grant_role(ADMIN, GUARDIAN, "guardian").unwrap();
+    suite
+        .pause("guardian", Some(vec![PauseCategory::Issuance]))
+        .unwrap();
+    assert_eq!(suite.pause_status(), [PauseCategory::Issuance]);
+
+    let err = suite.record_extraction(OPERATOR, "e-1", 1_000).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::Paused {
+            category: PauseCategory::Issuance
+        }),
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.total_supply(), Uint128::zero());
+
+    // Audits are record keeping and carry on
+    suite
+        .conduct_reserve_audit(AUDITOR, "a-1", 950_000)
+        .unwrap();
+
+    suite.unpause(ADMIN, None).unwrap();
+    suite.record_extraction(OPERATOR, "e-1", 1_000).unwrap();
+    assert_eq!(suite.total_supply(), Uint128::new(1_000));
+}
+
+#[test]
+fn trades() {
+    let mut suite = SuiteBuilder::new().build();
+
+    suite
+        .record_trade(
+            OPERATOR,
+            "t-1",
+            OPERATOR,
+            "refinery",
+            400,
+            Decimal::percent(7_850),
+        )
+        .unwrap();
+    let trade = suite.trading_record("t-1");
+    assert_eq!(trade.seller, Addr::unchecked(OPERATOR));
+    assert_eq!(trade.buyer, Addr::unchecked("refinery"));
+    assert_eq!(trade.total_value, Decimal::from_ratio(31_400u128, 1u128));
+    assert_eq!(trade.trade_status, TradeStatus::Pending);
+
+    let err = suite
+        .update_trade_status(OPERATOR, "t-2", TradeStatus::Settled)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::TradingRecordNotFound {
+            trade_id: "t-2".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .update_trade_status(OPERATOR, "t-1", TradeStatus::Settled)
+        .unwrap();
+    assert_eq!(
+        suite.trading_record("t-1").trade_status,
+        TradeStatus::Settled
+    );
+}
+
+#[test]
+fn reserve_quality_metrics() {
+    let suite = SuiteBuilder::new().build();
+
+    let metrics = suite.reserve_quality_metrics();
+    assert_eq!(metrics.oil_type, OilType::LightSweet);
+    assert_eq!(metrics.api_gravity, Decimal::percent(3_900));
+    assert_eq!(metrics.sulfur_content, Decimal::percent(20));
+}
+
+#[test]
+fn record_queries_paginate() {
+    let mut suite = SuiteBuilder::new().build();
+    for id in ["e-1", "e-2", "e-3"] {
+        suite.record_extraction(OPERATOR, id, 10).unwrap();
+    }
+    for id in ["a-1", "a-2"] {
+        suite.conduct_reserve_audit(AUDITOR, id, 1_000).unwrap();
+    }
+    for id in ["t-1", "t-2"] {
+        suite
+            .record_trade(OPERATOR, id, OPERATOR, "refinery", 1, Decimal::one())
+            .unwrap();
+    }
+
+    let page = suite.all_extraction_records(None, Some(2));
+    let ids: Vec<_> = page.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["e-1", "e-2"]);
+
+    let page = suite.all_extraction_records(Some("e-2"), None);
+    let ids: Vec<_> = page.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["e-3"]);
+
+    let page = suite.all_reserve_audits(Some("a-1"), None);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].1.audit_id, "a-2");
+
+    let page = suite.all_trading_records(None, Some(1));
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].1.trade_id, "t-1");
+}
+
+#[test]
+fn record_ids_cannot_be_reused() {
+    let mut suite = SuiteBuilder::new().build();
+    suite.record_extraction(OPERATOR, "e-1", 100).unwrap();
+    suite.conduct_reserve_audit(AUDITOR, "a-1", 1_000).unwrap();
+    suite
+        .record_trade(OPERATOR, "t-1", OPERATOR, "refinery", 10, Decimal::one())
+        .unwrap();
+
+    let err = suite.record_extraction(OPERATOR, "e-1", 300).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::DuplicateRecordId {
+            id: "e-1".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.extracted_barrels(), Uint128::new(100));
+    assert_eq!(suite.total_supply(), Uint128::new(100));
+
+    let err = suite.conduct_reserve_audit(AUDITOR, "a-1", 5).unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::DuplicateRecordId {
+            id: "a-1".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .record_trade(OPERATOR, "t-1", OPERATOR, "refinery", 99, Decimal::one())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Common(CommonError::DuplicateRecordId {
+            id: "t-1".to_string()
+        }),
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.trading_record("t-1").tokens_traded, Uint128::new(10));
+}
+
+#[test]
+fn record_ids_are_generated_when_omitted() {
+    let mut suite = SuiteBuilder::new().build();
+
+    for expected in ["00000000000000000001", "00000000000000000002"] {
+        let res = suite
+            .execute(
+                AUDITOR,
+                OilReserveExecuteMsg::ConductReserveAudit {
+                    audit_id: None,
+                    audited_reserves: Uint128::new(1_000),
+                    audit_report_url: "https://audit.example/report".to_string(),
+                    reserve_quality_grade: "B".to_string(),
+                    extraction_feasibility_score: Decimal::percent(7_000),
+                },
+            )
+            .unwrap();
+        let id: String = from_json(res.data.unwrap()).unwrap();
+        assert_eq!(id, expected);
+        assert_eq!(suite.reserve_audit(&id).reserve_quality_grade, "B");
+    }
+}
+
+#[test]
+fn cw20_messages_pass_through() {
+    let mut suite = SuiteBuilder::new().build();
+    suite.record_extraction(OPERATOR, "e-1", 1_000).unwrap();
+
+    suite.transfer(OPERATOR, "refinery", 400).unwrap();
+    assert_eq!(suite.balance(OPERATOR), Uint128::new(600));
+    assert_eq!(suite.balance("refinery"), Uint128::new(400));
+    assert_eq!(suite.total_supply(), Uint128::new(1_000));
+}
+
+#[test]
+fn migrate_keeps_state() {
+    let mut suite = SuiteBuilder::new().build();
+    suite.record_extraction(OPERATOR, "e-1", 1_000).unwrap();
+    let info = suite.oil_reserve_info();
+
+    suite.migrate(ADMIN).unwrap();
+    assert_eq!(suite.oil_reserve_info(), info);
+}
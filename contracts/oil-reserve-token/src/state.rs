@@ -0,0 +1,127 @@
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map};
+use rwa_common::pause::GUARDIAN;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// Custom state for oil reserve specific data
+pub const OIL_RESERVE_INFO: Item<OilReserveInfo> = Item::new("oil_reserve_info");
+pub const EXTRACTION_RECORDS: Map<&str, ExtractionRecord> = Map::new("extraction_records");
+pub const RESERVE_AUDITS: Map<&str, ReserveAudit> = Map::new("reserve_audits");
+pub const TRADING_RECORDS: Map<&str, TradingRecord> = Map::new("trading_records");
+
+// Roles kept in the shared role registry
+pub const EXTRACTION_COMPANY: &str = "extraction_company";
+pub const RESERVE_AUDITOR: &str = "reserve_auditor";
+pub const GOVERNMENT_AUTHORITY: &str = "government_authority";
+pub const ROLES: &[&str] = &[
+    EXTRACTION_COMPANY,
+    RESERVE_AUDITOR,
+    GOVERNMENT_AUTHORITY,
+    GUARDIAN,
+];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OilReserveInfo {
+    pub reserve_id: String,
+    pub reserve_name: String,
+    pub location: String, // Country/Region
+    pub field_name: String,
+    pub oil_type: OilType,
+    pub api_gravity: Decimal,    // API gravity of the oil
+    pub sulfur_content: Decimal, // Sulfur content percentage
+    pub total_reserves_barrels: Uint128,
+    pub extracted_barrels: Uint128,
+    pub available_barrels: Uint128,
+    pub barrels_per_token: Decimal, // How many barrels each token represents
+    // Initial holders of the extraction_company, reserve_auditor and
+    // government_authority roles; later changes are made in the role registry
+    pub extraction_company: Addr,
+    pub reserve_auditor: Addr,
+    pub government_authority: Addr,
+    pub extraction_start_date: Timestamp,
+    pub estimated_extraction_end_date: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum OilType {
+    LightSweet,
+    LightSour,
+    HeavySweet,
+    HeavySour,
+    ExtraHeavy,
+    Condensate,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExtractionRecord {
+    pub extraction_id: String,
+    pub extraction_date: Timestamp,
+    pub barrels_extracted: Uint128,
+    pub extraction_method: ExtractionMethod,
+    pub extraction_company: Addr,
+    pub environmental_impact_score: Decimal,  // 0-100 scale
+    pub carbon_footprint_per_barrel: Decimal, // CO2 emissions per barrel
+    pub extraction_cost_per_barrel: Decimal,  // Cost in USD per barrel
+    pub quality_certificate_url: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ExtractionMethod {
+    ConventionalDrilling,
+    HydraulicFracturing,
+    SteamInjection,
+    HorizontalDrilling,
+    OffshoreDrilling,
+    EnhancedOilRecovery,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReserveAudit {
+    pub audit_id: String,
+    pub audit_date: Timestamp,
+    pub auditor: Addr,
+    pub audited_reserves: Uint128,
+    pub audit_report_url: String,
+    pub audit_status: AuditStatus,
+    pub reserve_quality_grade: String,         // A, B, C grade
+    pub extraction_feasibility_score: Decimal, // 0-100 scale
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum AuditStatus {
+    Pending,
+    Approved,
+    Rejected,
+    RequiresReview,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TradingRecord {
+    pub trade_id: String,
+    pub trade_date: Timestamp,
+    pub seller: Addr,
+    pub buyer: Addr,
+    pub tokens_traded: Uint128,
+    pub price_per_token: Decimal, // Price in USD
+    pub total_value: Decimal,
+    pub trade_type: TradeType,
+    pub settlement_date: Timestamp,
+    pub trade_status: TradeStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum TradeType {
+    Spot,
+    Forward,
+    Futures,
+    Swap,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum TradeStatus {
+    Pending,
+    Executed,
+    Settled,
+    Cancelled,
+}
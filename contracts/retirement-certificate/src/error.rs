@@ -0,0 +1,22 @@
+//! Every variant renders as `<Code>: <description>`, where `<Code>` is the
+//! variant name. Codes are part of the contract's interface and never change
+//! meaning; clients should match on the part before the first colon.
+
+use cosmwasm_std::StdError;
+use rwa_common::CommonError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("Std: {0}")]
+    Std(#[from] StdError),
+
+    #[error("Cw721Base: {0}")]
+    Cw721Base(#[from] cw721_base::ContractError),
+
+    #[error(transparent)]
+    Common(#[from] CommonError),
+
+    #[error("Soulbound: retirement certificates cannot be transferred, approved or burned")]
+    Soulbound {},
+}
@@ -0,0 +1,16 @@
+//! A soulbound CW721 collection of carbon credit retirement certificates.
+//!
+//! The carbon credit contract instantiates the collection as its minter and
+//! mints one certificate per retirement, under the retirement id. Certificates
+//! can never be transferred, approved or burned.
+
+pub mod contract;
+pub mod error;
+pub mod msg;
+
+#[cfg(test)]
+mod multitest;
+
+pub use crate::contract::{execute, instantiate, migrate, query};
+pub use crate::error::ContractError;
+pub use crate::msg::{CertificateMetadata, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
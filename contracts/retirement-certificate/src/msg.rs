@@ -0,0 +1,27 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Empty, Uint128};
+
+// The collection speaks the standard CW721 messages; only the movement of
+// certificates is refused
+pub type InstantiateMsg = cw721_base::InstantiateMsg;
+pub type ExecuteMsg = cw721_base::ExecuteMsg<CertificateMetadata, Empty>;
+pub type QueryMsg = cw721_base::QueryMsg<Empty>;
+
+/// What a certificate attests: the credits retired and who they were retired
+/// for.
+#[cw_serde]
+pub struct CertificateMetadata {
+    pub retirement_id: String,
+    pub project_id: String,
+    /// Vintages of the batches the retired credits came from
+    pub vintage_years: Vec<u32>,
+    pub credits: Uint128,
+    /// Tonnes of CO2 equivalent the credits stand for
+    pub tco2e: Decimal,
+    /// Who the retirement is claimed for
+    pub beneficiary: String,
+    pub retirement_purpose: String,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,145 @@
+//! The collection mints through its minter only and never lets a certificate
+//! move.
+
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
+use cw721::{NftInfoResponse, OwnerOfResponse};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+use crate::error::ContractError;
+use crate::msg::{CertificateMetadata, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+const MINTER: &str = "carbon-credit-token";
+const HOLDER: &str = "holder";
+
+fn contract_retirement_certificate() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_migrate(crate::contract::migrate);
+    Box::new(contract)
+}
+
+fn metadata() -> CertificateMetadata {
+    CertificateMetadata {
+        retirement_id: "r-1".to_string(),
+        project_id: "VCS-674".to_string(),
+        vintage_years: vec![2023],
+        credits: Uint128::new(40),
+        tco2e: Decimal::percent(4_000),
+        beneficiary: HOLDER.to_string(),
+        retirement_purpose: "2024 scope 1 offset".to_string(),
+    }
+}
+
+fn mint_msg() -> ExecuteMsg {
+    ExecuteMsg::Mint {
+        token_id: "r-1".to_string(),
+        owner: HOLDER.to_string(),
+        token_uri: Some("https://registry.example/r-1".to_string()),
+        extension: metadata(),
+    }
+}
+
+/// A collection minted by `MINTER`, holding certificate `r-1` of `HOLDER`.
+fn setup() -> (App, Addr) {
+    let mut app = App::default();
+    let code_id = app.store_code(contract_retirement_certificate());
+    let collection = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(MINTER),
+            &InstantiateMsg {
+                name: "Rimba Raya Retirement Certificates".to_string(),
+                symbol: "RRCC-RC".to_string(),
+                minter: MINTER.to_string(),
+            },
+            &[],
+            "retirement-certificate",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(MINTER),
+        collection.clone(),
+        &mint_msg(),
+        &[],
+    )
+    .unwrap();
+    (app, collection)
+}
+
+#[test]
+fn only_the_minter_mints_certificates() {
+    let (mut app, collection) = setup();
+
+    let info: NftInfoResponse<CertificateMetadata> = app
+        .wrap()
+        .query_wasm_smart(
+            &collection,
+            &QueryMsg::NftInfo {
+                token_id: "r-1".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(info.extension, metadata());
+    assert_eq!(
+        info.token_uri.as_deref(),
+        Some("https://registry.example/r-1")
+    );
+
+    let mut msg = mint_msg();
+    if let ExecuteMsg::Mint { token_id, .. } = &mut msg {
+        *token_id = "r-2".to_string();
+    }
+    let err = app
+        .execute_contract(Addr::unchecked(HOLDER), collection, &msg, &[])
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast().unwrap(),
+        ContractError::Cw721Base(cw721_base::ContractError::Ownership(_))
+    ));
+}
+
+#[test]
+fn certificates_are_soulbound() {
+    let (mut app, collection) = setup();
+
+    let refused = [
+        ExecuteMsg::TransferNft {
+            recipient: "buyer".to_string(),
+            token_id: "r-1".to_string(),
+        },
+        ExecuteMsg::Approve {
+            spender: "buyer".to_string(),
+            token_id: "r-1".to_string(),
+            expires: None,
+        },
+        ExecuteMsg::ApproveAll {
+            operator: "buyer".to_string(),
+            expires: None,
+        },
+        ExecuteMsg::Burn {
+            token_id: "r-1".to_string(),
+        },
+    ];
+    for msg in refused {
+        let err = app
+            .execute_contract(Addr::unchecked(HOLDER), collection.clone(), &msg, &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Soulbound {}, err.downcast().unwrap());
+    }
+
+    let owner: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &collection,
+            &QueryMsg::OwnerOf {
+                token_id: "r-1".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, HOLDER);
+}
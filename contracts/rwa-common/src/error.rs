@@ -0,0 +1,67 @@
+//! Failures shared by every asset contract.
+//!
+//! Error strings follow the convention used by the contract error enums: a
+//! stable `Code` (the variant name) followed by `: ` and a human readable
+//! description. Clients match on the part before the first colon.
+
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+
+use crate::pause::PauseCategory;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CommonError {
+    #[error("Std: {0}")]
+    Std(#[from] StdError),
+
+    #[error("Overflow: {0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("InsufficientBalance: needed {needed}, available {available}")]
+    InsufficientBalance { needed: Uint128, available: Uint128 },
+
+    #[error("MintCapExceeded: minting {amount} would exceed the cap of {cap}")]
+    MintCapExceeded { amount: Uint128, cap: Uint128 },
+
+    #[error("WrongContract: cannot migrate from {found} to {expected}")]
+    WrongContract { expected: String, found: String },
+
+    #[error("CannotDowngrade: stored version {stored} is newer than {new}")]
+    CannotDowngrade { stored: String, new: String },
+
+    #[error("InvalidVersion: {version} is not a semantic version")]
+    InvalidVersion { version: String },
+
+    #[error("DuplicateRecordId: a record with id {id} already exists")]
+    DuplicateRecordId { id: String },
+
+    #[error("UnknownRole: {role} is not a role of this contract")]
+    UnknownRole { role: String },
+
+    #[error("NotRoleAdmin: only the role admin can {action}")]
+    NotRoleAdmin { action: String },
+
+    #[error("NotRoleAdminOrHolder: only the role admin or the holder can {action}")]
+    NotRoleAdminOrHolder { action: String },
+
+    #[error("RoleNotHeld: {address} does not hold the {role} role")]
+    RoleNotHeld { role: String, address: String },
+
+    #[error("RoleTransferNotFound: no pending transfer of the {role} role from {from}")]
+    RoleTransferNotFound { role: String, from: String },
+
+    #[error("NotProposedHolder: only the proposed holder can accept the {role} role")]
+    NotProposedHolder { role: String },
+
+    #[error("NotPendingRoleAdmin: only the proposed admin can accept the admin role")]
+    NotPendingRoleAdmin {},
+
+    #[error("NotGuardian: only a guardian can {action}")]
+    NotGuardian { action: String },
+
+    #[error("Paused: the {category} category is paused")]
+    Paused { category: PauseCategory },
+
+    #[error("Sanctioned: {address} is on the sanction blacklist")]
+    Sanctioned { address: String },
+}
@@ -0,0 +1,37 @@
+//! Building blocks shared by the real-world asset token contracts.
+//!
+//! Every asset contract is a CW20 token with domain logic layered on top. This
+//! crate holds the parts that are identical across them: the CW20 passthrough
+//! messages and their dispatch into `cw20-base`, record ids and pagination,
+//! supply reconciliation, the role registry, circuit breakers, sanction
+//! screening, migration guards and the helpers that move supply outside of the
+//! CW20 entry points.
+
+pub mod error;
+pub mod migration;
+pub mod pagination;
+pub mod passthrough;
+pub mod pause;
+pub mod reconcile;
+pub mod records;
+pub mod response;
+pub mod roles;
+pub mod sanctions;
+pub mod token;
+
+pub use crate::error::CommonError;
+pub use crate::migration::ensure_upgrade;
+pub use crate::pagination::{paginate_records, DEFAULT_LIMIT, MAX_LIMIT};
+pub use crate::passthrough::{execute_cw20, query_cw20, Cw20ExecuteMsg, Cw20QueryMsg};
+pub use crate::pause::{
+    ensure_not_paused, execute_pause, query_pause, PauseCategory, PauseExecuteMsg, PauseQueryMsg,
+};
+pub use crate::reconcile::Drift;
+pub use crate::records::{assign_indexed_record_id, assign_record_id};
+pub use crate::response::{action_response, record_response};
+pub use crate::roles::{
+    ensure_admin, ensure_role, execute_roles, query_roles, RoleExecuteMsg, RoleQueryMsg,
+};
+pub use crate::sanctions::{
+    ensure_not_sanctioned, execute_sanctions, query_sanctions, SanctionExecuteMsg, SanctionQueryMsg,
+};
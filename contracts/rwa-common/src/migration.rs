@@ -0,0 +1,63 @@
+//! Version checks and storage rewrites shared by the `migrate` entry points.
+
+use cosmwasm_std::{StdResult, Storage};
+use cw2::get_contract_version;
+use cw_storage_plus::Item;
+use semver::Version;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::CommonError;
+
+/// Name `cw20-base` records under cw2 when it is instantiated. Contracts
+/// deployed before they recorded their own version still carry it.
+pub const CW20_BASE_CONTRACT_NAME: &str = "crates.io:cw20-base";
+
+/// Checks that the code being migrated to belongs to the same contract as the
+/// stored one and is not older, and returns the stored version.
+///
+/// Deployments that still carry the `cw20-base` name predate versioning and
+/// are reported as version `0.0.0`.
+pub fn ensure_upgrade(
+    storage: &dyn Storage,
+    contract_name: &str,
+    contract_version: &str,
+) -> Result<Version, CommonError> {
+    let stored = get_contract_version(storage)?;
+    let new_version = parse_version(contract_version)?;
+
+    if stored.contract == CW20_BASE_CONTRACT_NAME {
+        return Ok(Version::new(0, 0, 0));
+    }
+    if stored.contract != contract_name {
+        return Err(CommonError::WrongContract {
+            expected: contract_name.to_string(),
+            found: stored.contract,
+        });
+    }
+
+    let stored_version = parse_version(&stored.version)?;
+    if stored_version > new_version {
+        return Err(CommonError::CannotDowngrade {
+            stored: stored.version,
+            new: contract_version.to_string(),
+        });
+    }
+    Ok(stored_version)
+}
+
+/// Loads and saves a stored struct again, so that fields added since it was
+/// written (which must carry `#[serde(default)]`) are persisted.
+pub fn rewrite_item<T>(storage: &mut dyn Storage, item: &Item<T>) -> StdResult<()>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let value = item.load(storage)?;
+    item.save(storage, &value)
+}
+
+fn parse_version(version: &str) -> Result<Version, CommonError> {
+    Version::parse(version).map_err(|_| CommonError::InvalidVersion {
+        version: version.to_string(),
+    })
+}
@@ -0,0 +1,28 @@
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Map};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Page size used when a query does not ask for one.
+pub const DEFAULT_LIMIT: u32 = 30;
+/// Largest page size a query may ask for.
+pub const MAX_LIMIT: u32 = 30;
+
+/// Returns up to `limit` records of a string-keyed map, in key order,
+/// starting after `start_after`.
+pub fn paginate_records<'a, T>(
+    storage: &dyn Storage,
+    map: &Map<'a, &'a str, T>,
+    start_after: Option<&'a str>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, T)>>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    map.range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
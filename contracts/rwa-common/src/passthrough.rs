@@ -0,0 +1,209 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128};
+use cw20::{
+    AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse,
+    DownloadLogoResponse, Expiration, Logo, MarketingInfoResponse, MinterResponse,
+    TokenInfoResponse,
+};
+use cw20_base::contract::{execute as cw20_execute, query as cw20_query};
+use cw20_base::msg::{ExecuteMsg as BaseExecuteMsg, QueryMsg as BaseQueryMsg};
+use cw20_base::ContractError as Cw20ContractError;
+
+/// The standard CW20 execute messages every asset contract exposes unchanged.
+///
+/// Asset contracts embed this enum in their own `ExecuteMsg` through an
+/// untagged wrapper, so the JSON on the wire is identical to plain CW20.
+#[cw_serde]
+pub enum Cw20ExecuteMsg {
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+    },
+    Burn {
+        amount: Uint128,
+    },
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    Mint {
+        recipient: String,
+        amount: Uint128,
+    },
+    UpdateMinter {
+        new_minter: Option<String>,
+    },
+    UpdateMarketing {
+        project: Option<String>,
+        description: Option<String>,
+        marketing: Option<String>,
+    },
+    UploadLogo(Logo),
+}
+
+/// The standard CW20 queries every asset contract exposes unchanged.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum Cw20QueryMsg {
+    #[returns(BalanceResponse)]
+    Balance { address: String },
+    #[returns(TokenInfoResponse)]
+    TokenInfo {},
+    #[returns(Option<MinterResponse>)]
+    Minter {},
+    #[returns(AllowanceResponse)]
+    Allowance { owner: String, spender: String },
+    #[returns(AllAllowancesResponse)]
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(AllAccountsResponse)]
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(MarketingInfoResponse)]
+    MarketingInfo {},
+    #[returns(DownloadLogoResponse)]
+    DownloadLogo {},
+}
+
+impl From<Cw20ExecuteMsg> for BaseExecuteMsg {
+    fn from(msg: Cw20ExecuteMsg) -> Self {
+        match msg {
+            Cw20ExecuteMsg::Transfer { recipient, amount } => {
+                BaseExecuteMsg::Transfer { recipient, amount }
+            }
+            Cw20ExecuteMsg::Burn { amount } => BaseExecuteMsg::Burn { amount },
+            Cw20ExecuteMsg::Send {
+                contract,
+                amount,
+                msg,
+            } => BaseExecuteMsg::Send {
+                contract,
+                amount,
+                msg,
+            },
+            Cw20ExecuteMsg::IncreaseAllowance {
+                spender,
+                amount,
+                expires,
+            } => BaseExecuteMsg::IncreaseAllowance {
+                spender,
+                amount,
+                expires,
+            },
+            Cw20ExecuteMsg::DecreaseAllowance {
+                spender,
+                amount,
+                expires,
+            } => BaseExecuteMsg::DecreaseAllowance {
+                spender,
+                amount,
+                expires,
+            },
+            Cw20ExecuteMsg::TransferFrom {
+                owner,
+                recipient,
+                amount,
+            } => BaseExecuteMsg::TransferFrom {
+                owner,
+                recipient,
+                amount,
+            },
+            Cw20ExecuteMsg::SendFrom {
+                owner,
+                contract,
+                amount,
+                msg,
+            } => BaseExecuteMsg::SendFrom {
+                owner,
+                contract,
+                amount,
+                msg,
+            },
+            Cw20ExecuteMsg::Mint { recipient, amount } => {
+                BaseExecuteMsg::Mint { recipient, amount }
+            }
+            Cw20ExecuteMsg::UpdateMinter { new_minter } => {
+                BaseExecuteMsg::UpdateMinter { new_minter }
+            }
+            Cw20ExecuteMsg::UpdateMarketing {
+                project,
+                description,
+                marketing,
+            } => BaseExecuteMsg::UpdateMarketing {
+                project,
+                description,
+                marketing,
+            },
+            Cw20ExecuteMsg::UploadLogo(logo) => BaseExecuteMsg::UploadLogo(logo),
+        }
+    }
+}
+
+impl From<Cw20QueryMsg> for BaseQueryMsg {
+    fn from(msg: Cw20QueryMsg) -> Self {
+        match msg {
+            Cw20QueryMsg::Balance { address } => BaseQueryMsg::Balance { address },
+            Cw20QueryMsg::TokenInfo {} => BaseQueryMsg::TokenInfo {},
+            Cw20QueryMsg::Minter {} => BaseQueryMsg::Minter {},
+            Cw20QueryMsg::Allowance { owner, spender } => {
+                BaseQueryMsg::Allowance { owner, spender }
+            }
+            Cw20QueryMsg::AllAllowances {
+                owner,
+                start_after,
+                limit,
+            } => BaseQueryMsg::AllAllowances {
+                owner,
+                start_after,
+                limit,
+            },
+            Cw20QueryMsg::AllAccounts { start_after, limit } => {
+                BaseQueryMsg::AllAccounts { start_after, limit }
+            }
+            Cw20QueryMsg::MarketingInfo {} => BaseQueryMsg::MarketingInfo {},
+            Cw20QueryMsg::DownloadLogo {} => BaseQueryMsg::DownloadLogo {},
+        }
+    }
+}
+
+/// Forwards a standard CW20 message to `cw20-base`.
+pub fn execute_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ExecuteMsg,
+) -> Result<Response, Cw20ContractError> {
+    cw20_execute(deps, env, info, msg.into())
+}
+
+/// Forwards a standard CW20 query to `cw20-base`.
+pub fn query_cw20(deps: Deps, env: Env, msg: Cw20QueryMsg) -> StdResult<Binary> {
+    cw20_query(deps, env, msg.into())
+}
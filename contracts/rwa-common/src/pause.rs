@@ -0,0 +1,166 @@
+//! Circuit breakers shared by the asset contracts.
+//!
+//! Every state-changing operation falls into a [`PauseCategory`]. Holders of
+//! the `guardian` role can pause any set of categories, or all of them at
+//! once; only the role admin can lift a pause, so a compromised guardian key
+//! can stop the contract but not restart it.
+
+use std::fmt;
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Event, MessageInfo, Response, StdResult, Storage,
+};
+use cw_storage_plus::Item;
+
+use crate::error::CommonError;
+use crate::passthrough::Cw20ExecuteMsg;
+use crate::response::action_response;
+use crate::roles::{ensure_admin, ensure_role};
+
+/// Role allowed to pause. Every contract lists it among its roles.
+pub const GUARDIAN: &str = "guardian";
+
+/// Currently paused categories, sorted and without duplicates.
+pub const PAUSED_CATEGORIES: Item<Vec<PauseCategory>> = Item::new("paused_categories");
+
+#[cw_serde]
+#[derive(Copy, Eq, PartialOrd, Ord)]
+pub enum PauseCategory {
+    /// Moving tokens between accounts
+    Transfers,
+    /// Minting and issuing new supply
+    Issuance,
+    /// Retiring, redeeming and burning supply
+    Retirement,
+    /// Creating and updating audit records
+    RecordKeeping,
+}
+
+impl PauseCategory {
+    pub const ALL: [PauseCategory; 4] = [
+        PauseCategory::Transfers,
+        PauseCategory::Issuance,
+        PauseCategory::Retirement,
+        PauseCategory::RecordKeeping,
+    ];
+}
+
+impl fmt::Display for PauseCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PauseCategory::Transfers => "transfers",
+            PauseCategory::Issuance => "issuance",
+            PauseCategory::Retirement => "retirement",
+            PauseCategory::RecordKeeping => "record_keeping",
+        })
+    }
+}
+
+#[cw_serde]
+pub enum PauseExecuteMsg {
+    /// Pauses the given categories, or every category when omitted. Guardian
+    /// only.
+    Pause {
+        categories: Option<Vec<PauseCategory>>,
+    },
+    /// Lifts the pause on the given categories, or on every category when
+    /// omitted. Admin only.
+    Unpause {
+        categories: Option<Vec<PauseCategory>>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum PauseQueryMsg {
+    #[returns(PauseStatusResponse)]
+    PauseStatus {},
+}
+
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub paused: Vec<PauseCategory>,
+}
+
+impl Cw20ExecuteMsg {
+    /// The category a standard CW20 message is paused with, if any.
+    /// Allowance, minter and marketing updates are never paused.
+    pub fn pause_category(&self) -> Option<PauseCategory> {
+        match self {
+            Cw20ExecuteMsg::Transfer { .. }
+            | Cw20ExecuteMsg::Send { .. }
+            | Cw20ExecuteMsg::TransferFrom { .. }
+            | Cw20ExecuteMsg::SendFrom { .. } => Some(PauseCategory::Transfers),
+            Cw20ExecuteMsg::Mint { .. } => Some(PauseCategory::Issuance),
+            Cw20ExecuteMsg::Burn { .. } => Some(PauseCategory::Retirement),
+            Cw20ExecuteMsg::IncreaseAllowance { .. }
+            | Cw20ExecuteMsg::DecreaseAllowance { .. }
+            | Cw20ExecuteMsg::UpdateMinter { .. }
+            | Cw20ExecuteMsg::UpdateMarketing { .. }
+            | Cw20ExecuteMsg::UploadLogo(_) => None,
+        }
+    }
+}
+
+/// Fails with `Paused` while `category` is paused.
+pub fn ensure_not_paused(
+    storage: &dyn Storage,
+    category: PauseCategory,
+) -> Result<(), CommonError> {
+    let paused = PAUSED_CATEGORIES.may_load(storage)?.unwrap_or_default();
+    if paused.contains(&category) {
+        return Err(CommonError::Paused { category });
+    }
+    Ok(())
+}
+
+pub fn execute_pause(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: PauseExecuteMsg,
+) -> Result<Response, CommonError> {
+    let mut paused = PAUSED_CATEGORIES
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    let (action, event, categories) = match msg {
+        PauseExecuteMsg::Pause { categories } => {
+            ensure_role(deps.storage, &info.sender, &[GUARDIAN], || {
+                CommonError::NotGuardian {
+                    action: "pause".to_string(),
+                }
+            })?;
+            let categories = categories.unwrap_or_else(|| PauseCategory::ALL.to_vec());
+            paused.extend(&categories);
+            ("pause", "paused", categories)
+        }
+        PauseExecuteMsg::Unpause { categories } => {
+            ensure_admin(deps.storage, &info.sender, "unpause")?;
+            let categories = categories.unwrap_or_else(|| PauseCategory::ALL.to_vec());
+            paused.retain(|category| !categories.contains(category));
+            ("unpause", "unpaused", categories)
+        }
+    };
+    paused.sort();
+    paused.dedup();
+    PAUSED_CATEGORIES.save(deps.storage, &paused)?;
+
+    // One event per category, so indexers can follow each breaker on its own
+    let events = categories.iter().map(|category| {
+        Event::new(event)
+            .add_attribute("category", category.to_string())
+            .add_attribute("sender", info.sender.as_str())
+    });
+    Ok(action_response(action).add_events(events))
+}
+
+pub fn query_pause(deps: Deps, msg: PauseQueryMsg) -> StdResult<Binary> {
+    match msg {
+        PauseQueryMsg::PauseStatus {} => to_json_binary(&PauseStatusResponse {
+            paused: PAUSED_CATEGORIES
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        }),
+    }
+}
@@ -0,0 +1,42 @@
+use std::ops::Sub;
+
+use cosmwasm_schema::cw_serde;
+
+/// The gap between an amount implied by a contract's domain counters and the
+/// amount actually recorded, for example available credits against the CW20
+/// total supply.
+///
+/// At most one of `surplus` and `shortfall` is non-zero.
+#[cw_serde]
+pub struct Drift<T> {
+    pub expected: T,
+    pub actual: T,
+    /// How far `actual` is above `expected`.
+    pub surplus: T,
+    /// How far `actual` is below `expected`.
+    pub shortfall: T,
+}
+
+impl<T> Drift<T>
+where
+    T: Copy + Default + PartialOrd + Sub<Output = T>,
+{
+    pub fn between(expected: T, actual: T) -> Self {
+        let (surplus, shortfall) = if actual >= expected {
+            (actual - expected, T::default())
+        } else {
+            (T::default(), expected - actual)
+        };
+        Drift {
+            expected,
+            actual,
+            surplus,
+            shortfall,
+        }
+    }
+
+    /// True when the two amounts agree.
+    pub fn is_zero(&self) -> bool {
+        self.expected == self.actual
+    }
+}
@@ -0,0 +1,77 @@
+//! Ids for the audit records every contract keeps.
+
+use cosmwasm_std::Storage;
+use cw_storage_plus::{IndexList, IndexedMap, Map};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::CommonError;
+
+/// Next generated id of each record collection, keyed by its namespace.
+const RECORD_COUNTERS: Map<&[u8], u64> = Map::new("record_counters");
+
+/// Picks the id a new record of `map` is stored under.
+///
+/// A caller-supplied id is used as given and must not be taken yet. Without
+/// one, the collection's counter is advanced past any ids already taken and
+/// its value is used, zero-padded so that generated ids sort in the order
+/// they were created.
+pub fn assign_record_id<T>(
+    storage: &mut dyn Storage,
+    map: &Map<&str, T>,
+    id: Option<String>,
+) -> Result<String, CommonError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    assign_id(
+        storage,
+        map.namespace(),
+        |storage, id| map.has(storage, id),
+        id,
+    )
+}
+
+/// Picks the id a new record of the indexed map `map`, whose records are
+/// stored under `namespace`, is stored under, as [`assign_record_id`] does.
+pub fn assign_indexed_record_id<'a, T, I>(
+    storage: &mut dyn Storage,
+    map: &IndexedMap<'a, &'a str, T, I>,
+    namespace: &str,
+    id: Option<String>,
+) -> Result<String, CommonError>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    I: IndexList<T>,
+{
+    assign_id(
+        storage,
+        namespace.as_bytes(),
+        |storage, id| map.has(storage, id),
+        id,
+    )
+}
+
+fn assign_id(
+    storage: &mut dyn Storage,
+    namespace: &[u8],
+    taken: impl Fn(&dyn Storage, &str) -> bool,
+    id: Option<String>,
+) -> Result<String, CommonError> {
+    if let Some(id) = id {
+        if taken(storage, &id) {
+            return Err(CommonError::DuplicateRecordId { id });
+        }
+        return Ok(id);
+    }
+
+    let mut next = RECORD_COUNTERS.may_load(storage, namespace)?.unwrap_or(1);
+    loop {
+        let id = format!("{next:020}");
+        next += 1;
+        if !taken(storage, &id) {
+            RECORD_COUNTERS.save(storage, namespace, &next)?;
+            return Ok(id);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use cosmwasm_std::{to_json_binary, Response, StdResult};
+
+/// Starts a response tagged with the `action` attribute every handler emits.
+pub fn action_response(action: &str) -> Response {
+    Response::new().add_attribute("action", action)
+}
+
+/// Starts the response of a handler that creates a record: tagged like
+/// [`action_response`], with the record id under `id_key`, and the id as
+/// JSON in the response data so callers can learn a generated one.
+pub fn record_response(action: &str, id_key: &str, id: &str) -> StdResult<Response> {
+    Ok(action_response(action)
+        .add_attribute(id_key, id)
+        .set_data(to_json_binary(id)?))
+}
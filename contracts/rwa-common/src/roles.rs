@@ -0,0 +1,349 @@
+//! Role registry shared by the asset contracts.
+//!
+//! Every privileged action is guarded by a named role, such as
+//! `verification_body` or `paying_agent`. A role can have any number of
+//! holders. A single admin grants and revokes roles; a holder's seat can also
+//! be handed over in two steps, proposed by the admin or the holder and
+//! accepted by the new holder, and the admin role itself moves the same way.
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response,
+    StdError, StdResult, Storage,
+};
+use cw_storage_plus::{Bound, Item, Map};
+
+use crate::error::CommonError;
+use crate::pagination::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::response::action_response;
+
+/// Grants and revokes roles.
+pub const ROLE_ADMIN: Item<Addr> = Item::new("role_admin");
+/// Proposed successor of the admin, until it accepts.
+pub const PENDING_ROLE_ADMIN: Item<Addr> = Item::new("pending_role_admin");
+/// Holders of each role, keyed by role name and holder.
+pub const ROLE_MEMBERS: Map<(&str, &Addr), Empty> = Map::new("role_members");
+/// Proposed successor of a holder, keyed by role name and current holder.
+pub const PENDING_ROLE_TRANSFERS: Map<(&str, &Addr), Addr> = Map::new("pending_role_transfers");
+
+#[cw_serde]
+pub enum RoleExecuteMsg {
+    /// Adds a holder to a role. Admin only.
+    GrantRole { role: String, address: String },
+    /// Removes a holder from a role. Admin only.
+    RevokeRole { role: String, address: String },
+    /// Proposes handing `from`'s seat in a role over to `to`. Sent by the
+    /// admin or by `from`.
+    ProposeRoleTransfer {
+        role: String,
+        from: String,
+        to: String,
+    },
+    /// Completes a proposed transfer. Sent by the proposed holder.
+    AcceptRoleTransfer { role: String, from: String },
+    /// Withdraws a proposed transfer. Sent by the admin or by `from`.
+    CancelRoleTransfer { role: String, from: String },
+    /// Proposes a new admin. Admin only.
+    ProposeRoleAdmin { address: String },
+    /// Completes a proposed admin change. Sent by the proposed admin.
+    AcceptRoleAdmin {},
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum RoleQueryMsg {
+    #[returns(RoleAdminResponse)]
+    RoleAdmin {},
+    #[returns(RoleMembersResponse)]
+    RoleMembers {
+        role: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(bool)]
+    HasRole { role: String, address: String },
+    #[returns(PendingRoleTransfersResponse)]
+    PendingRoleTransfers {
+        role: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct RoleAdminResponse {
+    pub admin: Addr,
+    pub pending_admin: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct RoleMembersResponse {
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct PendingRoleTransfer {
+    pub from: Addr,
+    pub to: Addr,
+}
+
+#[cw_serde]
+pub struct PendingRoleTransfersResponse {
+    pub transfers: Vec<PendingRoleTransfer>,
+}
+
+/// Fills an empty registry with its admin and the initial holders of each
+/// role.
+pub fn seed_roles(
+    storage: &mut dyn Storage,
+    admin: &Addr,
+    holders: &[(&str, &Addr)],
+) -> StdResult<()> {
+    ROLE_ADMIN.save(storage, admin)?;
+    for (role, holder) in holders {
+        ROLE_MEMBERS.save(storage, (*role, *holder), &Empty {})?;
+    }
+    Ok(())
+}
+
+/// True once [`seed_roles`] has run. Contracts deployed before the registry
+/// existed seed it when they are migrated.
+pub fn roles_seeded(storage: &dyn Storage) -> bool {
+    ROLE_ADMIN.exists(storage)
+}
+
+/// Seeds the registry of a contract instantiated before it existed, making the
+/// contract's wasm admin the role admin. Does nothing once the registry is
+/// seeded.
+pub fn seed_legacy_roles(deps: DepsMut, env: &Env, holders: &[(&str, &Addr)]) -> StdResult<()> {
+    if roles_seeded(deps.storage) {
+        return Ok(());
+    }
+    let admin = deps
+        .querier
+        .query_wasm_contract_info(&env.contract.address)?
+        .admin
+        .ok_or_else(|| StdError::generic_err("contract has no admin to seed the role registry"))?;
+    let admin = deps.api.addr_validate(&admin)?;
+    seed_roles(deps.storage, &admin, holders)
+}
+
+pub fn has_role(storage: &dyn Storage, role: &str, address: &Addr) -> bool {
+    ROLE_MEMBERS.has(storage, (role, address))
+}
+
+/// Fails with `error` unless `sender` holds one of `roles`.
+pub fn ensure_role<E>(
+    storage: &dyn Storage,
+    sender: &Addr,
+    roles: &[&str],
+    error: impl FnOnce() -> E,
+) -> Result<(), E> {
+    if roles.iter().any(|role| has_role(storage, role, sender)) {
+        Ok(())
+    } else {
+        Err(error())
+    }
+}
+
+/// Handles a registry message. `roles` lists the role names the contract
+/// knows; granting or transferring any other name is rejected.
+pub fn execute_roles(
+    deps: DepsMut,
+    info: MessageInfo,
+    roles: &[&str],
+    msg: RoleExecuteMsg,
+) -> Result<Response, CommonError> {
+    match msg {
+        RoleExecuteMsg::GrantRole { role, address } => {
+            ensure_known_role(roles, &role)?;
+            ensure_admin(deps.storage, &info.sender, "grant roles")?;
+            let address = deps.api.addr_validate(&address)?;
+            ROLE_MEMBERS.save(deps.storage, (role.as_str(), &address), &Empty {})?;
+
+            Ok(action_response("grant_role")
+                .add_attribute("role", role)
+                .add_attribute("address", address))
+        }
+        RoleExecuteMsg::RevokeRole { role, address } => {
+            ensure_admin(deps.storage, &info.sender, "revoke roles")?;
+            let address = deps.api.addr_validate(&address)?;
+            ensure_holder(deps.storage, &role, &address)?;
+            ROLE_MEMBERS.remove(deps.storage, (role.as_str(), &address));
+            PENDING_ROLE_TRANSFERS.remove(deps.storage, (role.as_str(), &address));
+
+            Ok(action_response("revoke_role")
+                .add_attribute("role", role)
+                .add_attribute("address", address))
+        }
+        RoleExecuteMsg::ProposeRoleTransfer { role, from, to } => {
+            ensure_known_role(roles, &role)?;
+            let from = deps.api.addr_validate(&from)?;
+            let to = deps.api.addr_validate(&to)?;
+            ensure_admin_or_holder(deps.storage, &info.sender, &from, "propose role transfers")?;
+            ensure_holder(deps.storage, &role, &from)?;
+            PENDING_ROLE_TRANSFERS.save(deps.storage, (role.as_str(), &from), &to)?;
+
+            Ok(action_response("propose_role_transfer")
+                .add_attribute("role", role)
+                .add_attribute("from", from)
+                .add_attribute("to", to))
+        }
+        RoleExecuteMsg::AcceptRoleTransfer { role, from } => {
+            let from = deps.api.addr_validate(&from)?;
+            let to = PENDING_ROLE_TRANSFERS
+                .may_load(deps.storage, (role.as_str(), &from))?
+                .ok_or_else(|| CommonError::RoleTransferNotFound {
+                    role: role.clone(),
+                    from: from.to_string(),
+                })?;
+            if info.sender != to {
+                return Err(CommonError::NotProposedHolder { role });
+            }
+            PENDING_ROLE_TRANSFERS.remove(deps.storage, (role.as_str(), &from));
+            ROLE_MEMBERS.remove(deps.storage, (role.as_str(), &from));
+            ROLE_MEMBERS.save(deps.storage, (role.as_str(), &to), &Empty {})?;
+
+            Ok(action_response("accept_role_transfer")
+                .add_attribute("role", role)
+                .add_attribute("from", from)
+                .add_attribute("to", to))
+        }
+        RoleExecuteMsg::CancelRoleTransfer { role, from } => {
+            let from = deps.api.addr_validate(&from)?;
+            ensure_admin_or_holder(deps.storage, &info.sender, &from, "cancel role transfers")?;
+            if !PENDING_ROLE_TRANSFERS.has(deps.storage, (role.as_str(), &from)) {
+                return Err(CommonError::RoleTransferNotFound {
+                    role,
+                    from: from.to_string(),
+                });
+            }
+            PENDING_ROLE_TRANSFERS.remove(deps.storage, (role.as_str(), &from));
+
+            Ok(action_response("cancel_role_transfer")
+                .add_attribute("role", role)
+                .add_attribute("from", from))
+        }
+        RoleExecuteMsg::ProposeRoleAdmin { address } => {
+            ensure_admin(deps.storage, &info.sender, "propose a new admin")?;
+            let address = deps.api.addr_validate(&address)?;
+            PENDING_ROLE_ADMIN.save(deps.storage, &address)?;
+
+            Ok(action_response("propose_role_admin").add_attribute("address", address))
+        }
+        RoleExecuteMsg::AcceptRoleAdmin {} => {
+            let pending = PENDING_ROLE_ADMIN.may_load(deps.storage)?;
+            if pending.as_ref() != Some(&info.sender) {
+                return Err(CommonError::NotPendingRoleAdmin {});
+            }
+            PENDING_ROLE_ADMIN.remove(deps.storage);
+            ROLE_ADMIN.save(deps.storage, &info.sender)?;
+
+            Ok(action_response("accept_role_admin").add_attribute("address", info.sender))
+        }
+    }
+}
+
+pub fn query_roles(deps: Deps, msg: RoleQueryMsg) -> StdResult<Binary> {
+    match msg {
+        RoleQueryMsg::RoleAdmin {} => to_json_binary(&RoleAdminResponse {
+            admin: ROLE_ADMIN.load(deps.storage)?,
+            pending_admin: PENDING_ROLE_ADMIN.may_load(deps.storage)?,
+        }),
+        RoleQueryMsg::RoleMembers {
+            role,
+            start_after,
+            limit,
+        } => {
+            let start_after = start_after
+                .map(|address| deps.api.addr_validate(&address))
+                .transpose()?;
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let members = ROLE_MEMBERS
+                .prefix(role.as_str())
+                .keys(
+                    deps.storage,
+                    start_after.as_ref().map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .collect::<StdResult<_>>()?;
+            to_json_binary(&RoleMembersResponse { members })
+        }
+        RoleQueryMsg::HasRole { role, address } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_json_binary(&has_role(deps.storage, &role, &address))
+        }
+        RoleQueryMsg::PendingRoleTransfers {
+            role,
+            start_after,
+            limit,
+        } => {
+            let start_after = start_after
+                .map(|address| deps.api.addr_validate(&address))
+                .transpose()?;
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let transfers = PENDING_ROLE_TRANSFERS
+                .prefix(role.as_str())
+                .range(
+                    deps.storage,
+                    start_after.as_ref().map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .map(|item| item.map(|(from, to)| PendingRoleTransfer { from, to }))
+                .collect::<StdResult<_>>()?;
+            to_json_binary(&PendingRoleTransfersResponse { transfers })
+        }
+    }
+}
+
+fn ensure_known_role(roles: &[&str], role: &str) -> Result<(), CommonError> {
+    if roles.contains(&role) {
+        Ok(())
+    } else {
+        Err(CommonError::UnknownRole {
+            role: role.to_string(),
+        })
+    }
+}
+
+/// Fails with `NotRoleAdmin` unless `sender` is the role admin; `action` names
+/// what was attempted.
+pub fn ensure_admin(storage: &dyn Storage, sender: &Addr, action: &str) -> Result<(), CommonError> {
+    if ROLE_ADMIN.load(storage)? == *sender {
+        Ok(())
+    } else {
+        Err(CommonError::NotRoleAdmin {
+            action: action.to_string(),
+        })
+    }
+}
+
+fn ensure_admin_or_holder(
+    storage: &dyn Storage,
+    sender: &Addr,
+    holder: &Addr,
+    action: &str,
+) -> Result<(), CommonError> {
+    if sender == holder || ROLE_ADMIN.load(storage)? == *sender {
+        Ok(())
+    } else {
+        Err(CommonError::NotRoleAdminOrHolder {
+            action: action.to_string(),
+        })
+    }
+}
+
+fn ensure_holder(storage: &dyn Storage, role: &str, address: &Addr) -> Result<(), CommonError> {
+    if has_role(storage, role, address) {
+        Ok(())
+    } else {
+        Err(CommonError::RoleNotHeld {
+            role: role.to_string(),
+            address: address.to_string(),
+        })
+    }
+}
@@ -0,0 +1,263 @@
+//! Sanction screening against the chain's `x/sanction` blacklist.
+//!
+//! The module's ante handler only screens transaction signers, so tokens could
+//! still move to or from a blacklisted account through a contract. Every
+//! token movement therefore checks the accounts involved with a stargate query
+//! for the blacklist, which the chain whitelists for contracts. Screening can
+//! be switched off by the role admin, for chains without the module.
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, MessageInfo, QueryRequest, Response, StdResult, Storage,
+};
+use cw_storage_plus::Item;
+
+use crate::error::CommonError;
+use crate::passthrough::Cw20ExecuteMsg;
+use crate::response::action_response;
+use crate::roles::ensure_admin;
+
+/// Stargate path of the `x/sanction` blacklist query.
+pub const BLACKLIST_QUERY_PATH: &str = "/mantrachain.sanction.v1.Query/Blacklist";
+
+/// Whether token movements are screened. Screening is on unless switched off.
+pub const SANCTION_SCREENING: Item<bool> = Item::new("sanction_screening");
+
+/// JSON form of `mantrachain.sanction.v1.QueryBlacklistResponse`.
+#[cw_serde]
+pub struct BlacklistResponse {
+    #[serde(default)]
+    pub blacklisted_accounts: Vec<String>,
+}
+
+#[cw_serde]
+pub enum SanctionExecuteMsg {
+    /// Switches sanction screening on or off. Admin only.
+    SetSanctionScreening { enabled: bool },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum SanctionQueryMsg {
+    #[returns(SanctionScreeningResponse)]
+    SanctionScreening {},
+}
+
+#[cw_serde]
+pub struct SanctionScreeningResponse {
+    pub enabled: bool,
+}
+
+impl Cw20ExecuteMsg {
+    /// The accounts tokens move from or to under a standard CW20 message sent
+    /// by `sender`. Empty for messages that move no tokens.
+    pub fn token_parties<'a>(&'a self, sender: &'a str) -> Vec<&'a str> {
+        match self {
+            Cw20ExecuteMsg::Transfer { recipient, .. } | Cw20ExecuteMsg::Mint { recipient, .. } => {
+                vec![sender, recipient]
+            }
+            Cw20ExecuteMsg::Send { contract, .. } => vec![sender, contract],
+            Cw20ExecuteMsg::TransferFrom {
+                owner, recipient, ..
+            } => vec![sender, owner, recipient],
+            Cw20ExecuteMsg::SendFrom {
+                owner, contract, ..
+            } => vec![sender, owner, contract],
+            Cw20ExecuteMsg::Burn { .. } => vec![sender],
+            Cw20ExecuteMsg::IncreaseAllowance { .. }
+            | Cw20ExecuteMsg::DecreaseAllowance { .. }
+            | Cw20ExecuteMsg::UpdateMinter { .. }
+            | Cw20ExecuteMsg::UpdateMarketing { .. }
+            | Cw20ExecuteMsg::UploadLogo(_) => vec![],
+        }
+    }
+}
+
+pub fn sanction_screening(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(SANCTION_SCREENING.may_load(storage)?.unwrap_or(true))
+}
+
+/// Fails with `Sanctioned` if any of `addresses` is blacklisted. Queries the
+/// chain only when screening is on and there is something to screen.
+pub fn ensure_not_sanctioned(deps: Deps, addresses: &[&str]) -> Result<(), CommonError> {
+    if addresses.is_empty() || !sanction_screening(deps.storage)? {
+        return Ok(());
+    }
+    let blacklist: BlacklistResponse = deps.querier.query(&QueryRequest::Stargate {
+        path: BLACKLIST_QUERY_PATH.to_string(),
+        data: Binary::default(),
+    })?;
+    match addresses.iter().find(|address| {
+        blacklist
+            .blacklisted_accounts
+            .iter()
+            .any(|b| b.as_str() == **address)
+    }) {
+        Some(address) => Err(CommonError::Sanctioned {
+            address: address.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+pub fn execute_sanctions(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: SanctionExecuteMsg,
+) -> Result<Response, CommonError> {
+    match msg {
+        SanctionExecuteMsg::SetSanctionScreening { enabled } => {
+            ensure_admin(deps.storage, &info.sender, "configure sanction screening")?;
+            SANCTION_SCREENING.save(deps.storage, &enabled)?;
+
+            Ok(action_response("set_sanction_screening")
+                .add_attribute("enabled", enabled.to_string()))
+        }
+    }
+}
+
+pub fn query_sanctions(deps: Deps, msg: SanctionQueryMsg) -> StdResult<Binary> {
+    match msg {
+        SanctionQueryMsg::SanctionScreening {} => to_json_binary(&SanctionScreeningResponse {
+            enabled: sanction_screening(deps.storage)?,
+        }),
+    }
+}
+
+/// A mock querier that answers the blacklist query, for unit tests of code
+/// that screens accounts. Multi-test apps do not serve stargate queries.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod testing {
+    use std::marker::PhantomData;
+
+    use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{
+        from_json, to_json_binary, Empty, OwnedDeps, Querier, QuerierResult, QueryRequest,
+        SystemError, SystemResult,
+    };
+
+    use super::{BlacklistResponse, BLACKLIST_QUERY_PATH};
+
+    pub struct MockSanctionQuerier {
+        base: MockQuerier,
+        blacklist: Vec<String>,
+    }
+
+    impl MockSanctionQuerier {
+        pub fn new(blacklist: &[&str]) -> Self {
+            Self {
+                base: MockQuerier::default(),
+                blacklist: blacklist
+                    .iter()
+                    .map(|address| address.to_string())
+                    .collect(),
+            }
+        }
+
+        pub fn set_blacklist(&mut self, blacklist: &[&str]) {
+            self.blacklist = blacklist
+                .iter()
+                .map(|address| address.to_string())
+                .collect();
+        }
+    }
+
+    impl Querier for MockSanctionQuerier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            let request: QueryRequest<Empty> = match from_json(bin_request) {
+                Ok(request) => request,
+                Err(err) => {
+                    return SystemResult::Err(SystemError::InvalidRequest {
+                        error: err.to_string(),
+                        request: bin_request.into(),
+                    })
+                }
+            };
+            match request {
+                QueryRequest::Stargate { path, .. } if path == BLACKLIST_QUERY_PATH => {
+                    SystemResult::Ok(
+                        to_json_binary(&BlacklistResponse {
+                            blacklisted_accounts: self.blacklist.clone(),
+                        })
+                        .into(),
+                    )
+                }
+                _ => self.base.raw_query(bin_request),
+            }
+        }
+    }
+
+    /// Like `cosmwasm_std::testing::mock_dependencies`, with `blacklist` on the
+    /// sanction blacklist.
+    pub fn mock_dependencies_with_blacklist(
+        blacklist: &[&str],
+    ) -> OwnedDeps<MockStorage, MockApi, MockSanctionQuerier> {
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: MockSanctionQuerier::new(blacklist),
+            custom_query_type: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_info;
+    use cosmwasm_std::Addr;
+
+    use super::testing::mock_dependencies_with_blacklist;
+    use super::*;
+    use crate::roles::seed_roles;
+
+    #[test]
+    fn screens_every_party() {
+        let deps = mock_dependencies_with_blacklist(&["mallory"]);
+
+        ensure_not_sanctioned(deps.as_ref(), &["alice", "bob"]).unwrap();
+        assert_eq!(
+            ensure_not_sanctioned(deps.as_ref(), &["alice", "mallory"]).unwrap_err(),
+            CommonError::Sanctioned {
+                address: "mallory".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn token_parties_cover_both_ends() {
+        let msg = Cw20ExecuteMsg::TransferFrom {
+            owner: "owner".to_string(),
+            recipient: "recipient".to_string(),
+            amount: 1u128.into(),
+        };
+        assert_eq!(
+            msg.token_parties("spender"),
+            ["spender", "owner", "recipient"]
+        );
+
+        let msg = Cw20ExecuteMsg::IncreaseAllowance {
+            spender: "spender".to_string(),
+            amount: 1u128.into(),
+            expires: None,
+        };
+        assert!(msg.token_parties("owner").is_empty());
+    }
+
+    #[test]
+    fn admin_can_switch_screening_off() {
+        let mut deps = mock_dependencies_with_blacklist(&["mallory"]);
+        seed_roles(deps.as_mut().storage, &Addr::unchecked("admin"), &[]).unwrap();
+
+        let msg = SanctionExecuteMsg::SetSanctionScreening { enabled: false };
+        assert_eq!(
+            execute_sanctions(deps.as_mut(), mock_info("mallory", &[]), msg.clone()).unwrap_err(),
+            CommonError::NotRoleAdmin {
+                action: "configure sanction screening".to_string()
+            }
+        );
+        execute_sanctions(deps.as_mut(), mock_info("admin", &[]), msg).unwrap();
+
+        assert!(!sanction_screening(deps.as_ref().storage).unwrap());
+        ensure_not_sanctioned(deps.as_ref(), &["mallory"]).unwrap();
+    }
+}
@@ -0,0 +1,55 @@
+//! Supply changes made by domain logic rather than by a CW20 message.
+//!
+//! Retiring credits, redeeming bonds and minting against extracted barrels all
+//! move balances on behalf of a role holder, so they cannot go through the
+//! `cw20-base` entry points, which check the sender against the owner or the
+//! minter.
+
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw20_base::state::{BALANCES, TOKEN_INFO};
+
+use crate::error::CommonError;
+
+/// Removes `amount` from `owner` and from the total supply.
+pub fn burn_tokens(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    amount: Uint128,
+) -> Result<(), CommonError> {
+    let balance = BALANCES.may_load(storage, owner)?.unwrap_or_default();
+    if balance < amount {
+        return Err(CommonError::InsufficientBalance {
+            needed: amount,
+            available: balance,
+        });
+    }
+    BALANCES.save(storage, owner, &(balance - amount))?;
+
+    TOKEN_INFO.update(storage, |mut info| -> StdResult<_> {
+        info.total_supply = info.total_supply.checked_sub(amount)?;
+        Ok(info)
+    })?;
+    Ok(())
+}
+
+/// Credits `amount` to `recipient` and to the total supply, honouring the
+/// minting cap configured at instantiation.
+pub fn mint_tokens(
+    storage: &mut dyn Storage,
+    recipient: &Addr,
+    amount: Uint128,
+) -> Result<(), CommonError> {
+    let mut token_info = TOKEN_INFO.load(storage)?;
+    token_info.total_supply = token_info.total_supply.checked_add(amount)?;
+    if let Some(cap) = token_info.get_cap() {
+        if token_info.total_supply > cap {
+            return Err(CommonError::MintCapExceeded { amount, cap });
+        }
+    }
+    TOKEN_INFO.save(storage, &token_info)?;
+
+    BALANCES.update(storage, recipient, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default().checked_add(amount)?)
+    })?;
+    Ok(())
+}
@@ -1,7 +1,7 @@
 #!/bin/bash
 
 # Deploy CW20-Style Token Contracts
-# This script deploys the carbon credit, oil reserve, and bond token contracts
+# This script deploys the carbon credit, carbon pool, oil reserve, and bond token contracts
 
 set -e
 
@@ -46,7 +46,7 @@ echo -e "${GREEN}✅ Prerequisites check passed${NC}"
 # Build contracts
 echo -e "${YELLOW}🔨 Building contracts...${NC}"
 
-CONTRACTS=("carbon-credit-token" "oil-reserve-token" "bond-token")
+CONTRACTS=("retirement-certificate" "carbon-credit-token" "carbon-pool" "oil-reserve-token" "bond-token")
 WASM_FILES=()
 
 for contract in "${CONTRACTS[@]}"; do
@@ -172,6 +172,9 @@ for i in "${!CONTRACTS[@]}"; do
         "carbon-credit-token")
             echo "nnbd tx wasm instantiate $code_id '{\"cw20_base\":{\"name\":\"Carbon Credit Token\",\"symbol\":\"CCT\",\"decimals\":6,\"initial_balances\":[],\"mint\":{\"minter\":\"'\"\$ADMIN_ADDRESS\"'\"}},\"carbon_credit_info\":{\"project_id\":\"CC-001\",\"project_name\":\"Sample Project\",\"project_type\":\"renewable_energy\",\"verification_standard\":\"VCS\",\"vintage_year\":2024,\"country\":\"USA\",\"total_credits_issued\":\"0\",\"credits_retired\":\"0\",\"credits_available\":\"0\",\"co2_equivalent_per_credit\":\"1.0\",\"verification_body\":\"'\"\$VERIFICATION_BODY\"'\"}},\"project_developer\":\"'\"\$DEVELOPER\"'\"}}' --from validator --label \"Carbon Credit Token\" --admin \$ADMIN_ADDRESS"
             ;;
+        "retirement-certificate")
+            echo "Instantiated by the carbon credit token: pass \"certificate_code_id\":$code_id in its instantiate message"
+            ;;
         "oil-reserve-token")
             echo "nnbd tx wasm instantiate $code_id '{\"cw20_base\":{\"name\":\"Oil Reserve Token\",\"symbol\":\"ORT\",\"decimals\":6,\"initial_balances\":[],\"mint\":{\"minter\":\"'\"\$ADMIN_ADDRESS\"'\"}},\"oil_reserve_info\":{\"reserve_id\":\"OR-001\",\"reserve_name\":\"Sample Reserve\",\"location\":\"USA\",\"field_name\":\"Sample Field\",\"oil_type\":\"LightSweet\",\"api_gravity\":\"35.0\",\"sulfur_content\":\"0.5\",\"total_reserves_barrels\":\"1000000\",\"extracted_barrels\":\"0\",\"available_barrels\":\"1000000\",\"barrels_per_token\":\"1.0\",\"extraction_company\":\"'\"\$EXTRACTION_COMPANY\"'\"}},\"reserve_auditor\":\"'\"\$AUDITOR\"'\"}},\"government_authority\":\"'\"\$GOVERNMENT\"'\"}},\"extraction_start_date\":\"1640995200\",\"estimated_extraction_end_date\":\"1672531200\"}}' --from validator --label \"Oil Reserve Token\" --admin \$ADMIN_ADDRESS"
             ;;